@@ -0,0 +1,85 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+
+use super::TransactionCategory;
+
+// in-game days kept in the ledger
+const LEDGER_DAYS: usize = 7;
+
+#[derive(Default, Clone)]
+pub struct LedgerPeriod {
+    pub day: u64,
+    pub categories: HashMap<TransactionCategory, f64>,
+    pub sources: HashMap<Entity, f64>,
+}
+
+impl LedgerPeriod {
+    pub fn income(&self) -> f64 {
+        self.categories
+            .values()
+            .filter(|amount| **amount > 0.0)
+            .sum()
+    }
+
+    pub fn expenses(&self) -> f64 {
+        self.categories
+            .values()
+            .filter(|amount| **amount < 0.0)
+            .sum()
+    }
+
+    fn merge(&mut self, other: &LedgerPeriod) {
+        for (category, amount) in other.categories.iter() {
+            *self.categories.entry(*category).or_insert(0.0) += amount;
+        }
+
+        for (source, amount) in other.sources.iter() {
+            *self.sources.entry(*source).or_insert(0.0) += amount;
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Ledger {
+    pub periods: VecDeque<LedgerPeriod>,
+}
+
+impl Ledger {
+    pub fn record(
+        &mut self,
+        day: u64,
+        category: TransactionCategory,
+        source: Option<Entity>,
+        amount: f64,
+    ) {
+        if self.periods.back().map(|period| period.day) != Some(day) {
+            self.periods.push_back(LedgerPeriod {
+                day,
+                ..Default::default()
+            });
+
+            if self.periods.len() > LEDGER_DAYS {
+                self.periods.pop_front();
+            }
+        }
+
+        let period = self.periods.back_mut().unwrap();
+        *period.categories.entry(category).or_insert(0.0) += amount;
+
+        if let Some(source) = source {
+            *period.sources.entry(source).or_insert(0.0) += amount;
+        }
+    }
+
+    // all transactions of the latest days combined
+    pub fn summary(&self, days: usize) -> LedgerPeriod {
+        let mut summary = LedgerPeriod::default();
+
+        for period in self.periods.iter().rev().take(days) {
+            summary.merge(period);
+        }
+
+        summary
+    }
+}
@@ -1,12 +1,30 @@
+mod ledger;
 #[cfg(test)]
 mod tests;
 
+pub use ledger::{Ledger, LedgerPeriod};
+
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::game::{assets::resource_specifications::ResourceSpecifications, constants::CURRENCY};
+use std::collections::HashMap;
+
+use crate::game::{
+    assets::{
+        building_specifications::BuildingSpecification,
+        resource_specifications::ResourceSpecifications,
+    },
+    constants::CURRENCY,
+    construction::UnderConstruction,
+    production::{Idle, ProductionBuilding},
+    storage::{fetch_from_storage, Storage, StorageConsolidator},
+    time::GameTime,
+};
 
 const START_VALUE: i64 = 250000;
+// share of the idle maintenance paid by mothballed buildings
+const MOTHBALL_FACTOR: f64 = 0.2;
+const SPARE_PART_MARKUP: f64 = 2.0;
 
 #[derive(Serialize, Deserialize, Clone, Default)]
 #[serde(deny_unknown_fields)]
@@ -14,20 +32,83 @@ pub struct Account {
     pub value: i64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionCategory {
+    Construction,
+    Maintenance,
+    ProductionCost,
+    Import,
+    Export,
+    Sales,
+    Vehicle,
+    Contract,
+    Loan,
+    Interest,
+}
+
+impl TransactionCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransactionCategory::Construction => "Construction",
+            TransactionCategory::Maintenance => "Maintenance",
+            TransactionCategory::ProductionCost => "Production Cost",
+            TransactionCategory::Import => "Import",
+            TransactionCategory::Export => "Export",
+            TransactionCategory::Sales => "Sales",
+            TransactionCategory::Vehicle => "Vehicle Purchase",
+            TransactionCategory::Contract => "Contracts",
+            TransactionCategory::Loan => "Loans",
+            TransactionCategory::Interest => "Interest",
+        }
+    }
+}
+
 pub struct AccountTransaction {
     pub amount: i64,
+    pub category: TransactionCategory,
+    // entity that caused the transaction, if any
+    pub source: Option<Entity>,
 }
 
+#[derive(Default)]
 pub struct MaintenanceCost {
     pub amount: f64,
+    // charged instead of amount while idle
+    pub idle_amount: Option<f64>,
+    // spare parts used up while running
+    pub resources: HashMap<String, f64>,
 }
 
 impl MaintenanceCost {
     pub fn new_from_cost(cost: i64) -> Self {
         Self {
             amount: cost as f64 * 0.00005,
+            ..Default::default()
         }
     }
+
+    pub fn new_from_specification(building: &BuildingSpecification, cost: i64) -> Self {
+        match &building.maintenance {
+            Some(maintenance) => Self {
+                amount: maintenance.active,
+                idle_amount: Some(maintenance.idle),
+                resources: maintenance.resources.clone(),
+            },
+            // idling saves as much as the specified buildings do
+            None => {
+                let maintenance = Self::new_from_cost(cost);
+
+                Self {
+                    idle_amount: Some(maintenance.amount * MOTHBALL_FACTOR),
+                    ..maintenance
+                }
+            }
+        }
+    }
+
+    pub fn idle(&self) -> f64 {
+        self.idle_amount.unwrap_or(self.amount)
+    }
 }
 
 pub fn account_transactions(
@@ -39,10 +120,68 @@ pub fn account_transactions(
     }
 }
 
-pub fn maintenance_cost(mut account: ResMut<Account>, query: Query<&MaintenanceCost>) {
+pub fn record_transactions(
+    mut ledger: ResMut<Ledger>,
+    time: Res<GameTime>,
+    mut transactions: EventReader<AccountTransaction>,
+) {
+    let day = time.day() as u64;
+    for event in transactions.iter() {
+        ledger.record(day, event.category, event.source, event.amount as f64);
+    }
+}
+
+pub fn maintenance_cost(
+    mut account: ResMut<Account>,
+    mut ledger: ResMut<Ledger>,
+    time: Res<GameTime>,
+    query: Query<
+        (
+            Entity,
+            &MaintenanceCost,
+            Option<&Idle>,
+            Option<&ProductionBuilding>,
+            Option<&StorageConsolidator>,
+        ),
+        Without<UnderConstruction>,
+    >,
+    mut storage_query: Query<&mut Storage>,
+    resources: Res<ResourceSpecifications>,
+) {
+    let day = time.day() as u64;
     let mut sum = 0.0;
-    for cost in query.iter() {
-        sum += cost.amount;
+    for (entity, cost, idle, production, consolidator) in query.iter() {
+        let amount = if production.map_or(false, |production| production.mothballed) {
+            cost.idle() * MOTHBALL_FACTOR
+        } else if idle.is_some() {
+            cost.idle()
+        } else {
+            let mut amount = cost.amount;
+
+            // spare parts missing in storage are bought at a markup
+            for (resource, rate) in cost.resources.iter() {
+                let fetched = match consolidator {
+                    Some(consolidator) => fetch_from_storage(
+                        &consolidator.inputs(),
+                        &mut storage_query,
+                        resource,
+                        *rate,
+                    ),
+                    None => false,
+                };
+
+                if !fetched {
+                    amount += resources
+                        .get(resource)
+                        .map_or(0.0, |resource| resource.cost * rate * SPARE_PART_MARKUP);
+                }
+            }
+
+            amount
+        };
+
+        sum += amount;
+        ledger.record(day, TransactionCategory::Maintenance, Some(entity), -amount);
     }
 
     account.value -= sum as i64;
@@ -52,6 +191,10 @@ pub fn reset_account(mut account: ResMut<Account>) {
     account.value = START_VALUE;
 }
 
+pub fn reset_ledger(mut ledger: ResMut<Ledger>) {
+    *ledger = Ledger::default();
+}
+
 pub trait PurchaseCost {
     fn price(&self, resources: &ResourceSpecifications) -> i64;
 
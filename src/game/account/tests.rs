@@ -1,6 +1,7 @@
 use bevy::app::Events;
 
 use super::*;
+use crate::game::assets::resource_specifications::ResourceSpecification;
 
 #[test]
 fn transaction_processing() {
@@ -15,9 +16,21 @@ fn transaction_processing() {
     let mut events = world
         .get_resource_mut::<Events<AccountTransaction>>()
         .unwrap();
-    events.send(AccountTransaction { amount: -100 });
-    events.send(AccountTransaction { amount: -100 });
-    events.send(AccountTransaction { amount: 50 });
+    events.send(AccountTransaction {
+        amount: -100,
+        category: TransactionCategory::Construction,
+        source: None,
+    });
+    events.send(AccountTransaction {
+        amount: -100,
+        category: TransactionCategory::Construction,
+        source: None,
+    });
+    events.send(AccountTransaction {
+        amount: 50,
+        category: TransactionCategory::Construction,
+        source: None,
+    });
 
     stage.run(&mut world);
 
@@ -47,11 +60,17 @@ fn maintenance_cost_emitting() {
     let mut world = World::default();
 
     world.insert_resource(Account { value: 10000 });
+    world.insert_resource(Ledger::default());
+    world.insert_resource(GameTime::default());
 
-    world.spawn().insert(MaintenanceCost { amount: 0.3 });
-    world.spawn().insert(MaintenanceCost { amount: 0.3 });
-    world.spawn().insert(MaintenanceCost { amount: 0.3 });
-    world.spawn().insert(MaintenanceCost { amount: 100.0 });
+    world.insert_resource(ResourceSpecifications::new());
+
+    for amount in &[0.3, 0.3, 0.3, 100.0] {
+        world.spawn().insert(MaintenanceCost {
+            amount: *amount,
+            ..Default::default()
+        });
+    }
 
     let mut stage = SystemStage::parallel();
     stage.add_system(maintenance_cost.system());
@@ -63,3 +82,166 @@ fn maintenance_cost_emitting() {
     // rounds by removing fractions
     assert_eq!(account.value, 10000 - 100);
 }
+
+fn maintenance_setup(resources: ResourceSpecifications) -> (World, SystemStage) {
+    let mut world = World::default();
+
+    world.insert_resource(Account { value: 10000 });
+    world.insert_resource(Ledger::default());
+    world.insert_resource(GameTime::default());
+    world.insert_resource(resources);
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(maintenance_cost.system());
+
+    (world, stage)
+}
+
+fn specified_cost() -> MaintenanceCost {
+    MaintenanceCost {
+        amount: 100.0,
+        idle_amount: Some(50.0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn maintenance_cost_depends_on_activity() {
+    let (mut world, mut stage) = maintenance_setup(ResourceSpecifications::new());
+
+    world.spawn().insert(specified_cost());
+    world
+        .spawn()
+        .insert(specified_cost())
+        .insert(Idle::default());
+    world
+        .spawn()
+        .insert(specified_cost())
+        .insert(Idle::default())
+        .insert(ProductionBuilding {
+            mothballed: true,
+            ..Default::default()
+        });
+
+    stage.run(&mut world);
+
+    let account = world.get_resource::<Account>().unwrap();
+
+    assert_eq!(account.value, 10000 - 100 - 50 - 10);
+}
+
+#[test]
+fn maintenance_consumes_spare_parts() {
+    const PARTS: &str = "parts";
+
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(
+        PARTS.to_owned(),
+        ResourceSpecification {
+            cost: 20.0,
+            ..Default::default()
+        },
+    );
+    let (mut world, mut stage) = maintenance_setup(resources);
+
+    let storage_id = world
+        .spawn()
+        .insert(Storage {
+            resource: PARTS.to_owned(),
+            amount: 1.5,
+            capacity: 10.0,
+            ..Default::default()
+        })
+        .id();
+
+    let mut parts = HashMap::new();
+    parts.insert(PARTS.to_owned(), 1.0);
+    world
+        .spawn()
+        .insert(MaintenanceCost {
+            amount: 100.0,
+            resources: parts,
+            ..Default::default()
+        })
+        .insert(StorageConsolidator {
+            connected_storage: vec![storage_id],
+            ..Default::default()
+        });
+
+    // parts are taken from storage
+    stage.run(&mut world);
+    assert_eq!(world.get_resource::<Account>().unwrap().value, 10000 - 100);
+    assert!((world.get::<Storage>(storage_id).unwrap().amount - 0.5).abs() < f64::EPSILON);
+
+    // missing parts are paid at a markup
+    stage.run(&mut world);
+    assert_eq!(
+        world.get_resource::<Account>().unwrap().value,
+        10000 - 100 - 140
+    );
+}
+
+#[test]
+fn unspecified_maintenance_is_lower_while_idle() {
+    let cost = MaintenanceCost::new_from_specification(&BuildingSpecification::default(), 100000);
+
+    assert!((cost.amount - 5.0).abs() < 1e-9);
+    assert!((cost.idle() - cost.amount * MOTHBALL_FACTOR).abs() < f64::EPSILON);
+}
+
+#[test]
+fn ledger_aggregates_by_category_and_source() {
+    let mut world = World::default();
+
+    world.insert_resource(Ledger::default());
+    world.insert_resource(GameTime::default());
+    world.insert_resource(Events::<AccountTransaction>::default());
+
+    let source = world.spawn().id();
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(record_transactions.system());
+
+    let mut events = world
+        .get_resource_mut::<Events<AccountTransaction>>()
+        .unwrap();
+    events.send(AccountTransaction {
+        amount: 300,
+        category: TransactionCategory::Export,
+        source: Some(source),
+    });
+    events.send(AccountTransaction {
+        amount: -100,
+        category: TransactionCategory::ProductionCost,
+        source: Some(source),
+    });
+    events.send(AccountTransaction {
+        amount: -50,
+        category: TransactionCategory::Vehicle,
+        source: None,
+    });
+
+    stage.run(&mut world);
+
+    let summary = world.get_resource::<Ledger>().unwrap().summary(1);
+
+    assert_eq!(summary.income() as i64, 300);
+    assert_eq!(summary.expenses() as i64, -150);
+    assert_eq!(
+        summary.sources.get(&source).map(|amount| *amount as i64),
+        Some(200)
+    );
+}
+
+#[test]
+fn ledger_keeps_recent_days() {
+    let mut ledger = Ledger::default();
+
+    for day in 0..10 {
+        ledger.record(day, TransactionCategory::Export, None, 10.0);
+    }
+
+    assert_eq!(ledger.periods.len(), 7);
+    assert_eq!(ledger.periods.front().unwrap().day, 3);
+    assert_eq!(ledger.summary(2).income() as i64, 20);
+}
@@ -4,17 +4,18 @@ use bevy_ecs_tilemap::prelude::*;
 use crate::game::{
     assets::{
         building_specifications::BuildingSpecifications,
-        resource_specifications::ResourceSpecifications, Building, Occupied, Position,
-        RequiresUpdate,
+        resource_specifications::ResourceSpecifications, Building, BuildingPart, Occupied,
+        Position, RequiresUpdate,
     },
     constants::MapTile,
     construction::UnderConstruction,
     helper::get_entity::get_entity,
     production::{
         DeliveryStation, Depot, ImportExportDirection, ImportExportStation, StorageManagement,
+        TownShop, TrainStation,
     },
     setup::{GROUND_LAYER_ID, MAP_ID},
-    storage::Storage,
+    storage::{silo_tile, Storage},
     street::Street,
 };
 
@@ -31,15 +32,30 @@ pub fn construction_update(
     }
 }
 
+// turns the texture of a tile by quarter turns clockwise
+pub fn rotate_tile(tile: &mut Tile, rotation: u8) {
+    let (flip_x, flip_y, flip_d) = match rotation % 4 {
+        1 => (true, false, true),
+        2 => (true, true, false),
+        3 => (false, true, true),
+        _ => (false, false, false),
+    };
+
+    tile.flip_x = flip_x;
+    tile.flip_y = flip_y;
+    tile.flip_d = flip_d;
+}
+
 pub fn building_update(
     mut query: Query<(&mut Tile, &Building), (With<RequiresUpdate>, Without<UnderConstruction>)>,
     buildings: Res<BuildingSpecifications>,
 ) {
     for (mut tile, building) in query.iter_mut() {
-        tile.texture_index = {
-            let building = buildings.get(&building.id).unwrap();
-            building.tile
-        };
+        tile.texture_index = buildings
+            .get(&building.id)
+            .unwrap()
+            .tile_at(UVec2::new(0, 0), building.rotation);
+        rotate_tile(&mut tile, building.rotation);
         tile.visible = true;
     }
 }
@@ -65,6 +81,18 @@ pub fn storage_update(
     resources: Res<ResourceSpecifications>,
 ) {
     for (mut tile, storage) in query.iter_mut() {
+        if storage.is_warehouse() {
+            tile.texture_index = MapTile::Warehouse as u16;
+            tile.visible = true;
+            continue;
+        }
+
+        if storage.is_silo() {
+            tile.texture_index = silo_tile(UVec2::new(0, 0));
+            tile.visible = true;
+            continue;
+        }
+
         let resource = resources
             .get(&storage.resource)
             .unwrap_or_else(|| panic!("Expected {} resource to exist", storage.resource));
@@ -74,6 +102,47 @@ pub fn storage_update(
     }
 }
 
+pub fn building_part_update(
+    mut part_query: Query<(Entity, &mut Tile, &BuildingPart, &Position)>,
+    main_query: Query<(
+        &Position,
+        Option<&Storage>,
+        Option<&Building>,
+        Option<&UnderConstruction>,
+    )>,
+    update_query: Query<(), With<RequiresUpdate>>,
+    buildings: Res<BuildingSpecifications>,
+) {
+    for (entity, mut tile, part, position) in part_query.iter_mut() {
+        if update_query.get(entity).is_err() && update_query.get(part.main).is_err() {
+            continue;
+        }
+
+        let (main_position, storage, building, under_construction) = match main_query.get(part.main)
+        {
+            Ok(main) => main,
+            Err(_) => continue,
+        };
+
+        let offset = position.position - main_position.position;
+
+        tile.texture_index = if under_construction.is_some() {
+            MapTile::Construction as u16
+        } else if let Some(building) = building {
+            rotate_tile(&mut tile, building.rotation);
+            buildings
+                .get(&building.id)
+                .unwrap()
+                .tile_at(offset, building.rotation)
+        } else if storage.map(|storage| storage.is_silo()).unwrap_or(false) {
+            silo_tile(offset)
+        } else {
+            continue;
+        };
+        tile.visible = true;
+    }
+}
+
 pub fn import_export_station_update(
     mut query: Query<
         (&mut Tile, &ImportExportStation),
@@ -105,6 +174,38 @@ pub fn delivery_station_update(
     }
 }
 
+pub fn town_shop_update(
+    mut query: Query<
+        &mut Tile,
+        (
+            With<TownShop>,
+            With<RequiresUpdate>,
+            Without<UnderConstruction>,
+        ),
+    >,
+) {
+    for mut tile in query.iter_mut() {
+        tile.texture_index = MapTile::TownShop as u16;
+        tile.visible = true;
+    }
+}
+
+pub fn train_station_update(
+    mut query: Query<
+        &mut Tile,
+        (
+            With<TrainStation>,
+            With<RequiresUpdate>,
+            Without<UnderConstruction>,
+        ),
+    >,
+) {
+    for mut tile in query.iter_mut() {
+        tile.texture_index = MapTile::TrainStation as u16;
+        tile.visible = true;
+    }
+}
+
 pub fn storage_management_update(
     mut query: Query<
         &mut Tile,
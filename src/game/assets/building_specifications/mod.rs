@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 
+use bevy::prelude::*;
 use bevy_egui::egui::Ui;
 use glob::glob;
 use serde::Deserialize;
@@ -8,9 +9,9 @@ use std::{collections::HashMap, fs::File, io::prelude::*, path::Path};
 
 use crate::game::{
     account::PurchaseCost,
-    assets::{resource_specifications::ResourceSpecifications, InfoUI},
+    assets::{resource_specifications::ResourceSpecifications, Direction, Footprint, InfoUI},
     constants::CURRENCY,
-    production::Product,
+    production::{Product, ProductDependency},
 };
 
 pub type BuildingSpecifications = HashMap<String, BuildingSpecification>;
@@ -23,6 +24,41 @@ pub struct BuildingSpecificationCost {
     pub base: f64,
 }
 
+#[derive(Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BuildingSpecificationFootprint {
+    pub width: u32,
+    pub height: u32,
+    // tile layout row by row, starting with the top row
+    pub tiles: Vec<Vec<u16>>,
+}
+
+// sides storage is consumed from and filled on, before rotating the building
+#[derive(Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BuildingSpecificationPorts {
+    pub inputs: Vec<Direction>,
+    pub outputs: Vec<Direction>,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BuildingSpecificationHousing {
+    pub residents: u32,
+    #[serde(default)]
+    pub consumption: Vec<ProductDependency>,
+}
+
+// money per production tick, spare parts are used up while running
+#[derive(Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BuildingSpecificationMaintenance {
+    pub active: f64,
+    pub idle: f64,
+    #[serde(default)]
+    pub resources: HashMap<String, f64>,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct BuildingSpecification {
@@ -31,6 +67,64 @@ pub struct BuildingSpecification {
     pub products: Vec<Product>,
     pub group: String,
     pub cost: BuildingSpecificationCost,
+    #[serde(default)]
+    pub footprint: Option<BuildingSpecificationFootprint>,
+    #[serde(default)]
+    pub ports: Option<BuildingSpecificationPorts>,
+    #[serde(default)]
+    pub workers: u32,
+    #[serde(default)]
+    pub housing: Option<BuildingSpecificationHousing>,
+    #[serde(default)]
+    pub maintenance: Option<BuildingSpecificationMaintenance>,
+}
+
+impl BuildingSpecification {
+    pub fn footprint(&self) -> Option<Footprint> {
+        self.footprint.as_ref().map(|footprint| Footprint {
+            size: UVec2::new(footprint.width, footprint.height),
+        })
+    }
+
+    pub fn rotated_footprint(&self, rotation: u8) -> Option<Footprint> {
+        self.footprint()
+            .map(|footprint| footprint.rotated(rotation))
+    }
+
+    // tile at the given offset from the lower left corner of the building turned clockwise
+    pub fn tile_at(&self, offset: UVec2, rotation: u8) -> u16 {
+        let footprint = match &self.footprint {
+            Some(footprint) => footprint,
+            None => return self.tile,
+        };
+        let (width, height) = (footprint.width, footprint.height);
+
+        // offset in the layout before it was turned
+        let offset = match rotation % 4 {
+            1 => width
+                .checked_sub(offset.y + 1)
+                .map(|x| UVec2::new(x, offset.x)),
+            2 => width
+                .checked_sub(offset.x + 1)
+                .zip(height.checked_sub(offset.y + 1))
+                .map(|(x, y)| UVec2::new(x, y)),
+            3 => height
+                .checked_sub(offset.x + 1)
+                .map(|y| UVec2::new(offset.y, y)),
+            _ => Some(offset),
+        };
+        let offset = match offset {
+            Some(offset) if offset.y < height => offset,
+            _ => return self.tile,
+        };
+
+        footprint
+            .tiles
+            .get((footprint.height - 1 - offset.y) as usize)
+            .and_then(|row| row.get(offset.x as usize))
+            .cloned()
+            .unwrap_or(self.tile)
+    }
 }
 
 impl PurchaseCost for BuildingSpecification {
@@ -67,6 +161,10 @@ impl PurchaseCost for BuildingSpecification {
 impl InfoUI for BuildingSpecification {
     fn ui(&self, ui: &mut Ui, _resources: &ResourceSpecifications) {
         ui.label(&self.name);
+
+        if self.workers > 0 {
+            ui.label(format!("Workers needed: {}", self.workers));
+        }
     }
 }
 
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
+use bevy::prelude::*;
+
 use crate::game::{
     account::PurchaseCost,
     assets::{
-        building_specifications::BuildingSpecificationCost,
+        building_specifications::{BuildingSpecificationCost, BuildingSpecificationFootprint},
         resource_specifications::{ResourceSpecification, ResourceSpecifications},
     },
 };
@@ -67,3 +69,63 @@ fn purchase_cost() {
 
     assert_eq!(parts, correct);
 }
+
+#[test]
+fn tile_layout() {
+    let specification = BuildingSpecification {
+        tile: 9,
+        footprint: Some(BuildingSpecificationFootprint {
+            width: 2,
+            height: 3,
+            tiles: vec![vec![1, 2], vec![3, 4], vec![5, 6]],
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(specification.footprint().unwrap().size, UVec2::new(2, 3));
+    assert_eq!(specification.tile_at(UVec2::new(0, 0), 0), 5);
+    assert_eq!(specification.tile_at(UVec2::new(1, 0), 0), 6);
+    assert_eq!(specification.tile_at(UVec2::new(1, 2), 0), 2);
+    assert_eq!(specification.tile_at(UVec2::new(2, 2), 0), 9);
+
+    let specification = BuildingSpecification {
+        tile: 4,
+        ..Default::default()
+    };
+
+    assert!(specification.footprint().is_none());
+    assert_eq!(specification.tile_at(UVec2::new(0, 0), 0), 4);
+}
+
+#[test]
+fn rotated_tile_layout() {
+    let specification = BuildingSpecification {
+        tile: 9,
+        footprint: Some(BuildingSpecificationFootprint {
+            width: 2,
+            height: 3,
+            tiles: vec![vec![1, 2], vec![3, 4], vec![5, 6]],
+        }),
+        ..Default::default()
+    };
+
+    // a quarter turn clockwise moves the left column to the top row
+    assert_eq!(
+        specification.rotated_footprint(1).unwrap().size,
+        UVec2::new(3, 2)
+    );
+    assert_eq!(specification.tile_at(UVec2::new(0, 1), 1), 5);
+    assert_eq!(specification.tile_at(UVec2::new(2, 1), 1), 1);
+    assert_eq!(specification.tile_at(UVec2::new(0, 0), 1), 6);
+
+    assert_eq!(
+        specification.rotated_footprint(2).unwrap().size,
+        UVec2::new(2, 3)
+    );
+    assert_eq!(specification.tile_at(UVec2::new(0, 0), 2), 2);
+    assert_eq!(specification.tile_at(UVec2::new(1, 2), 2), 5);
+
+    assert_eq!(specification.tile_at(UVec2::new(0, 0), 3), 1);
+    assert_eq!(specification.tile_at(UVec2::new(2, 1), 3), 6);
+    assert_eq!(specification.tile_at(UVec2::new(3, 0), 3), 9);
+}
@@ -2,15 +2,19 @@ use bevy::prelude::*;
 
 use crate::game::{
     assets::{
-        building_specifications::{BuildingSpecificationCost, BuildingSpecifications},
+        building_specifications::{
+            BuildingSpecification, BuildingSpecificationCost, BuildingSpecifications,
+        },
         resource_specifications::ResourceSpecifications,
     },
     production::Product,
+    scenario::{ScenarioManager, ScenarioObjective},
 };
 
 pub fn integrity_check(
     resources: Res<ResourceSpecifications>,
     buildings: Res<BuildingSpecifications>,
+    scenarios: Res<ScenarioManager>,
 ) {
     for building in buildings.values() {
         for product in &building.products {
@@ -18,6 +22,68 @@ pub fn integrity_check(
         }
 
         check_cost(&building.cost, &resources);
+        check_footprint(building);
+
+        if let Some(housing) = &building.housing {
+            for dependency in &housing.consumption {
+                asset_resource(&dependency.resource, &resources);
+            }
+        }
+
+        if let Some(maintenance) = &building.maintenance {
+            for resource in maintenance.resources.keys() {
+                asset_resource(resource, &resources);
+            }
+        }
+    }
+
+    check_scenarios(&scenarios, &resources, &buildings);
+}
+
+fn check_scenarios(
+    scenarios: &ScenarioManager,
+    resources: &ResourceSpecifications,
+    buildings: &BuildingSpecifications,
+) {
+    for scenario in &scenarios.scenarios {
+        for objective in &scenario.objectives {
+            match objective {
+                ScenarioObjective::Export { resource, .. }
+                | ScenarioObjective::Produce { resource, .. } => {
+                    asset_resource(resource, resources)
+                }
+                ScenarioObjective::Build { building, .. } => asset_building(building, buildings),
+                ScenarioObjective::Balance { .. } => {}
+            }
+        }
+
+        for building in scenario.buildings.iter().flatten() {
+            asset_building(building, buildings);
+        }
+    }
+
+    for campaign in &scenarios.campaigns {
+        for scenario in &campaign.scenarios {
+            if scenarios.get(scenario).is_none() {
+                panic!("expected '{}' to be a valid scenario", scenario);
+            }
+        }
+    }
+}
+
+fn check_footprint(building: &BuildingSpecification) {
+    if let Some(footprint) = &building.footprint {
+        if footprint.tiles.len() != footprint.height as usize
+            || footprint
+                .tiles
+                .iter()
+                .any(|row| row.len() != footprint.width as usize)
+        {
+            panic!(
+                "expected tile layout of '{}' to match its footprint",
+                building.name
+            );
+        }
     }
 }
 
@@ -48,3 +114,9 @@ fn asset_resource(resource: &str, resources: &ResourceSpecifications) {
         panic!("expected '{}' to be a valid resource", resource);
     }
 }
+
+fn asset_building(building: &str, buildings: &BuildingSpecifications) {
+    if !buildings.contains_key(building) {
+        panic!("expected '{}' to be a valid building", building);
+    }
+}
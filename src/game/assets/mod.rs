@@ -36,6 +36,22 @@ pub struct MapSettings {
     pub size: MapSize,
 }
 
+impl MapSettings {
+    pub fn from_size(size: MapSize) -> Self {
+        let (width, height) = match size {
+            MapSize::Small => (3, 3),
+            MapSize::Medium => (5, 5),
+            MapSize::Large => (8, 8),
+        };
+
+        Self {
+            width,
+            height,
+            size,
+        }
+    }
+}
+
 impl Default for MapSettings {
     fn default() -> Self {
         Self {
@@ -55,11 +71,17 @@ pub struct Position {
 #[serde(deny_unknown_fields)]
 pub struct Building {
     pub id: String,
+    // quarter turns clockwise
+    #[serde(default)]
+    pub rotation: u8,
+    // built before ports existed, connects to storage on every side
+    #[serde(default)]
+    pub any_side: bool,
 }
 
 pub struct RequiresUpdate;
 
-#[derive(PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum Direction {
     North,
     South,
@@ -68,6 +90,23 @@ pub enum Direction {
     None,
 }
 
+impl Direction {
+    pub fn rotated(self, quarter_turns: u8) -> Direction {
+        let mut direction = self;
+        for _ in 0..quarter_turns % 4 {
+            direction = match direction {
+                Direction::North => Direction::East,
+                Direction::East => Direction::South,
+                Direction::South => Direction::West,
+                Direction::West => Direction::North,
+                Direction::None => Direction::None,
+            };
+        }
+
+        direction
+    }
+}
+
 #[derive(Default)]
 pub struct ClickedTile {
     pub dragging: bool,
@@ -81,6 +120,41 @@ pub struct ClickedTile {
 pub struct Occupied;
 pub struct CanDriveOver;
 
+// size of a building covering several tiles, stored on its main entity at the lower left tile
+#[derive(Clone, Copy)]
+pub struct Footprint {
+    pub size: UVec2,
+}
+
+impl Footprint {
+    pub fn positions(&self, origin: UVec2) -> Vec<UVec2> {
+        let mut positions = vec![];
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                positions.push(origin + UVec2::new(x, y));
+            }
+        }
+
+        positions
+    }
+
+    // quarter turns clockwise swap width and height
+    pub fn rotated(self, rotation: u8) -> Footprint {
+        if rotation % 2 == 1 {
+            Footprint {
+                size: UVec2::new(self.size.y, self.size.x),
+            }
+        } else {
+            self
+        }
+    }
+}
+
+// additional tile of a building with a footprint
+pub struct BuildingPart {
+    pub main: Entity,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Name {
@@ -26,6 +26,14 @@ pub struct ResourceSpecification {
     pub substitute: HashMap<String, f64>,
     #[serde(default, rename = "virtual")]
     pub virtual_resource: bool,
+    #[serde(default)]
+    pub liquid: bool,
+    // half-life of stored goods in seconds
+    #[serde(default)]
+    pub perishable: Option<f64>,
+    // bought by towns per thousand citizens and production tick
+    #[serde(default)]
+    pub demand: Option<f64>,
 }
 
 pub fn load_file(resources: &mut ResourceSpecifications, file_name: &str) {
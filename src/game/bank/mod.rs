@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::account::{AccountTransaction, TransactionCategory};
+
+// loan amount and interest rate per production tick
+pub const LOAN_OFFERS: [(i64, f64); 3] = [(100000, 0.0001), (250000, 0.00015), (500000, 0.0002)];
+const MAX_DEBT: i64 = 1000000;
+// how far the account may be overdrawn by construction
+const CREDIT_LIMIT: i64 = 50000;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Loan {
+    pub amount: i64,
+    pub interest: f64,
+}
+
+impl Loan {
+    pub fn interest_per_tick(&self) -> f64 {
+        self.amount as f64 * self.interest
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Bank {
+    pub loans: Vec<Loan>,
+    pub credit_limit: i64,
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self {
+            loans: vec![],
+            credit_limit: CREDIT_LIMIT,
+        }
+    }
+}
+
+impl Bank {
+    pub fn debt(&self) -> i64 {
+        self.loans.iter().map(|loan| loan.amount).sum()
+    }
+
+    // money that can be spent including the credit line
+    pub fn available(&self, balance: i64) -> i64 {
+        balance + self.credit_limit
+    }
+
+    pub fn can_borrow(&self, amount: i64) -> bool {
+        self.debt() + amount <= MAX_DEBT
+    }
+
+    // returns the amount paid out
+    pub fn take_loan(&mut self, offer: usize) -> Option<i64> {
+        let (amount, interest) = *LOAN_OFFERS.get(offer)?;
+        if !self.can_borrow(amount) {
+            return None;
+        }
+
+        self.loans.push(Loan { amount, interest });
+        Some(amount)
+    }
+
+    // returns the amount paid back
+    pub fn repay(&mut self, index: usize, balance: i64) -> Option<i64> {
+        let amount = self.loans.get(index)?.amount;
+        if amount > balance {
+            return None;
+        }
+
+        self.loans.remove(index);
+        Some(amount)
+    }
+}
+
+pub fn loan_interest(bank: Res<Bank>, mut events: EventWriter<AccountTransaction>) {
+    let interest: f64 = bank.loans.iter().map(|loan| loan.interest_per_tick()).sum();
+
+    if interest > 0.0 {
+        events.send(AccountTransaction {
+            amount: -(interest.round() as i64),
+            category: TransactionCategory::Interest,
+            source: None,
+        });
+    }
+}
+
+pub fn reset_bank(mut bank: ResMut<Bank>) {
+    *bank = Bank::default();
+}
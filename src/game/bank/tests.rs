@@ -0,0 +1,51 @@
+use bevy::app::Events;
+
+use super::*;
+
+#[test]
+fn loans_up_to_max_debt() {
+    let mut bank = Bank::default();
+
+    assert_eq!(bank.take_loan(2), Some(500000));
+    assert_eq!(bank.take_loan(2), Some(500000));
+    assert_eq!(bank.take_loan(0), None);
+    assert_eq!(bank.debt(), MAX_DEBT);
+}
+
+#[test]
+fn repay_requires_balance() {
+    let mut bank = Bank::default();
+    bank.take_loan(0);
+
+    assert_eq!(bank.repay(0, 50000), None);
+    assert_eq!(bank.repay(0, 100000), Some(100000));
+    assert!(bank.loans.is_empty());
+}
+
+#[test]
+fn credit_line_extends_available_money() {
+    let bank = Bank::default();
+
+    assert_eq!(bank.available(1000), 1000 + CREDIT_LIMIT);
+}
+
+#[test]
+fn interest_is_charged() {
+    let mut world = World::default();
+
+    let mut bank = Bank::default();
+    bank.take_loan(0);
+
+    world.insert_resource(bank);
+    world.insert_resource(Events::<AccountTransaction>::default());
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(loan_interest.system());
+    stage.run(&mut world);
+
+    let events = world.get_resource::<Events<AccountTransaction>>().unwrap();
+    let mut reader = events.get_reader();
+    let sum: i64 = reader.iter(events).map(|event| event.amount).sum();
+
+    assert_eq!(sum, -10);
+}
@@ -0,0 +1,85 @@
+use glob::glob;
+use std::{
+    fs::{remove_file, File},
+    io::prelude::*,
+    path::Path,
+};
+
+use crate::game::state_manager::helper::generate_blueprint_path;
+
+use super::{Blueprint, BlueprintManager};
+
+fn file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+
+    format!("{}/{}.yml", generate_blueprint_path(), name.to_lowercase())
+}
+
+impl BlueprintManager {
+    pub fn load_file(&mut self, file_name: &str) {
+        let path = Path::new(file_name);
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(why) => {
+                log::error!("Could not read file: {}", why);
+                return;
+            }
+        };
+
+        let mut content = String::new();
+        let _ = file.read_to_string(&mut content);
+
+        let blueprint: Result<Blueprint, serde_yaml::Error> = serde_yaml::from_str(&content);
+
+        match blueprint {
+            Ok(blueprint) => {
+                log::info!("load blueprint {}", blueprint.name);
+                self.blueprints.push(blueprint);
+            }
+            Err(why) => log::error!("Could not load blueprint: {}", why),
+        }
+    }
+
+    pub fn load_blueprints(&mut self) {
+        self.blueprints = vec![];
+
+        let pattern = format!("{}/*.yml", generate_blueprint_path());
+        for file in glob(&pattern).expect("Failed to read files") {
+            self.load_file(&format!("{}", file.unwrap().display()));
+        }
+
+        self.blueprints.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    pub fn save_blueprint(&mut self, blueprint: Blueprint) {
+        let content = match serde_yaml::to_string(&blueprint) {
+            Ok(content) => content,
+            Err(why) => {
+                log::error!("Could not serialize blueprint: {}", why);
+                return;
+            }
+        };
+
+        match File::create(&file_name(&blueprint.name)) {
+            Ok(mut file) => {
+                if let Err(why) = file.write_all(content.as_bytes()) {
+                    log::error!("Could not write file: {}", why);
+                }
+            }
+            Err(why) => log::error!("Could not create file: {}", why),
+        }
+
+        self.load_blueprints();
+    }
+
+    pub fn delete_blueprint(&mut self, name: &str) {
+        if let Err(why) = remove_file(file_name(name)) {
+            log::error!("Could not delete blueprint: {}", why);
+        }
+
+        self.load_blueprints();
+    }
+}
@@ -0,0 +1,242 @@
+mod loader;
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::game::{
+    account::PurchaseCost,
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications,
+    },
+    construction::UnderConstruction,
+    state_manager::BuildingEntity,
+};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BlueprintEntity {
+    // offset from the lower left corner of the blueprint
+    pub offset: UVec2,
+    pub entity: BuildingEntity,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Blueprint {
+    pub name: String,
+    pub size: UVec2,
+    pub entities: Vec<BlueprintEntity>,
+}
+
+#[derive(Default)]
+pub struct BlueprintManager {
+    pub blueprints: Vec<Blueprint>,
+    // blueprint placed by the paste tool
+    pub current: Option<Blueprint>,
+}
+
+impl BlueprintManager {
+    pub fn new() -> Self {
+        let mut new = Self::default();
+        new.load_blueprints();
+        new
+    }
+}
+
+// size of the tiles covered by a blueprint entity
+pub fn entity_size(entity: &BuildingEntity, buildings: &BuildingSpecifications) -> UVec2 {
+    let footprint = match entity {
+        BuildingEntity::Building(building) => buildings
+            .get(&building.id)
+            .and_then(|specification| specification.rotated_footprint(building.rotation)),
+        BuildingEntity::Storage(storage) => storage.footprint(),
+        _ => None,
+    };
+
+    footprint
+        .map(|footprint| footprint.size)
+        .unwrap_or_else(|| UVec2::new(1, 1))
+}
+
+pub fn entity_price(
+    entity: &BuildingEntity,
+    buildings: &BuildingSpecifications,
+    resources: &ResourceSpecifications,
+) -> i64 {
+    match entity {
+        BuildingEntity::Building(building) => buildings
+            .get(&building.id)
+            .map_or(0, |building| building.price(resources)),
+        BuildingEntity::Storage(c) => c.price(resources),
+        BuildingEntity::ImportExportStation(c) => c.price(resources),
+        BuildingEntity::DeliveryStation(c) => c.price(resources),
+        BuildingEntity::TownShop(c) => c.price(resources),
+        BuildingEntity::StorageManagement(c) => c.price(resources),
+        BuildingEntity::Depot(c) => c.price(resources),
+        BuildingEntity::Street(c) => c.price(resources),
+        BuildingEntity::Rail(c) => c.price(resources),
+        BuildingEntity::TrainStation(c) => c.price(resources),
+        BuildingEntity::Conveyor(c) => c.price(resources),
+        BuildingEntity::Pipeline(c) => c.price(resources),
+    }
+}
+
+pub fn entity_construction(
+    entity: &BuildingEntity,
+    buildings: &BuildingSpecifications,
+    resources: &ResourceSpecifications,
+) -> UnderConstruction {
+    match entity {
+        BuildingEntity::Building(building) => match buildings.get(&building.id) {
+            Some(building) => UnderConstruction::from_building_specification(building),
+            None => UnderConstruction::default(),
+        },
+        _ => UnderConstruction::from_fixed_cost(entity_price(entity, buildings, resources)),
+    }
+}
+
+// positions referenced by depots and storage management, stored relative to the blueprint
+fn map_positions(entity: &mut BuildingEntity, map: impl Fn(UVec2) -> Option<UVec2>) {
+    let apply = |positions: &HashSet<UVec2>| -> HashSet<UVec2> {
+        positions.iter().filter_map(|pos| map(*pos)).collect()
+    };
+
+    match entity {
+        BuildingEntity::Depot(depot) => {
+            depot.deliveries = apply(&depot.deliveries);
+            depot.pickups = apply(&depot.pickups);
+        }
+        BuildingEntity::StorageManagement(management) => {
+            management.targets = apply(&management.targets);
+            management.inputs = apply(&management.inputs);
+        }
+        _ => {}
+    }
+}
+
+impl Blueprint {
+    // captures entities by their map position, the area starts at origin
+    pub fn from_entities(
+        entities: Vec<(UVec2, BuildingEntity)>,
+        origin: UVec2,
+        size: UVec2,
+    ) -> Self {
+        let inside = |pos: UVec2| {
+            pos.x >= origin.x
+                && pos.y >= origin.y
+                && pos.x < origin.x + size.x
+                && pos.y < origin.y + size.y
+        };
+
+        let entities = entities
+            .into_iter()
+            .filter(|(pos, _entity)| inside(*pos))
+            .filter_map(|(pos, mut entity)| {
+                match &mut entity {
+                    // the shop belongs to its town
+                    BuildingEntity::TownShop(_) => return None,
+                    BuildingEntity::Storage(storage) => {
+                        storage.clear();
+                        storage.tier = 0;
+                        storage.capacity = storage.base_capacity();
+                    }
+                    BuildingEntity::Building(building) => building.mothballed = false,
+                    _ => {}
+                }
+
+                map_positions(&mut entity, |pos| {
+                    if inside(pos) {
+                        Some(pos - origin)
+                    } else {
+                        None
+                    }
+                });
+
+                Some(BlueprintEntity {
+                    offset: pos - origin,
+                    entity,
+                })
+            })
+            .collect();
+
+        Self {
+            name: "".to_owned(),
+            size,
+            entities,
+        }
+    }
+
+    // a quarter turn clockwise, footprints turn along with their buildings
+    pub fn rotated(&self, buildings: &BuildingSpecifications) -> Self {
+        let width = self.size.x;
+        let turn =
+            |pos: UVec2, size: UVec2| UVec2::new(pos.y, width.saturating_sub(pos.x + size.x));
+
+        let entities: Vec<BlueprintEntity> = self
+            .entities
+            .iter()
+            .map(|item| {
+                let mut entity = item.entity.clone();
+                let size = entity_size(&entity, buildings);
+
+                match &mut entity {
+                    BuildingEntity::Building(building) => {
+                        building.rotation = (building.rotation + 1) % 4;
+                    }
+                    BuildingEntity::Conveyor(conveyor) => {
+                        conveyor.direction = conveyor.direction.rotated(1);
+                    }
+                    _ => {}
+                }
+                map_positions(&mut entity, |pos| Some(turn(pos, UVec2::new(1, 1))));
+
+                BlueprintEntity {
+                    offset: turn(item.offset, size),
+                    entity,
+                }
+            })
+            .collect();
+
+        let size = entities.iter().fold(UVec2::new(0, 0), |size, item| {
+            size.max(item.offset + entity_size(&item.entity, buildings))
+        });
+
+        Self {
+            name: self.name.clone(),
+            size,
+            entities,
+        }
+    }
+
+    pub fn rotated_times(&self, quarter_turns: u8, buildings: &BuildingSpecifications) -> Self {
+        let mut blueprint = self.clone();
+        for _ in 0..quarter_turns % 4 {
+            blueprint = blueprint.rotated(buildings);
+        }
+
+        blueprint
+    }
+
+    pub fn price(
+        &self,
+        buildings: &BuildingSpecifications,
+        resources: &ResourceSpecifications,
+    ) -> i64 {
+        self.entities
+            .iter()
+            .map(|item| entity_price(&item.entity, buildings, resources))
+            .sum()
+    }
+
+    // converts relative positions of depots and storage management to map positions
+    pub fn placed_entity(&self, item: &BlueprintEntity, origin: UVec2) -> BuildingEntity {
+        let mut entity = item.entity.clone();
+        map_positions(&mut entity, |pos| Some(pos + origin));
+
+        entity
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::game::{
+    assets::{
+        building_specifications::{BuildingSpecification, BuildingSpecificationFootprint},
+        Direction,
+    },
+    conveyor::Conveyor,
+    production::{Depot, TownShop},
+    state_manager::SerializedBuilding,
+    storage::Storage,
+};
+
+const FURNACE: &str = "furnace";
+
+fn buildings() -> BuildingSpecifications {
+    let mut buildings = HashMap::new();
+    buildings.insert(
+        FURNACE.to_owned(),
+        BuildingSpecification {
+            footprint: Some(BuildingSpecificationFootprint {
+                width: 2,
+                height: 1,
+                tiles: vec![vec![0, 0]],
+            }),
+            ..Default::default()
+        },
+    );
+
+    buildings
+}
+
+fn furnace() -> BuildingEntity {
+    BuildingEntity::Building(SerializedBuilding {
+        id: FURNACE.to_owned(),
+        active_products: vec![],
+        rotation: 0,
+        any_side: false,
+        mothballed: false,
+    })
+}
+
+#[test]
+fn captures_area() {
+    let mut deliveries = HashSet::new();
+    deliveries.insert(UVec2::new(11, 11));
+    deliveries.insert(UVec2::new(30, 30));
+
+    let entities = vec![
+        (
+            UVec2::new(10, 10),
+            BuildingEntity::Storage(Storage {
+                resource: "coal".to_owned(),
+                amount: 5.0,
+                ..Default::default()
+            }),
+        ),
+        (
+            UVec2::new(11, 10),
+            BuildingEntity::Depot(Depot {
+                deliveries,
+                ..Default::default()
+            }),
+        ),
+        (
+            UVec2::new(12, 10),
+            BuildingEntity::TownShop(TownShop::default()),
+        ),
+        (UVec2::new(20, 20), furnace()),
+    ];
+
+    let blueprint = Blueprint::from_entities(entities, UVec2::new(10, 10), UVec2::new(3, 2));
+
+    assert_eq!(blueprint.entities.len(), 2);
+
+    match &blueprint.entities[0].entity {
+        BuildingEntity::Storage(storage) => assert!(storage.amount.abs() < f64::EPSILON),
+        _ => panic!("expected storage"),
+    }
+
+    assert_eq!(blueprint.entities[1].offset, UVec2::new(1, 0));
+    match &blueprint.entities[1].entity {
+        BuildingEntity::Depot(depot) => {
+            assert_eq!(depot.deliveries.len(), 1);
+            assert!(depot.deliveries.contains(&UVec2::new(1, 1)));
+        }
+        _ => panic!("expected depot"),
+    }
+}
+
+#[test]
+fn rotates_clockwise() {
+    let buildings = buildings();
+    let blueprint = Blueprint {
+        name: "test".to_owned(),
+        size: UVec2::new(3, 2),
+        entities: vec![
+            BlueprintEntity {
+                offset: UVec2::new(0, 0),
+                entity: furnace(),
+            },
+            BlueprintEntity {
+                offset: UVec2::new(2, 1),
+                entity: BuildingEntity::Conveyor(Conveyor {
+                    direction: Direction::North,
+                    resource: None,
+                }),
+            },
+        ],
+    };
+
+    let rotated = blueprint.rotated(&buildings);
+
+    // the furnace stands upright after the turn
+    assert_eq!(rotated.size, UVec2::new(2, 3));
+    assert_eq!(rotated.entities[0].offset, UVec2::new(0, 1));
+    match &rotated.entities[0].entity {
+        BuildingEntity::Building(building) => assert_eq!(building.rotation, 1),
+        _ => panic!("expected building"),
+    }
+
+    assert_eq!(rotated.entities[1].offset, UVec2::new(1, 0));
+    match &rotated.entities[1].entity {
+        BuildingEntity::Conveyor(conveyor) => assert_eq!(conveyor.direction, Direction::East),
+        _ => panic!("expected conveyor"),
+    }
+
+    let turned = blueprint.rotated_times(4, &buildings);
+    assert_eq!(turned.entities[1].offset, UVec2::new(2, 1));
+}
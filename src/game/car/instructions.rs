@@ -3,7 +3,8 @@ use bevy_ecs_tilemap::prelude::*;
 use rand::{prelude::SliceRandom, thread_rng};
 
 use crate::game::{
-    assets::Position,
+    assets::{resource_specifications::ResourceSpecifications, BuildingPart, Position},
+    construction::{ConstructionSettings, UnderConstruction},
     production::{DeliveryStation, Depot},
     setup::{BUILDING_LAYER_ID, MAP_ID},
     storage::{amount_in_storage, Storage, StorageConsolidator},
@@ -20,12 +21,18 @@ pub fn load(
     consolidator_query: Query<&StorageConsolidator, With<DeliveryStation>>,
     map_query: MapQuery,
     mut car_events: EventReader<CarLoadInstructionEvent>,
+    resources: Res<ResourceSpecifications>,
 ) {
     for car_event in car_events.iter() {
         let (_car, position) = car_query.get_mut(car_event.car).unwrap();
 
         let full = {
             match storage_query.get_mut(car_event.car) {
+                // trucks carry no liquids and tankers nothing else
+                Ok(storage) if !storage.accepts(&car_event.resource, &resources) => {
+                    log::warn!("Car cannot load {}", car_event.resource);
+                    continue;
+                }
                 Ok(storage) => storage.is_full(),
                 _ => {
                     log::warn!("Car has no storage but should wait for loading");
@@ -62,12 +69,44 @@ pub fn load(
     }
 }
 
+// hands materials to construction sites next to a delivery station, returns the amount taken
+fn deliver_to_sites(
+    position: UVec2,
+    resource: &str,
+    amount: f64,
+    map_query: &MapQuery,
+    part_query: &Query<&BuildingPart>,
+    site_query: &mut Query<&mut UnderConstruction>,
+) -> f64 {
+    let mut delivered = 0.0;
+    let neighbors = map_query.get_tile_neighbors(position, MAP_ID, BUILDING_LAYER_ID);
+
+    for (_neighbor_pos, neighbor) in neighbors.iter() {
+        if let Some(neighbor) = neighbor {
+            let site = part_query
+                .get(*neighbor)
+                .map(|part| part.main)
+                .unwrap_or(*neighbor);
+
+            if let Ok(mut construction) = site_query.get_mut(site) {
+                delivered += construction.deliver(resource, amount - delivered);
+            }
+        }
+    }
+
+    delivered
+}
+
 pub fn unload(
     mut car_query: Query<(&mut Car, &Position)>,
     mut storage_query: Query<&mut Storage>,
     consolidator_query: Query<&StorageConsolidator, With<DeliveryStation>>,
+    mut site_query: Query<&mut UnderConstruction>,
+    part_query: Query<&BuildingPart>,
     map_query: MapQuery,
     mut car_events: EventReader<CarUnloadInstructionEvent>,
+    resources: Res<ResourceSpecifications>,
+    settings: Res<ConstructionSettings>,
 ) {
     for car_event in car_events.iter() {
         let (_car, position) = car_query.get_mut(car_event.car).unwrap();
@@ -87,22 +126,42 @@ pub fn unload(
                 map_query.get_tile_entity(position.position / 2, MAP_ID, BUILDING_LAYER_ID)
             {
                 if let Ok(consolidator) = consolidator_query.get(entity) {
-                    let amount = {
+                    let mut amount = {
                         let storage = storage_query.get_mut(car_event.car).unwrap();
                         storage.amount.min(AMOUNT)
                     };
 
-                    if has_space_in_storage(
-                        consolidator,
-                        &mut storage_query,
-                        &car_event.resource,
-                        amount,
-                    ) {
+                    // construction sites next to the station are served first
+                    if settings.deliveries {
+                        let delivered = deliver_to_sites(
+                            position.position / 2,
+                            &car_event.resource,
+                            amount,
+                            &map_query,
+                            &part_query,
+                            &mut site_query,
+                        );
+
+                        let mut storage = storage_query.get_mut(car_event.car).unwrap();
+                        storage.amount -= delivered;
+                        amount -= delivered;
+                    }
+
+                    if amount > 0.0
+                        && has_space_in_storage(
+                            consolidator,
+                            &mut storage_query,
+                            &car_event.resource,
+                            amount,
+                            &resources,
+                        )
+                    {
                         distribute_to_storage(
                             consolidator,
                             &mut storage_query,
                             &car_event.resource,
                             amount,
+                            &resources,
                         );
 
                         let mut storage = storage_query.get_mut(car_event.car).unwrap();
@@ -172,6 +231,7 @@ pub fn car_instruction(
     mut goto_events: EventWriter<CarGoToInstructionEvent>,
     mut unload_events: EventWriter<CarUnloadInstructionEvent>,
     map_query: MapQuery,
+    resources: Res<ResourceSpecifications>,
 ) {
     for (car_entity, mut car, position, mut wait) in car_query.iter_mut() {
         let storage = storage_query.get_mut(car_entity).unwrap().clone();
@@ -270,6 +330,12 @@ pub fn car_instruction(
                 {
                     CarInstructions::Nop => true,
                     CarInstructions::GoTo(destination) => position.position / 2 == *destination,
+                    // trucks carry no liquids and tankers nothing else
+                    CarInstructions::Load(resource) | CarInstructions::WaitForLoad(resource)
+                        if !storage.accepts(resource, &resources) =>
+                    {
+                        true
+                    }
                     CarInstructions::Load(_resource) => {
                         if let Some(current_position_storage) = current_position_storage {
                             storage.is_full() || current_position_storage.is_empty()
@@ -21,6 +21,9 @@ use crate::game::{
 
 pub use calculate_destination::calculate_destination;
 
+const TRUCK_PRICE: i64 = 250;
+const TANKER_PRICE: i64 = 400;
+
 pub struct Destination {
     pub destination: UVec2,
 }
@@ -133,7 +136,13 @@ impl PurchaseCost for (Car, Storage) {
             .get(&self.1.resource)
             .unwrap_or_else(|| panic!("expected to find resource {} in spec", self.1.resource));
 
-        ((resource.cost * self.1.capacity) / 100.0) as i64 + 250
+        let base = if resource.liquid {
+            TANKER_PRICE
+        } else {
+            TRUCK_PRICE
+        };
+
+        ((resource.cost * self.1.capacity) / 100.0) as i64 + base
     }
 }
 
@@ -1,15 +1,24 @@
 pub const TILE_SIZE: f32 = 16.0;
 pub const TILE_MAP_WIDTH: f32 = 16.0;
-pub const TILE_MAP_HEIGHT: f32 = 16.0;
+pub const TILE_MAP_HEIGHT: f32 = 32.0;
 pub const CHUNK_SIZE: u32 = 16;
 
 pub const STORAGE_SIZE: f64 = 250.0;
 pub const CAR_STORAGE_SIZE: f64 = 20.0;
+pub const WAGON_STORAGE_SIZE: f64 = 100.0;
+pub const WAREHOUSE_SIZE: f64 = 500.0;
+pub const SILO_SIZE: f64 = 1200.0;
+pub const REFRIGERATION_FACTOR: f64 = 4.0;
+pub const TOWN_START_POPULATION: f64 = 500.0;
+// workers of the starting town, available without any housing
+pub const START_WORKFORCE: u32 = 100;
 
 pub const PRODUCTION_TICK_SPEED: f64 = 2.5;
 pub const CAR_DRIVE_TICK_SPEED: f64 = 0.2;
 pub const CAR_INSTRUCTION_TICK_SPEED: f64 = 0.25;
 pub const GOAL_UPDATE_TICK_SPEED: f64 = PRODUCTION_TICK_SPEED;
+// seconds of play per in-game day
+pub const DAY_LENGTH: f64 = 60.0;
 
 pub const Z_CAR: f32 = 1.0;
 pub const Z_IDLE_INDICATOR: f32 = 1.5;
@@ -40,6 +49,15 @@ pub enum MapTile {
     WaterTilesOffset = 96,
     ForestTilesOffset = 144,
     PathTilesOffset = 192,
+    RailTilesOffset = 134,
+    TrainStation = 140,
+    TrainHorizontal,
+    TrainVertical,
+    Warehouse,
+    SiloOffset = 156,
+    ConveyorTilesOffset = 182,
+    TownShop = 189,
+    PipelineTilesOffset = 256,
 }
 
 #[derive(FromPrimitive, ToPrimitive, Copy, Clone)]
@@ -5,27 +5,64 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::game::{
-    account::{Account, AccountTransaction},
+    account::{Account, AccountTransaction, TransactionCategory},
     assets::{
         building_specifications::BuildingSpecification,
         resource_specifications::ResourceSpecifications, InfoUI, RequiresUpdate,
     },
+    bank::Bank,
     constants::CURRENCY,
+    storage::{amount_in_storage, fetch_from_storage, Storage, StorageConsolidator},
 };
 use bevy::prelude::*;
 use bevy_egui::egui::Ui;
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct UnderConstruction {
     resources_needed: HashMap<String, f64>,
     labor: f64,
+    // position in the construction queue, assigned once queued
+    #[serde(default)]
+    pub order: u64,
+    #[serde(default)]
+    pub paused: bool,
+    // money spent so far, partially refunded when cancelled
+    #[serde(default)]
+    pub spent: f64,
+    // upgrades of existing buildings are reverted instead of demolished when cancelled
+    #[serde(default)]
+    pub upgrade: bool,
+    // storage tier reached once an upgrade is finished
+    #[serde(default)]
+    pub tier: Option<usize>,
 }
 
+// money spent on a finished building, the basis of its bulldoze refund
+#[derive(Clone, Copy, Debug)]
+pub struct ConstructionSpent(pub f64);
+
 impl UnderConstruction {
     pub fn from_building_specification(specification: &BuildingSpecification) -> Self {
         Self {
             resources_needed: specification.cost.resources.clone(),
             labor: specification.cost.base,
+            ..Default::default()
+        }
+    }
+
+    pub fn new(resources_needed: HashMap<String, f64>, labor: i64) -> Self {
+        Self {
+            resources_needed,
+            labor: labor as f64,
+            ..Default::default()
+        }
+    }
+
+    pub fn upgrade(resources_needed: HashMap<String, f64>, labor: i64, tier: usize) -> Self {
+        Self {
+            upgrade: true,
+            tier: Some(tier),
+            ..Self::new(resources_needed, labor)
         }
     }
 
@@ -33,6 +70,50 @@ impl UnderConstruction {
         Self {
             resources_needed: HashMap::new(),
             labor: labor as f64,
+            ..Default::default()
+        }
+    }
+
+    pub fn labor(&self) -> f64 {
+        self.labor
+    }
+
+    pub fn resources_needed(&self) -> &HashMap<String, f64> {
+        &self.resources_needed
+    }
+
+    // nothing left to deliver or pay for, the site is removed on the next tick
+    pub fn is_finished(&self) -> bool {
+        self.resources_needed.is_empty() && self.labor <= 0.0
+    }
+
+    pub fn refund(&self) -> i64 {
+        (self.spent * CANCEL_REFUND) as i64
+    }
+
+    // materials handed over by cars, returns how much the site took
+    pub fn deliver(&mut self, resource: &str, amount: f64) -> f64 {
+        let taken = self
+            .resources_needed
+            .get(resource)
+            .copied()
+            .unwrap_or(0.0)
+            .min(amount);
+
+        if taken > 0.0 {
+            self.use_resource(resource, taken);
+        }
+
+        taken
+    }
+
+    fn use_resource(&mut self, resource: &str, amount: f64) {
+        let left = self.resources_needed.get(resource).copied().unwrap_or(0.0) - amount;
+
+        if left <= 0.0 {
+            self.resources_needed.remove(resource);
+        } else {
+            self.resources_needed.insert(resource.to_owned(), left);
         }
     }
 }
@@ -62,55 +143,159 @@ impl InfoUI for UnderConstruction {
     }
 }
 
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConstructionSettings {
+    // sites take their materials from adjacent storage
+    pub deliveries: bool,
+    // materials which were not delivered are bought at a markup
+    pub market_fallback: bool,
+    // money spent on construction per tick
+    #[serde(default)]
+    pub budget: Option<i64>,
+    // goods of bulldozed storages are moved to adjacent storages before selling the rest
+    #[serde(default)]
+    pub salvage: bool,
+}
+
 const MAX_RESOURCE: f64 = 5.0;
 const MAX_LABOR: f64 = 1000.0;
+pub const MARKET_MARKUP: f64 = 1.5;
+pub const CANCEL_REFUND: f64 = 0.5;
 
 pub fn construction(
     mut commands: Commands,
-    mut query: Query<(Entity, &mut UnderConstruction)>,
+    mut query: Query<(
+        Entity,
+        &mut UnderConstruction,
+        Option<&StorageConsolidator>,
+        Option<&ConstructionSpent>,
+    )>,
+    mut storage_query: Query<&mut Storage>,
     resources: Res<ResourceSpecifications>,
     account: Res<Account>,
+    bank: Res<Bank>,
+    settings: Res<ConstructionSettings>,
     mut events: EventWriter<AccountTransaction>,
 ) {
+    let mut sites: Vec<(u64, Entity)> = query
+        .iter()
+        .filter(|(_entity, construction, _consolidator, _spent)| !construction.paused)
+        .map(|(entity, construction, _consolidator, _spent)| (construction.order, entity))
+        .collect();
+    sites.sort();
+
     let mut sum = 0.0;
-    for (entity, mut construction) in query.iter_mut() {
-        if sum >= account.value as f64 {
-            return;
+    for (_order, entity) in sites {
+        let (entity, mut construction, consolidator, spent) = query.get_mut(entity).unwrap();
+
+        let budget_left = settings.budget.map(|budget| budget as f64 - sum);
+        let funded = sum < bank.available(account.value) as f64
+            && budget_left.map_or(true, |budget_left| budget_left > 0.0);
+
+        // sites without storage access can only buy their materials
+        let inputs = consolidator
+            .filter(|_| settings.deliveries)
+            .map(|consolidator| consolidator.inputs());
+
+        if let Some(inputs) = &inputs {
+            let delivered = construction
+                .resources_needed
+                .iter()
+                .map(|(resource, amount)| {
+                    let available = amount_in_storage(inputs, &mut storage_query, resource);
+                    (
+                        resource.to_owned(),
+                        available.min(*amount).min(MAX_RESOURCE),
+                    )
+                })
+                .find(|(_resource, amount)| *amount > 0.0);
+
+            if let Some((resource, amount)) = delivered {
+                fetch_from_storage(inputs, &mut storage_query, &resource, amount);
+                construction.use_resource(&resource, amount);
+                continue;
+            }
         }
 
         let item = construction.resources_needed.clone().into_iter().next();
 
         if let Some((resource, amount)) = item {
-            let amount_buy = amount.min(MAX_RESOURCE);
-
-            if amount - amount_buy <= 0.0 {
-                construction.resources_needed.remove(&resource);
-            } else {
-                construction
-                    .resources_needed
-                    .insert(resource.to_owned(), amount - amount_buy);
+            if !funded {
+                continue;
+            }
+
+            let markup = match inputs {
+                Some(_) if settings.market_fallback => MARKET_MARKUP,
+                // waiting for deliveries
+                Some(_) => continue,
+                None => 1.0,
+            };
+            let unit_price = resources.get(&resource).unwrap().cost * markup;
+            let mut amount_buy = amount.min(MAX_RESOURCE);
+            if let Some(budget_left) = budget_left.filter(|_| unit_price > 0.0) {
+                amount_buy = amount_buy.min(budget_left / unit_price);
             }
+            construction.use_resource(&resource, amount_buy);
 
-            let resource = resources.get(&resource).unwrap();
-            let price = (resource.cost * amount_buy) as i64;
-            events.send(AccountTransaction { amount: -price });
+            let price = (unit_price * amount_buy) as i64;
+            events.send(AccountTransaction {
+                amount: -price,
+                category: TransactionCategory::Construction,
+                source: Some(entity),
+            });
+            construction.spent += price as f64;
             sum += price as f64;
             continue;
         }
 
         if construction.labor > 0.0 {
-            let invest_max = construction.labor.min(MAX_LABOR);
+            if !funded {
+                continue;
+            }
+
+            let invest_max = construction
+                .labor
+                .min(MAX_LABOR)
+                .min(budget_left.unwrap_or(f64::MAX));
             construction.labor -= invest_max;
             events.send(AccountTransaction {
                 amount: -invest_max as i64,
+                category: TransactionCategory::Construction,
+                source: Some(entity),
             });
+            construction.spent += invest_max;
             sum += invest_max;
             continue;
         }
 
+        // upgrades add to what was spent on the building before
+        let spent = spent.map_or(0.0, |spent| spent.0) + construction.spent;
         commands
             .entity(entity)
             .remove::<UnderConstruction>()
+            .insert(ConstructionSpent(spent))
             .insert(RequiresUpdate);
     }
 }
+
+// newly placed sites are added to the end of the queue
+pub fn enqueue_construction(mut query: Query<&mut UnderConstruction>) {
+    let mut next = query
+        .iter()
+        .map(|construction| construction.order)
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    for mut construction in query.iter_mut() {
+        if construction.order == 0 {
+            construction.order = next;
+            next += 1;
+        }
+    }
+}
+
+pub fn reset_construction_settings(mut settings: ResMut<ConstructionSettings>) {
+    *settings = ConstructionSettings::default();
+}
@@ -9,6 +9,16 @@ const STEEL_PRICE: f64 = 10.0;
 
 struct TestSetupParams {
     amount: i64,
+    credit_limit: i64,
+}
+
+impl Default for TestSetupParams {
+    fn default() -> Self {
+        Self {
+            amount: 1000,
+            credit_limit: 0,
+        }
+    }
 }
 
 struct TestSetup {
@@ -41,7 +51,12 @@ impl TestSetup {
         world.insert_resource(Account {
             value: params.amount,
         });
+        world.insert_resource(Bank {
+            loans: vec![],
+            credit_limit: params.credit_limit,
+        });
         world.insert_resource(Events::<AccountTransaction>::default());
+        world.insert_resource(ConstructionSettings::default());
         let mut resources = HashMap::new();
         resources.insert(
             STEEL.to_owned(),
@@ -54,11 +69,60 @@ impl TestSetup {
 
         Self { world, stage }
     }
+
+    fn with_deliveries(mut self, market_fallback: bool) -> Self {
+        self.world.insert_resource(ConstructionSettings {
+            deliveries: true,
+            market_fallback,
+            ..Default::default()
+        });
+        self
+    }
+
+    fn add_site(&mut self, steel: f64, steel_delivered: f64) -> (Entity, Entity) {
+        let storage_id = self
+            .world
+            .spawn()
+            .insert(Storage {
+                resource: STEEL.to_owned(),
+                amount: steel_delivered,
+                capacity: 20.0,
+                ..Default::default()
+            })
+            .id();
+
+        let mut resources_needed = HashMap::new();
+        resources_needed.insert(STEEL.to_owned(), steel);
+
+        let entity = self
+            .world
+            .spawn()
+            .insert(UnderConstruction {
+                labor: 0.0,
+                resources_needed,
+                ..Default::default()
+            })
+            .insert(StorageConsolidator {
+                connected_storage: vec![storage_id],
+                ..Default::default()
+            })
+            .id();
+
+        (entity, storage_id)
+    }
+
+    fn steel_needed(&self, entity: Entity) -> f64 {
+        let construction = self.world.get::<UnderConstruction>(entity).unwrap();
+        *construction.resources_needed.get(STEEL).unwrap_or(&0.0)
+    }
 }
 
 #[test]
 fn drains_money() {
-    let mut setup = TestSetup::new(TestSetupParams { amount: 1000 });
+    let mut setup = TestSetup::new(TestSetupParams {
+        amount: 1000,
+        credit_limit: 0,
+    });
     let entity = setup
         .world
         .spawn()
@@ -74,9 +138,30 @@ fn drains_money() {
     assert_eq!(construction.labor as i64, 1000);
 }
 
+#[test]
+fn keeps_spent_money_once_finished() {
+    let mut setup = TestSetup::new(TestSetupParams::default());
+    let entity = setup
+        .world
+        .spawn()
+        .insert(UnderConstruction::from_fixed_cost(500))
+        .id();
+
+    // paid on the first run, finished on the second
+    setup.stage.run(&mut setup.world);
+    setup.stage.run(&mut setup.world);
+
+    assert!(setup.world.get::<UnderConstruction>(entity).is_none());
+    let spent = setup.world.get::<ConstructionSpent>(entity).unwrap();
+    assert_eq!(spent.0 as i64, 500);
+}
+
 #[test]
 fn insufficient_money_for_all() {
-    let mut setup = TestSetup::new(TestSetupParams { amount: 1000 });
+    let mut setup = TestSetup::new(TestSetupParams {
+        amount: 1000,
+        credit_limit: 0,
+    });
 
     let entity = setup
         .world
@@ -107,7 +192,10 @@ fn insufficient_money_for_all() {
 
 #[test]
 fn purchases_resources() {
-    let mut setup = TestSetup::new(TestSetupParams { amount: 1000 });
+    let mut setup = TestSetup::new(TestSetupParams {
+        amount: 1000,
+        credit_limit: 0,
+    });
 
     let mut resources_needed = HashMap::new();
     resources_needed.insert(STEEL.to_owned(), 10.0);
@@ -118,6 +206,7 @@ fn purchases_resources() {
         .insert(UnderConstruction {
             labor: 0.0,
             resources_needed,
+            ..Default::default()
         })
         .id();
 
@@ -128,3 +217,145 @@ fn purchases_resources() {
     let construction = setup.world.get::<UnderConstruction>(entity).unwrap();
     assert!((construction.resources_needed.get(STEEL).unwrap() - 5.0) < f64::EPSILON);
 }
+
+#[test]
+fn spends_credit_line() {
+    let mut setup = TestSetup::new(TestSetupParams {
+        amount: 0,
+        credit_limit: 500,
+    });
+    let entity = setup
+        .world
+        .spawn()
+        .insert(UnderConstruction::from_fixed_cost(2000))
+        .id();
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_event_sum(-1000);
+
+    let construction = setup.world.get::<UnderConstruction>(entity).unwrap();
+
+    assert_eq!(construction.labor as i64, 1000);
+}
+
+#[test]
+fn consumes_delivered_resources() {
+    let mut setup = TestSetup::new(TestSetupParams::default()).with_deliveries(false);
+    let (entity, storage_id) = setup.add_site(10.0, 8.0);
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_event_sum(0);
+    assert!((setup.steel_needed(entity) - 5.0).abs() < f64::EPSILON);
+    let storage = setup.world.get::<Storage>(storage_id).unwrap();
+    assert!((storage.amount - 3.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn waits_for_deliveries() {
+    let mut setup = TestSetup::new(TestSetupParams::default()).with_deliveries(false);
+    let (entity, _storage_id) = setup.add_site(10.0, 0.0);
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_event_sum(0);
+    assert!((setup.steel_needed(entity) - 10.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn buys_missing_resources_at_markup() {
+    let mut setup = TestSetup::new(TestSetupParams::default()).with_deliveries(true);
+    let (entity, _storage_id) = setup.add_site(10.0, 0.0);
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_event_sum(-(5.0 * STEEL_PRICE * MARKET_MARKUP) as i64);
+    assert!((setup.steel_needed(entity) - 5.0).abs() < f64::EPSILON);
+}
+
+fn add_queued(setup: &mut TestSetup, order: u64, paused: bool) -> Entity {
+    setup
+        .world
+        .spawn()
+        .insert(UnderConstruction {
+            order,
+            paused,
+            ..UnderConstruction::from_fixed_cost(2000)
+        })
+        .id()
+}
+
+#[test]
+fn follows_queue_order() {
+    let mut setup = TestSetup::new(TestSetupParams::default());
+
+    let last = add_queued(&mut setup, 2, false);
+    let paused = add_queued(&mut setup, 0, true);
+    let first = add_queued(&mut setup, 1, false);
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_event_sum(-1000);
+
+    let labor = |entity| setup.world.get::<UnderConstruction>(entity).unwrap().labor as i64;
+    assert_eq!(labor(first), 1000);
+    assert_eq!(labor(last), 2000);
+    assert_eq!(labor(paused), 2000);
+
+    let construction = setup.world.get::<UnderConstruction>(first).unwrap();
+    assert_eq!(construction.refund(), (1000.0 * CANCEL_REFUND) as i64);
+}
+
+#[test]
+fn respects_budget() {
+    let mut setup = TestSetup::new(TestSetupParams::default());
+    setup.world.insert_resource(ConstructionSettings {
+        budget: Some(300),
+        ..Default::default()
+    });
+
+    let first = add_queued(&mut setup, 1, false);
+    let second = add_queued(&mut setup, 2, false);
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_event_sum(-300);
+    let construction = setup.world.get::<UnderConstruction>(first).unwrap();
+    assert_eq!(construction.labor as i64, 1700);
+    let construction = setup.world.get::<UnderConstruction>(second).unwrap();
+    assert_eq!(construction.labor as i64, 2000);
+}
+
+#[test]
+fn enqueues_new_sites() {
+    let mut world = World::default();
+    let mut stage = SystemStage::parallel();
+    stage.add_system(enqueue_construction.system());
+
+    let queued = world
+        .spawn()
+        .insert(UnderConstruction {
+            order: 4,
+            ..Default::default()
+        })
+        .id();
+    let placed = world.spawn().insert(UnderConstruction::default()).id();
+
+    stage.run(&mut world);
+
+    assert_eq!(world.get::<UnderConstruction>(queued).unwrap().order, 4);
+    assert_eq!(world.get::<UnderConstruction>(placed).unwrap().order, 5);
+}
+
+#[test]
+fn deliveries_cover_only_what_is_needed() {
+    let mut resources_needed = HashMap::new();
+    resources_needed.insert(STEEL.to_owned(), 3.0);
+    let mut construction = UnderConstruction::new(resources_needed, 0);
+
+    assert!((construction.deliver("wood", 4.0)).abs() < f64::EPSILON);
+    assert!((construction.deliver(STEEL, 2.0) - 2.0).abs() < f64::EPSILON);
+    assert!((construction.deliver(STEEL, 4.0) - 1.0).abs() < f64::EPSILON);
+    assert!(construction.resources_needed().is_empty());
+}
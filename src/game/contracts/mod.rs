@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    account::{AccountTransaction, TransactionCategory},
+    assets::resource_specifications::ResourceSpecifications,
+    statistics::{StatisticTracker, Statistics},
+    time::GameTime,
+};
+
+const MAX_OFFERS: usize = 3;
+const MAX_COMPLETED: usize = 10;
+const OFFER_CHANCE: f64 = 0.02;
+// days an offer stays open
+const OFFER_DAYS: f64 = 3.0;
+const AMOUNTS: [f64; 4] = [100.0, 250.0, 500.0, 1000.0];
+const BONUS_FACTOR: f64 = 0.5;
+const PENALTY_FACTOR: f64 = 0.25;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ContractState {
+    Offered,
+    Active,
+    Fulfilled,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    pub resource: String,
+    pub amount: f64,
+    pub days: f64,
+    pub bonus: i64,
+    pub penalty: i64,
+    pub state: ContractState,
+    // offers expire at the deadline, accepted contracts fail
+    pub deadline: f64,
+    // exports up to this total are already counted, for this or an earlier contract
+    pub start_export: f64,
+    pub delivered: f64,
+}
+
+impl Contract {
+    pub fn new(resource: &str, amount: f64, days: f64, cost: f64, day: f64) -> Self {
+        let bonus = (amount * cost * BONUS_FACTOR) as i64;
+
+        Self {
+            resource: resource.to_owned(),
+            amount,
+            days,
+            bonus,
+            penalty: (amount * cost * PENALTY_FACTOR) as i64,
+            state: ContractState::Offered,
+            deadline: day + OFFER_DAYS,
+            start_export: 0.0,
+            delivered: 0.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ContractManager {
+    pub contracts: Vec<Contract>,
+}
+
+impl ContractManager {
+    pub fn accept(&mut self, index: usize, exported: f64, day: f64) {
+        if let Some(contract) = self.contracts.get_mut(index) {
+            if contract.state == ContractState::Offered {
+                contract.state = ContractState::Active;
+                contract.start_export = exported;
+                contract.deadline = day + contract.days;
+            }
+        }
+    }
+
+    pub fn with_state(&self, state: ContractState) -> Vec<(usize, &Contract)> {
+        self.contracts
+            .iter()
+            .enumerate()
+            .filter(|(_index, contract)| contract.state == state)
+            .collect()
+    }
+}
+
+// everything exported so far, including removed buildings
+pub fn exported_total(
+    resource: &str,
+    query: &Query<&Statistics>,
+    deleted_export_statistics: &StatisticTracker,
+) -> f64 {
+    query.iter().fold(
+        deleted_export_statistics.get(resource),
+        |acc, statistics| acc + statistics.export.get(resource),
+    )
+}
+
+pub fn update_contracts(
+    query: Query<&Statistics>,
+    deleted_export_statistics: Res<StatisticTracker>,
+    mut manager: ResMut<ContractManager>,
+    time: Res<GameTime>,
+    mut events: EventWriter<AccountTransaction>,
+) {
+    let day = time.day();
+
+    // contracts for the same resource are filled in order, each export counts only once
+    let mut claimed: HashMap<String, f64> = HashMap::new();
+
+    for contract in manager.contracts.iter_mut() {
+        if contract.state != ContractState::Active {
+            continue;
+        }
+
+        let total = exported_total(&contract.resource, &query, &deleted_export_statistics);
+        let counted = claimed
+            .get(&contract.resource)
+            .copied()
+            .unwrap_or(0.0)
+            .max(contract.start_export);
+        let delivered = (total - counted)
+            .min(contract.amount - contract.delivered)
+            .max(0.0);
+
+        contract.delivered += delivered;
+        contract.start_export = counted + delivered;
+        claimed.insert(contract.resource.clone(), contract.start_export);
+
+        if contract.delivered >= contract.amount {
+            contract.state = ContractState::Fulfilled;
+            events.send(AccountTransaction {
+                amount: contract.bonus,
+                category: TransactionCategory::Contract,
+                source: None,
+            });
+        } else if day > contract.deadline {
+            contract.state = ContractState::Failed;
+            events.send(AccountTransaction {
+                amount: -contract.penalty,
+                category: TransactionCategory::Contract,
+                source: None,
+            });
+        }
+    }
+
+    // drop expired offers and keep only the latest completed contracts
+    manager
+        .contracts
+        .retain(|contract| contract.state != ContractState::Offered || day <= contract.deadline);
+
+    let completed = manager
+        .contracts
+        .iter()
+        .filter(|contract| {
+            matches!(
+                contract.state,
+                ContractState::Fulfilled | ContractState::Failed
+            )
+        })
+        .count();
+
+    if completed > MAX_COMPLETED {
+        let mut remove = completed - MAX_COMPLETED;
+        manager.contracts.retain(|contract| {
+            let done = matches!(
+                contract.state,
+                ContractState::Fulfilled | ContractState::Failed
+            );
+
+            if done && remove > 0 {
+                remove -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+pub fn offer_contracts(
+    mut manager: ResMut<ContractManager>,
+    time: Res<GameTime>,
+    resources: Res<ResourceSpecifications>,
+) {
+    if manager.with_state(ContractState::Offered).len() >= MAX_OFFERS {
+        return;
+    }
+
+    let mut random = thread_rng();
+    if !random.gen_bool(OFFER_CHANCE) {
+        return;
+    }
+
+    let tradable: Vec<(&String, f64)> = resources
+        .iter()
+        .filter(|(_id, resource)| resource.cost > f64::EPSILON && !resource.virtual_resource)
+        .map(|(id, resource)| (id, resource.cost))
+        .collect();
+
+    if let Some((resource, cost)) = tradable.choose(&mut random) {
+        let amount = *AMOUNTS.choose(&mut random).unwrap();
+        let days = random.gen_range(5..15) as f64;
+
+        manager
+            .contracts
+            .push(Contract::new(resource, amount, days, *cost, time.day()));
+    }
+}
+
+pub fn reset_contracts(mut manager: ResMut<ContractManager>) {
+    *manager = ContractManager::default();
+}
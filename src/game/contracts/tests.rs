@@ -0,0 +1,133 @@
+use super::*;
+use crate::game::constants::DAY_LENGTH;
+use bevy::app::Events;
+
+const COKE: &str = "coke";
+
+fn setup(contracts: Vec<Contract>) -> (World, SystemStage) {
+    let mut world = World::default();
+
+    world.insert_resource(ContractManager { contracts });
+    world.insert_resource(StatisticTracker::default());
+    world.insert_resource(GameTime::default());
+    world.insert_resource(Events::<AccountTransaction>::default());
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(update_contracts.system());
+
+    (world, stage)
+}
+
+fn active_contract() -> Contract {
+    let mut manager = ContractManager {
+        contracts: vec![Contract::new(COKE, 100.0, 5.0, 10.0, 0.0)],
+    };
+    manager.accept(0, 20.0, 0.0);
+
+    manager.contracts[0].clone()
+}
+
+fn set_exported(world: &mut World, entity: Entity, amount: f64) {
+    let mut statistics = world.get_mut::<Statistics>(entity).unwrap();
+    statistics.export = StatisticTracker::default();
+    statistics.export.track(COKE, amount);
+}
+
+fn transactions(world: &World) -> i64 {
+    let events = world.get_resource::<Events<AccountTransaction>>().unwrap();
+    let mut reader = events.get_reader();
+
+    reader.iter(events).map(|event| event.amount).sum()
+}
+
+fn state(world: &World) -> ContractState {
+    world.get_resource::<ContractManager>().unwrap().contracts[0].state
+}
+
+#[test]
+fn accepting_starts_the_deadline() {
+    let contract = active_contract();
+
+    assert_eq!(contract.state, ContractState::Active);
+    assert!((contract.deadline - 5.0).abs() < f64::EPSILON);
+    assert!((contract.start_export - 20.0).abs() < f64::EPSILON);
+    assert_eq!(contract.bonus, 500);
+    assert_eq!(contract.penalty, 250);
+}
+
+#[test]
+fn fulfilled_contracts_pay_bonus() {
+    let (mut world, mut stage) = setup(vec![active_contract()]);
+    let station = world.spawn().insert(Statistics::default()).id();
+
+    set_exported(&mut world, station, 70.0);
+    stage.run(&mut world);
+
+    assert_eq!(state(&world), ContractState::Active);
+    assert_eq!(transactions(&world), 0);
+
+    set_exported(&mut world, station, 120.0);
+    stage.run(&mut world);
+
+    assert_eq!(state(&world), ContractState::Fulfilled);
+    assert_eq!(transactions(&world), 500);
+}
+
+#[test]
+fn missed_deadlines_cost_penalty() {
+    let (mut world, mut stage) = setup(vec![active_contract()]);
+    world.spawn().insert(Statistics::default());
+
+    world.get_resource_mut::<GameTime>().unwrap().elapsed = 6.0 * DAY_LENGTH;
+    stage.run(&mut world);
+
+    assert_eq!(state(&world), ContractState::Failed);
+    assert_eq!(transactions(&world), -250);
+}
+
+#[test]
+fn offers_expire() {
+    let (mut world, mut stage) = setup(vec![Contract::new(COKE, 100.0, 5.0, 10.0, 0.0)]);
+
+    stage.run(&mut world);
+    assert_eq!(
+        world
+            .get_resource::<ContractManager>()
+            .unwrap()
+            .contracts
+            .len(),
+        1
+    );
+
+    world.get_resource_mut::<GameTime>().unwrap().elapsed = (OFFER_DAYS + 1.0) * DAY_LENGTH;
+    stage.run(&mut world);
+
+    assert!(world
+        .get_resource::<ContractManager>()
+        .unwrap()
+        .contracts
+        .is_empty());
+}
+
+#[test]
+fn contracts_share_exports() {
+    let mut first = active_contract();
+    first.amount = 50.0;
+    let (mut world, mut stage) = setup(vec![first, active_contract()]);
+    let station = world.spawn().insert(Statistics::default()).id();
+
+    set_exported(&mut world, station, 90.0);
+    stage.run(&mut world);
+
+    let manager = world.get_resource::<ContractManager>().unwrap();
+    assert_eq!(manager.contracts[0].state, ContractState::Fulfilled);
+    assert!((manager.contracts[0].delivered - 50.0).abs() < f64::EPSILON);
+    assert_eq!(manager.contracts[1].state, ContractState::Active);
+    assert!((manager.contracts[1].delivered - 20.0).abs() < f64::EPSILON);
+
+    set_exported(&mut world, station, 150.0);
+    stage.run(&mut world);
+
+    let manager = world.get_resource::<ContractManager>().unwrap();
+    assert!((manager.contracts[1].delivered - 80.0).abs() < f64::EPSILON);
+}
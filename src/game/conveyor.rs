@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use bevy_egui::egui::Ui;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    account::PurchaseCost,
+    assets::{
+        resource_specifications::ResourceSpecifications, Direction, InfoUI, Position,
+        RequiresUpdate,
+    },
+    constants::MapTile,
+    construction::UnderConstruction,
+    helper::neighbor_structure::NeighborStructure,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    storage::Storage,
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Conveyor {
+    pub direction: Direction,
+    pub resource: Option<String>,
+}
+
+impl Default for Conveyor {
+    fn default() -> Self {
+        Self {
+            direction: Direction::North,
+            resource: None,
+        }
+    }
+}
+
+// tiles a belt takes from and delivers to, kept on the first tile of the belt
+pub struct ConveyorLine {
+    pub source: Option<UVec2>,
+    pub target: Option<UVec2>,
+}
+
+impl PurchaseCost for Conveyor {
+    fn price(&self, _resources: &ResourceSpecifications) -> i64 {
+        80
+    }
+}
+
+impl InfoUI for Conveyor {
+    fn ui(&self, ui: &mut Ui, resources: &ResourceSpecifications) {
+        ui.horizontal(|ui| {
+            let resource = match &self.resource {
+                Some(resource) => resources.get(resource).unwrap().name.clone(),
+                None => "nothing".to_owned(),
+            };
+
+            ui.label(format!("Conveyor moving {}", resource));
+        });
+    }
+}
+
+pub fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::South,
+        Direction::South => Direction::North,
+        Direction::West => Direction::East,
+        Direction::East => Direction::West,
+        Direction::None => Direction::None,
+    }
+}
+
+pub fn step(pos: UVec2, direction: Direction, size: UVec2) -> Option<UVec2> {
+    match direction {
+        Direction::North if pos.y + 1 < size.y => Some(UVec2::new(pos.x, pos.y + 1)),
+        Direction::South if pos.y > 0 => Some(UVec2::new(pos.x, pos.y - 1)),
+        Direction::West if pos.x > 0 => Some(UVec2::new(pos.x - 1, pos.y)),
+        Direction::East if pos.x + 1 < size.x => Some(UVec2::new(pos.x + 1, pos.y)),
+        _ => None,
+    }
+}
+
+pub fn direction_between(from: UVec2, to: UVec2) -> Direction {
+    if to.x < from.x {
+        Direction::West
+    } else if to.x > from.x {
+        Direction::East
+    } else if to.y < from.y {
+        Direction::South
+    } else if to.y > from.y {
+        Direction::North
+    } else {
+        Direction::None
+    }
+}
+
+// returns the belt from its first to its last tile, following the belt directions
+pub fn trace_line<F>(start: UVec2, size: UVec2, direction_at: F) -> Vec<UVec2>
+where
+    F: Fn(UVec2) -> Option<Direction>,
+{
+    let mut visited = HashSet::new();
+    visited.insert(start);
+
+    let mut head = start;
+    'backwards: loop {
+        for direction in [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]
+        .iter()
+        {
+            if let Some(previous) = step(head, *direction, size) {
+                if direction_at(previous) == Some(opposite(*direction)) && visited.insert(previous)
+                {
+                    head = previous;
+                    continue 'backwards;
+                }
+            }
+        }
+
+        break;
+    }
+
+    let mut visited = HashSet::new();
+    let mut line = vec![head];
+    visited.insert(head);
+
+    let mut current = head;
+    while let Some(direction) = direction_at(current) {
+        match step(current, direction, size) {
+            Some(next) if direction_at(next).is_some() && visited.insert(next) => {
+                line.push(next);
+                current = next;
+            }
+            _ => break,
+        }
+    }
+
+    line
+}
+
+// the first tile of a belt has no other belt leading into it
+pub fn is_line_start<F>(pos: UVec2, size: UVec2, direction_at: F) -> bool
+where
+    F: Fn(UVec2) -> Option<Direction>,
+{
+    [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ]
+    .iter()
+    .all(|direction| {
+        step(pos, *direction, size).map_or(true, |previous| {
+            direction_at(previous) != Some(opposite(*direction))
+        })
+    })
+}
+
+// ends of a belt, its source lies behind the first tile and its target after the last tile
+pub fn line_ends<F>(start: UVec2, size: UVec2, direction_at: F) -> ConveyorLine
+where
+    F: Fn(UVec2) -> Option<Direction>,
+{
+    let line = trace_line(start, size, &direction_at);
+    let tail = *line.last().unwrap();
+
+    ConveyorLine {
+        source: direction_at(start).and_then(|direction| step(start, opposite(direction), size)),
+        target: direction_at(tail).and_then(|direction| step(tail, direction, size)),
+    }
+}
+
+// belts are only traced again when one of them changed
+pub fn update_conveyor_lines(
+    mut commands: Commands,
+    changed_query: Query<(), (With<Conveyor>, With<RequiresUpdate>)>,
+    conveyor_query: Query<(Entity, &Conveyor, &Position), Without<UnderConstruction>>,
+    map_query: MapQuery,
+) {
+    if changed_query.iter().next().is_none() {
+        return;
+    }
+
+    let (_entity, layer) = map_query.get_layer(MAP_ID, BUILDING_LAYER_ID).unwrap();
+    let size = layer.get_layer_size_in_tiles();
+
+    let direction_at = |pos: UVec2| -> Option<Direction> {
+        let entity = map_query
+            .get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID)
+            .ok()?;
+
+        conveyor_query
+            .get(entity)
+            .ok()
+            .map(|(_entity, conveyor, _position)| conveyor.direction)
+    };
+
+    for (entity, _conveyor, position) in conveyor_query.iter() {
+        if is_line_start(position.position, size, &direction_at) {
+            commands
+                .entity(entity)
+                .insert(line_ends(position.position, size, &direction_at));
+        } else {
+            commands.entity(entity).remove::<ConveyorLine>();
+        }
+    }
+}
+
+pub fn update_conveyors(
+    mut tile_query: Query<
+        (&Conveyor, &mut Tile, &Position),
+        (With<RequiresUpdate>, Without<UnderConstruction>),
+    >,
+    conveyor_query: Query<(), With<Conveyor>>,
+    storage_query: Query<(), With<Storage>>,
+    map_query: MapQuery,
+) {
+    for (conveyor, mut tile, position) in tile_query.iter_mut() {
+        let neighbors = map_query.get_tile_neighbors(position.position, MAP_ID, BUILDING_LAYER_ID);
+        let vertical =
+            conveyor.direction == Direction::North || conveyor.direction == Direction::South;
+
+        // belts connect to other belts and to storage at their ends
+        let connects = |neighbor: &(IVec2, Option<Entity>), on_axis: bool| match neighbor.1 {
+            Some(entity) => {
+                conveyor_query.get(entity).is_ok() || (on_axis && storage_query.get(entity).is_ok())
+            }
+            None => false,
+        };
+
+        // N, S, W, E, NW, NE, SW, SE.
+        let ns = NeighborStructure {
+            north: connects(&neighbors[0], vertical),
+            south: connects(&neighbors[1], vertical),
+            west: connects(&neighbors[2], !vertical),
+            east: connects(&neighbors[3], !vertical),
+        };
+
+        let ns_index: u16 = ns.into();
+        tile.texture_index = ns_index + MapTile::ConveyorTilesOffset as u16;
+        tile.visible = true;
+    }
+}
@@ -2,13 +2,14 @@ use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
 use super::{
-    assets::{ClickedTile, Position},
+    assets::{BuildingPart, ClickedTile, Position},
     car::Car,
     constants::{
         MapTile, VehicleTile, TILE_MAP_HEIGHT, TILE_MAP_WIDTH, TILE_SIZE, Z_SELECTION_INDICATOR,
     },
     setup::{BUILDING_LAYER_ID, MAP_ID},
     street::Street,
+    train::Train,
 };
 
 #[derive(Default)]
@@ -24,6 +25,8 @@ pub fn current_selection(
     clicked_tile: Res<ClickedTile>,
     map_query: MapQuery,
     car_query: Query<(Entity, &Position), With<Car>>,
+    train_query: Query<(Entity, &Position), With<Train>>,
+    part_query: Query<&BuildingPart>,
     mut currently_selected: ResMut<CurrentlySelected>,
 ) {
     if currently_selected.locked || currently_selected.editing {
@@ -43,7 +46,18 @@ pub fn current_selection(
 
     if clicked_tile.occupied_building {
         if let Some(pos) = clicked_tile.pos {
+            for (entity, position) in train_query.iter() {
+                if position.position == pos {
+                    currently_selected.entity = Some(entity);
+                    return;
+                }
+            }
+
             if let Ok(entity) = map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
+                let entity = part_query
+                    .get(entity)
+                    .map(|part| part.main)
+                    .unwrap_or(entity);
                 currently_selected.entity = Some(entity);
                 return;
             }
@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications, BlockedForBuilding, ClickedTile,
+        Footprint, Occupied,
+    },
+    blueprint::{entity_construction, entity_size, Blueprint, BlueprintManager},
+    helper::footprint::footprint_free,
+    highlight_tiles::HighlightTilesUpdateEvent,
+    scenario::ScenarioManager,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    state_manager::{
+        load_game::spawn_building, serialize_building, BuildingEntity, BuildingQueries,
+    },
+};
+
+use super::{drag_area, drag_rect, update_neighbors, Drag, NeighborQueries, SelectedTool, Tool};
+
+pub fn blueprint_selection(
+    mut selected_tool: ResMut<SelectedTool>,
+    clicked_tile: Res<ClickedTile>,
+    mouse_input: Res<Input<MouseButton>>,
+    map_query: MapQuery,
+    mut manager: ResMut<BlueprintManager>,
+    queries: BuildingQueries,
+    mut highlight: EventWriter<HighlightTilesUpdateEvent>,
+) {
+    if selected_tool.tool != Tool::BlueprintSelect {
+        return;
+    }
+
+    let (origin, size) = match drag_area(&mut selected_tool, &clicked_tile, &mouse_input) {
+        Drag::Moved(start, end) => {
+            let (origin, size) = drag_rect(start, end);
+            highlight.send(HighlightTilesUpdateEvent::from_positions(
+                Footprint { size }.positions(origin),
+            ));
+            return;
+        }
+        Drag::Released(start, end) => drag_rect(start, end),
+        Drag::None => return,
+    };
+
+    // mouse was released, capture everything in the area
+    highlight.send(HighlightTilesUpdateEvent::from_positions(vec![]));
+
+    let mut entities = vec![];
+    for pos in (Footprint { size }).positions(origin) {
+        let entity = match map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
+            Ok(entity) => entity,
+            Err(_) => continue,
+        };
+
+        let building = match serialize_building(entity, &queries) {
+            Some(building) => building,
+            None => continue,
+        };
+
+        entities.push((pos, building));
+    }
+
+    let blueprint = Blueprint::from_entities(entities, origin, size);
+    if !blueprint.entities.is_empty() {
+        manager.current = Some(blueprint);
+        selected_tool.tool = Tool::BlueprintPaste;
+        selected_tool.rotation = 0;
+    }
+}
+
+pub fn blueprint_placement(
+    mut commands: Commands,
+    mut map_query: MapQuery,
+    selected_tool: Res<SelectedTool>,
+    clicked_tile: Res<ClickedTile>,
+    manager: Res<BlueprintManager>,
+    buildings: Res<BuildingSpecifications>,
+    resources: Res<ResourceSpecifications>,
+    scenarios: Res<ScenarioManager>,
+    occupied_query: Query<(), With<Occupied>>,
+    blocked_query: Query<(), With<BlockedForBuilding>>,
+    neighbor_queries: NeighborQueries,
+) {
+    if clicked_tile.dragging || selected_tool.tool != Tool::BlueprintPaste {
+        return;
+    }
+
+    let (origin, blueprint) = match (clicked_tile.pos, &manager.current) {
+        (Some(pos), Some(blueprint)) => (
+            pos,
+            blueprint.rotated_times(selected_tool.rotation, &buildings),
+        ),
+        _ => return,
+    };
+
+    // the scenario may not allow every building the blueprint was copied with
+    let allowed = blueprint.entities.iter().all(|item| match &item.entity {
+        BuildingEntity::Building(building) => scenarios.allows_building(&building.id),
+        _ => true,
+    });
+    if !allowed {
+        return;
+    }
+
+    let free = blueprint.entities.iter().all(|item| {
+        let footprint = Footprint {
+            size: entity_size(&item.entity, &buildings),
+        };
+        footprint_free(
+            &map_query,
+            &occupied_query,
+            &blocked_query,
+            origin + item.offset,
+            &footprint,
+        )
+    });
+    if !free {
+        return;
+    }
+
+    for item in blueprint.entities.iter() {
+        let pos = origin + item.offset;
+        let entity = blueprint.placed_entity(item, origin);
+
+        spawn_building(
+            &mut commands,
+            &mut map_query,
+            pos,
+            &entity,
+            Some(entity_construction(&entity, &buildings, &resources)),
+            &buildings,
+            &resources,
+        );
+
+        let footprint = Footprint {
+            size: entity_size(&entity, &buildings),
+        };
+        update_neighbors(
+            &mut commands,
+            &mut map_query,
+            pos,
+            &footprint,
+            &neighbor_queries,
+        );
+    }
+}
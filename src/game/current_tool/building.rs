@@ -5,12 +5,15 @@ use crate::game::{
     account::{MaintenanceCost, PurchaseCost},
     assets::{
         building_specifications::BuildingSpecifications,
-        resource_specifications::ResourceSpecifications, Building, ClickedTile, Editable, Occupied,
-        Position, RequiresUpdate,
+        resource_specifications::ResourceSpecifications, BlockedForBuilding, Building, ClickedTile,
+        Editable, Footprint, Occupied, Position, RequiresUpdate,
     },
     construction::UnderConstruction,
-    helper::get_entity::get_entity,
-    production::ProductionBuilding,
+    helper::{
+        footprint::{footprint_free, spawn_parts},
+        get_entity::get_entity,
+    },
+    production::{Housing, ProductionBuilding},
     setup::BUILDING_LAYER_ID,
     statistics::Statistics,
     storage::StorageConsolidator,
@@ -25,6 +28,8 @@ pub fn building_placement(
     clicked_tile: Res<ClickedTile>,
     buildings: Res<BuildingSpecifications>,
     resources: Res<ResourceSpecifications>,
+    occupied_query: Query<(), With<Occupied>>,
+    blocked_query: Query<(), With<BlockedForBuilding>>,
 ) {
     if clicked_tile.dragging {
         return;
@@ -33,17 +38,31 @@ pub fn building_placement(
     if let Tool::Building(id) = &selected_tool.tool {
         if !clicked_tile.occupied_building && clicked_tile.can_build {
             if let Some(pos) = clicked_tile.pos {
-                let entity = get_entity(&mut commands, &mut map_query, pos, BUILDING_LAYER_ID);
-
                 let building = buildings.get(id).unwrap();
 
+                let footprint = building
+                    .rotated_footprint(selected_tool.rotation)
+                    .unwrap_or(Footprint {
+                        size: UVec2::new(1, 1),
+                    });
+
+                if !footprint_free(&map_query, &occupied_query, &blocked_query, pos, &footprint) {
+                    return;
+                }
+
+                let entity = get_entity(&mut commands, &mut map_query, pos, BUILDING_LAYER_ID);
+
                 let price = building.price(&resources);
 
                 commands
                     .entity(entity)
-                    .insert(Building { id: id.clone() })
+                    .insert(Building {
+                        id: id.clone(),
+                        rotation: selected_tool.rotation,
+                        any_side: false,
+                    })
                     .insert(Position { position: pos })
-                    .insert(MaintenanceCost::new_from_cost(price))
+                    .insert(MaintenanceCost::new_from_specification(building, price))
                     .insert(RequiresUpdate)
                     .insert(UnderConstruction::from_building_specification(building))
                     .insert(Occupied);
@@ -59,9 +78,24 @@ pub fn building_placement(
                                 .iter()
                                 .map(|product| (product.clone(), true))
                                 .collect(),
+                            workers: building.workers,
+                            ..Default::default()
                         })
                         .insert(Editable);
                 }
+
+                if let Some(housing) = &building.housing {
+                    commands
+                        .entity(entity)
+                        .insert(Statistics::default())
+                        .insert(StorageConsolidator::default())
+                        .insert(Housing::from(housing));
+                }
+
+                if footprint.size != UVec2::new(1, 1) {
+                    commands.entity(entity).insert(footprint);
+                    spawn_parts(&mut commands, &mut map_query, entity, pos, &footprint);
+                }
             }
         }
     }
@@ -1,74 +1,481 @@
+use std::collections::{HashMap, HashSet};
+
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
 use crate::game::{
-    assets::{ClickedTile, Position, RemovedBuildingEvent},
+    account::{AccountTransaction, TransactionCategory},
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications, BuildingPart, ClickedTile, Footprint,
+        Position, RemovedBuildingEvent,
+    },
+    blueprint::entity_price,
     car::Car,
     constants::MapTile,
+    construction::{ConstructionSettings, UnderConstruction},
+    conveyor::Conveyor,
+    helper::footprint::covered_positions,
+    highlight_tiles::HighlightTilesUpdateEvent,
+    history::{snapshot, EditCommand, EditHistory, PlacedBuilding, SnapshotQueries},
+    market::Market,
+    pipeline::Pipeline,
     production::Idle,
+    rail::Track,
     setup::{BUILDING_LAYER_ID, GROUND_LAYER_ID, MAP_ID},
+    state_manager::BuildingEntity,
     statistics::{StatisticTracker, Statistics},
+    storage::Storage,
     street::Street,
+    train::Train,
 };
 
-use super::{update_neighbor_streets, SelectedTool, Tool};
+use super::{drag_area, drag_rect, update_neighbor_streets, Drag, SelectedTool, Tool};
+
+// share of the money spent on a finished building returned when it is removed
+pub const BULLDOZE_REFUND: f64 = 0.25;
+// share of the market price paid for goods left in a removed storage
+pub const SALVAGE_DISCOUNT: f64 = 0.5;
+
+// removes the building covering a position, sent by the bulldozer and cancelled construction
+pub struct DemolishEvent {
+    pub position: UVec2,
+}
+
+// buildings in the area currently dragged with the bulldozer
+#[derive(Default)]
+pub struct BulldozePreview {
+    pub buildings: usize,
+    // refunds and the sale of salvaged goods
+    pub refund: i64,
+}
+
+// map generated buildings were never paid for and return nothing
+pub fn bulldoze_refund(
+    item: &PlacedBuilding,
+    buildings: &BuildingSpecifications,
+    resources: &ResourceSpecifications,
+) -> i64 {
+    let construction = item.under_construction.as_ref();
+    let refund = construction.map(|c| c.refund()).unwrap_or_default();
+
+    // an upgrade site also stands on the finished building it was spent on
+    let finished = construction.map_or(true, |c| c.upgrade);
+    let spent = match item.spent {
+        Some(spent) => spent,
+        // finished before the money spent was kept
+        None if finished => entity_price(&item.building, buildings, resources) as f64,
+        None => 0.0,
+    };
+
+    refund + (spent * BULLDOZE_REFUND) as i64
+}
+
+fn area_buildings(
+    origin: UVec2,
+    size: UVec2,
+    map_query: &MapQuery,
+    snapshot_queries: &SnapshotQueries,
+) -> Vec<PlacedBuilding> {
+    let mut removed: Vec<PlacedBuilding> = vec![];
+
+    for pos in (Footprint { size }).positions(origin) {
+        if let Ok(entity) = map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
+            if let Some(item) = snapshot(entity, snapshot_queries) {
+                if removed.iter().all(|other| other.position != item.position) {
+                    removed.push(item);
+                }
+            }
+        }
+    }
+
+    removed
+}
 
 pub fn bulldoze(
     mut commands: Commands,
-    mut map_query: MapQuery,
-    selected_tool: Res<SelectedTool>,
+    mut selected_tool: ResMut<SelectedTool>,
     clicked_tile: Res<ClickedTile>,
+    mouse_input: Res<Input<MouseButton>>,
+    car_query: Query<(Entity, &Position), With<Car>>,
+    train_query: Query<(Entity, &Position), With<Train>>,
+    mut demolish_events: EventWriter<DemolishEvent>,
+    map_query: MapQuery,
+    mut history: ResMut<EditHistory>,
+    snapshot_queries: SnapshotQueries,
+    mut preview: ResMut<BulldozePreview>,
+    mut highlight: EventWriter<HighlightTilesUpdateEvent>,
+    mut transactions: EventWriter<AccountTransaction>,
+    specifications: (Res<BuildingSpecifications>, Res<ResourceSpecifications>),
+    salvage_params: (
+        Res<ConstructionSettings>,
+        Res<Market>,
+        Query<(), With<UnderConstruction>>,
+    ),
+) {
+    let (buildings, resources) = specifications;
+
+    if Tool::Bulldoze != selected_tool.tool {
+        return;
+    }
+
+    if !clicked_tile.dragging && clicked_tile.occupied_vehicle {
+        if let Some(pos) = clicked_tile.vehicle_pos {
+            for (entity, position) in car_query.iter() {
+                if position.position == pos {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
+        return;
+    }
+
+    let (origin, size) = match drag_area(&mut selected_tool, &clicked_tile, &mouse_input) {
+        Drag::Moved(start, end) => {
+            let (origin, size) = drag_rect(start, end);
+            highlight.send(HighlightTilesUpdateEvent::from_positions(
+                Footprint { size }.positions(origin),
+            ));
+
+            let removed = area_buildings(origin, size, &map_query, &snapshot_queries);
+            *preview = BulldozePreview {
+                buildings: removed.len(),
+                refund: removed
+                    .iter()
+                    .map(|item| bulldoze_refund(item, &buildings, &resources))
+                    .sum::<i64>()
+                    + salvage_estimate(
+                        &removed,
+                        &map_query,
+                        &snapshot_queries,
+                        &salvage_params,
+                        &resources,
+                    ),
+            };
+            return;
+        }
+        Drag::Released(start, end) => drag_rect(start, end),
+        Drag::None => return,
+    };
+
+    highlight.send(HighlightTilesUpdateEvent::from_positions(vec![]));
+    *preview = BulldozePreview::default();
+
+    let footprint = Footprint { size };
+    let area = footprint.positions(origin);
+    let mut trains = false;
+    for (entity, position) in train_query.iter() {
+        if area.contains(&position.position) {
+            commands.entity(entity).despawn_recursive();
+            trains = true;
+        }
+    }
+
+    // clicking a train only removes the train, not the track below
+    if trains && size == UVec2::new(1, 1) {
+        return;
+    }
+
+    let removed = area_buildings(origin, size, &map_query, &snapshot_queries);
+    if removed.is_empty() {
+        return;
+    }
+
+    let removed = removed
+        .into_iter()
+        .map(|mut item| {
+            let refund = bulldoze_refund(&item, &buildings, &resources);
+            if refund > 0 {
+                transactions.send(AccountTransaction {
+                    amount: refund,
+                    category: TransactionCategory::Construction,
+                    source: None,
+                });
+            }
+
+            demolish_events.send(DemolishEvent {
+                position: item.position,
+            });
+
+            // stored goods are salvaged, undoing brings back an empty storage
+            if let BuildingEntity::Storage(storage) = &mut item.building {
+                storage.clear();
+            }
+
+            (item, refund)
+        })
+        .collect();
+
+    history.push(EditCommand::Bulldozed(removed));
+}
+
+pub fn demolish(
+    mut commands: Commands,
+    mut map_query: MapQuery,
+    mut demolish_events: EventReader<DemolishEvent>,
     street_query: Query<&Street>,
     statistics_query: Query<&Statistics>,
-    car_query: Query<(Entity, &Position), With<Car>>,
+    track_query: Query<&Track>,
+    conveyor_query: Query<&Conveyor>,
+    pipeline_query: Query<&Pipeline>,
     idle_query: Query<&Idle>,
     mut deleted_export_statistics: ResMut<StatisticTracker>,
     mut tile_query: Query<&mut Tile>,
     mut removed_events: EventWriter<RemovedBuildingEvent>,
+    structure_queries: (
+        Query<&BuildingPart>,
+        Query<(&Footprint, &Position)>,
+        Query<(), With<UnderConstruction>>,
+    ),
+    salvage_params: (
+        Res<ConstructionSettings>,
+        ResMut<Market>,
+        Res<ResourceSpecifications>,
+        EventWriter<AccountTransaction>,
+        Query<&mut Storage>,
+    ),
 ) {
-    if clicked_tile.dragging {
-        return;
-    }
+    let (part_query, footprint_query, site_query) = structure_queries;
+    let (settings, mut market, resources, mut transactions, mut storage_query) = salvage_params;
+
+    let events = demolish_events
+        .iter()
+        .map(|event| event.position)
+        .collect::<Vec<_>>();
 
-    if Tool::Bulldoze == selected_tool.tool {
-        if clicked_tile.occupied_vehicle {
-            if let Some(pos) = clicked_tile.vehicle_pos {
-                for (entity, position) in car_query.iter() {
-                    if position.position == pos {
+    // storages removed together can not take each other's goods
+    let removed = events
+        .iter()
+        .filter_map(|pos| {
+            map_query
+                .get_tile_entity(*pos, MAP_ID, BUILDING_LAYER_ID)
+                .ok()
+        })
+        .map(|entity| {
+            part_query
+                .get(entity)
+                .map(|part| part.main)
+                .unwrap_or(entity)
+        })
+        .collect::<HashSet<_>>();
+
+    for pos in events {
+        let positions = match map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
+            Ok(entity) => {
+                let (entity, positions) =
+                    covered_positions(entity, &part_query, &footprint_query, pos);
+
+                if let Ok(idle) = idle_query.get(entity) {
+                    if let Some(entity) = idle.entity {
                         commands.entity(entity).despawn_recursive();
                     }
                 }
-            }
-        } else if clicked_tile.occupied_building {
-            if let Some(pos) = clicked_tile.pos {
-                if let Ok(entity) = map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
-                    if let Ok(idle) = idle_query.get(entity) {
-                        if let Some(entity) = idle.entity {
-                            commands.entity(entity).despawn_recursive();
-                        }
-                    }
 
-                    if let Ok(statistics) = statistics_query.get(entity) {
-                        deleted_export_statistics.merge(&statistics.export);
+                if let Ok(statistics) = statistics_query.get(entity) {
+                    deleted_export_statistics.merge(&statistics.export);
+                }
+
+                if storage_query.get_mut(entity).is_ok() {
+                    let neighbors = if settings.salvage {
+                        neighbor_storages(
+                            &map_query,
+                            &part_query,
+                            &site_query,
+                            &positions,
+                            &removed,
+                        )
+                    } else {
+                        vec![]
+                    };
+
+                    let revenue = salvage(
+                        entity,
+                        &neighbors,
+                        &mut storage_query,
+                        &mut market,
+                        &resources,
+                    );
+                    if revenue > 0 {
+                        transactions.send(AccountTransaction {
+                            amount: revenue,
+                            category: TransactionCategory::Export,
+                            source: None,
+                        });
                     }
                 }
 
-                let _ = map_query.despawn_tile(&mut commands, pos, MAP_ID, BUILDING_LAYER_ID);
-                map_query.notify_chunk_for_tile(pos, MAP_ID, BUILDING_LAYER_ID);
+                positions
+            }
+            Err(_) => vec![pos],
+        };
+
+        for pos in positions {
+            let _ = map_query.despawn_tile(&mut commands, pos, MAP_ID, BUILDING_LAYER_ID);
+            map_query.notify_chunk_for_tile(pos, MAP_ID, BUILDING_LAYER_ID);
+
+            removed_events.send(RemovedBuildingEvent { position: pos });
 
-                removed_events.send(RemovedBuildingEvent { position: pos });
+            update_neighbor_streets(&mut commands, &mut map_query, pos, &street_query);
+            update_neighbor_streets(&mut commands, &mut map_query, pos, &track_query);
+            update_neighbor_streets(&mut commands, &mut map_query, pos, &conveyor_query);
+            update_neighbor_streets(&mut commands, &mut map_query, pos, &pipeline_query);
 
-                update_neighbor_streets(&mut commands, &mut map_query, pos, street_query);
+            let entity = map_query
+                .get_tile_entity(pos, MAP_ID, GROUND_LAYER_ID)
+                .unwrap();
 
-                let entity = map_query
-                    .get_tile_entity(pos, MAP_ID, GROUND_LAYER_ID)
-                    .unwrap();
+            let mut tile = tile_query.get_mut(entity).unwrap();
+            tile.texture_index = MapTile::Ground as u16;
 
-                let mut tile = tile_query.get_mut(entity).unwrap();
-                tile.texture_index = MapTile::Ground as u16;
+            map_query.notify_chunk_for_tile(pos, MAP_ID, GROUND_LAYER_ID);
+        }
+    }
+}
 
-                map_query.notify_chunk_for_tile(pos, MAP_ID, GROUND_LAYER_ID);
+// construction sites can not take goods yet
+fn neighbor_storages(
+    map_query: &MapQuery,
+    part_query: &Query<&BuildingPart>,
+    site_query: &Query<(), With<UnderConstruction>>,
+    positions: &[UVec2],
+    removed: &HashSet<Entity>,
+) -> Vec<Entity> {
+    let mut neighbors = vec![];
+
+    for pos in positions {
+        let tiles = map_query.get_tile_neighbors(*pos, MAP_ID, BUILDING_LAYER_ID);
+        for (_pos, neighbor) in tiles[0..4].iter() {
+            if let Some(neighbor) = neighbor {
+                let neighbor = part_query
+                    .get(*neighbor)
+                    .map(|part| part.main)
+                    .unwrap_or(*neighbor);
+
+                if !removed.contains(&neighbor)
+                    && !neighbors.contains(&neighbor)
+                    && site_query.get(neighbor).is_err()
+                {
+                    neighbors.push(neighbor);
+                }
             }
         }
     }
+
+    neighbors
+}
+
+// moves the goods of a removed storage to its neighbors and sells what is left at a discount
+fn salvage(
+    entity: Entity,
+    neighbors: &[Entity],
+    storage_query: &mut Query<&mut Storage>,
+    market: &mut Market,
+    resources: &ResourceSpecifications,
+) -> i64 {
+    let goods = match storage_query.get_mut(entity) {
+        Ok(mut storage) => {
+            let goods = storage.goods();
+            storage.clear();
+            goods
+        }
+        Err(_) => return 0,
+    };
+
+    let mut revenue = 0.0;
+    for (resource, mut amount) in goods {
+        for neighbor in neighbors {
+            if let Ok(mut storage) = storage_query.get_mut(*neighbor) {
+                amount = salvage_into(&mut storage, &resource, amount, resources);
+            }
+        }
+
+        if amount > 0.0 {
+            revenue += amount * market.export_price(&resource, resources) * SALVAGE_DISCOUNT;
+            market.track_export(&resource, amount);
+        }
+    }
+
+    revenue as i64
+}
+
+// goods only go to storages of their own resource, never into warehouses
+fn salvage_into(
+    storage: &mut Storage,
+    resource: &str,
+    amount: f64,
+    resources: &ResourceSpecifications,
+) -> f64 {
+    if storage.is_warehouse() {
+        return amount;
+    }
+
+    storage.absorb(resource, amount, resources)
+}
+
+// revenue of the goods in removed storages which their neighbors can not take
+fn salvage_estimate(
+    removed: &[PlacedBuilding],
+    map_query: &MapQuery,
+    snapshot_queries: &SnapshotQueries,
+    salvage_params: &(
+        Res<ConstructionSettings>,
+        Res<Market>,
+        Query<(), With<UnderConstruction>>,
+    ),
+    resources: &ResourceSpecifications,
+) -> i64 {
+    let (part_query, _, building_queries) = snapshot_queries;
+    let storage_query = &building_queries.1;
+    let (settings, market, site_query) = salvage_params;
+
+    let removed_entities = removed
+        .iter()
+        .filter_map(|item| {
+            map_query
+                .get_tile_entity(item.position, MAP_ID, BUILDING_LAYER_ID)
+                .ok()
+        })
+        .collect::<HashSet<_>>();
+
+    // neighbors filling up are tracked on copies
+    let mut filled: HashMap<Entity, Storage> = HashMap::new();
+    let mut revenue = 0.0;
+    for item in removed.iter() {
+        let storage = match &item.building {
+            BuildingEntity::Storage(storage) => storage,
+            _ => continue,
+        };
+
+        let neighbors = if settings.salvage {
+            let positions = storage
+                .footprint()
+                .map_or(vec![item.position], |footprint| {
+                    footprint.positions(item.position)
+                });
+            neighbor_storages(
+                map_query,
+                part_query,
+                site_query,
+                &positions,
+                &removed_entities,
+            )
+        } else {
+            vec![]
+        };
+
+        for (resource, mut amount) in storage.goods() {
+            for neighbor in neighbors.iter() {
+                if let Ok(storage) = storage_query.get(*neighbor) {
+                    let storage = filled.entry(*neighbor).or_insert_with(|| storage.clone());
+                    amount = salvage_into(storage, &resource, amount, resources);
+                }
+            }
+
+            if amount > 0.0 {
+                revenue += amount * market.export_price(&resource, resources) * SALVAGE_DISCOUNT;
+            }
+        }
+    }
+
+    revenue as i64
 }
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::game::{
-    account::{Account, AccountTransaction, MaintenanceCost, PurchaseCost},
+    account::{Account, AccountTransaction, MaintenanceCost, PurchaseCost, TransactionCategory},
     assets::{resource_specifications::ResourceSpecifications, ClickedTile, Editable, Position},
     car::Car,
     constants::CAR_STORAGE_SIZE,
@@ -28,9 +28,8 @@ pub fn car_placement(
                 let car = Car::default();
 
                 let storage = Storage {
-                    resource: resource.clone(),
                     capacity: CAR_STORAGE_SIZE,
-                    ..Default::default()
+                    ..Storage::for_resource(resource, &resources)
                 };
 
                 let price = (car.clone(), storage.clone()).price(&resources);
@@ -38,7 +37,11 @@ pub fn car_placement(
                     return;
                 }
 
-                events.send(AccountTransaction { amount: -price });
+                events.send(AccountTransaction {
+                    amount: -price,
+                    category: TransactionCategory::Vehicle,
+                    source: None,
+                });
 
                 commands
                     .spawn()
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    account::{MaintenanceCost, PurchaseCost},
+    assets::{
+        resource_specifications::ResourceSpecifications, ClickedTile, Editable, Occupied, Position,
+        RequiresUpdate,
+    },
+    construction::UnderConstruction,
+    conveyor::{direction_between, Conveyor},
+    helper::get_entity::get_entity,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+};
+
+use super::{SelectedTool, Tool};
+
+pub fn conveyor_placement(
+    mut commands: Commands,
+    mut conveyor_query: Query<&mut Conveyor>,
+    mut map_query: MapQuery,
+    selected_tool: Res<SelectedTool>,
+    clicked_tile: Res<ClickedTile>,
+    resources: Res<ResourceSpecifications>,
+    mut last_placed: Local<Option<UVec2>>,
+) {
+    if selected_tool.tool != Tool::Conveyor {
+        *last_placed = None;
+        return;
+    }
+
+    if !clicked_tile.dragging {
+        *last_placed = None;
+    }
+
+    if clicked_tile.occupied_building || !clicked_tile.can_build {
+        return;
+    }
+
+    if let Some(pos) = clicked_tile.pos {
+        let mut conveyor = Conveyor::default();
+
+        // dragging a belt points it from the previous tile to the new one
+        if let Some(last) = *last_placed {
+            let direction = direction_between(last, pos);
+            let adjacent =
+                (last.x as i64 - pos.x as i64).abs() + (last.y as i64 - pos.y as i64).abs() == 1;
+
+            if adjacent {
+                if let Ok(entity) = map_query.get_tile_entity(last, MAP_ID, BUILDING_LAYER_ID) {
+                    if let Ok(mut last_conveyor) = conveyor_query.get_mut(entity) {
+                        last_conveyor.direction = direction;
+                        conveyor.resource = last_conveyor.resource.clone();
+                        commands.entity(entity).insert(RequiresUpdate);
+                    }
+                }
+
+                conveyor.direction = direction;
+            }
+        }
+
+        let entity = get_entity(&mut commands, &mut map_query, pos, BUILDING_LAYER_ID);
+        let price = conveyor.price(&resources);
+
+        commands
+            .entity(entity)
+            .insert(conveyor)
+            .insert(RequiresUpdate)
+            .insert(MaintenanceCost::new_from_cost(price))
+            .insert(UnderConstruction::from_fixed_cost(price))
+            .insert(Position { position: pos })
+            .insert(Editable)
+            .insert(Occupied);
+
+        *last_placed = Some(pos);
+
+        let neighbors = map_query.get_tile_neighbors(pos, MAP_ID, BUILDING_LAYER_ID);
+        for (_pos, neighbor) in neighbors[0..4].iter() {
+            if let Some(neighbor) = neighbor {
+                if conveyor_query.get_mut(*neighbor).is_ok() {
+                    commands.entity(*neighbor).insert(RequiresUpdate);
+                }
+            }
+        }
+    }
+}
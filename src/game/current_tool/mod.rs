@@ -1,20 +1,35 @@
+pub mod blueprint;
 pub mod building;
 pub mod bulldoze;
 pub mod car;
+pub mod conveyor;
 pub mod delivery_station;
 pub mod depot;
 pub mod import_export_station;
+pub mod pipeline;
+pub mod rail;
 pub mod storage;
 pub mod storage_management;
 pub mod street;
+#[cfg(test)]
+mod tests;
+pub mod town_shop;
+pub mod train;
+pub mod train_station;
 
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
+use bevy_egui::EguiContext;
 
 use super::{
-    assets::RequiresUpdate,
+    assets::{BuildingPart, ClickedTile, Footprint, RequiresUpdate},
+    conveyor::Conveyor,
+    pipeline::Pipeline,
     production::ImportExportDirection,
+    rail::Track,
     setup::{BUILDING_LAYER_ID, MAP_ID},
+    storage::StorageConsolidator,
+    street::Street,
 };
 
 #[derive(PartialEq, Eq, Debug)]
@@ -24,21 +39,172 @@ pub enum Tool {
     Street,
     Path,
     Storage(String),
+    RefrigeratedStorage(String),
+    Warehouse,
+    Silo(String),
     ImportExportStation(ImportExportDirection),
     DeliveryStation,
+    TownShop,
     StorageManagement,
     Depot,
     Car(String),
     Building(String),
+    Rail,
+    TrainStation,
+    Train,
+    Conveyor,
+    Pipeline,
+    BlueprintSelect,
+    BlueprintPaste,
 }
 
 pub struct SelectedTool {
     pub tool: Tool,
+    // quarter turns clockwise of placed buildings
+    pub rotation: u8,
+    // corners of the area dragged with the mouse
+    pub drag: Option<(UVec2, UVec2)>,
 }
 
 impl Default for SelectedTool {
     fn default() -> Self {
-        Self { tool: Tool::None }
+        Self {
+            tool: Tool::None,
+            rotation: 0,
+            drag: None,
+        }
+    }
+}
+
+pub enum Drag {
+    None,
+    Moved(UVec2, UVec2),
+    Released(UVec2, UVec2),
+}
+
+// follows a drag from the pressed tile, the area is used once the mouse is released
+pub fn drag_area(
+    selected_tool: &mut SelectedTool,
+    clicked_tile: &ClickedTile,
+    mouse_input: &Input<MouseButton>,
+) -> Drag {
+    if let Some((start, end)) = selected_tool.drag {
+        if mouse_input.just_released(MouseButton::Left) {
+            selected_tool.drag = None;
+            return Drag::Released(start, end);
+        }
+    }
+
+    match (clicked_tile.pos, selected_tool.drag) {
+        (Some(pos), None) => {
+            if clicked_tile.dragging {
+                return Drag::None;
+            }
+
+            selected_tool.drag = Some((pos, pos));
+            Drag::Moved(pos, pos)
+        }
+        (Some(pos), Some((start, end))) => {
+            if pos == end {
+                return Drag::None;
+            }
+
+            selected_tool.drag = Some((start, pos));
+            Drag::Moved(start, pos)
+        }
+        // the pointer left the map or moved over a window
+        (None, Some(_)) => {
+            selected_tool.drag = None;
+            Drag::None
+        }
+        (None, None) => Drag::None,
+    }
+}
+
+// origin and size of the rectangle spanned by two corners
+pub fn drag_rect(start: UVec2, end: UVec2) -> (UVec2, UVec2) {
+    let origin = start.min(end);
+    (origin, start.max(end) - origin + UVec2::new(1, 1))
+}
+
+pub fn rotate_tool(
+    keyboard_input: Res<Input<KeyCode>>,
+    egui_context: Res<EguiContext>,
+    mut selected_tool: ResMut<SelectedTool>,
+) {
+    if egui_context.ctx().wants_keyboard_input() {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        selected_tool.rotation = (selected_tool.rotation + 1) % 4;
+    }
+}
+
+// origins along the longer axis of a drag, spaced so the footprints line up
+pub fn drag_row(start: UVec2, end: UVec2, footprint: &Footprint) -> Vec<UVec2> {
+    let dx = end.x as i64 - start.x as i64;
+    let dy = end.y as i64 - start.y as i64;
+
+    let horizontal = dx.abs() >= dy.abs();
+    let (distance, size) = if horizontal {
+        (dx, footprint.size.x as i64)
+    } else {
+        (dy, footprint.size.y as i64)
+    };
+
+    (0..=distance.abs() / size)
+        .filter_map(|step| {
+            let offset = distance.signum() * step * size;
+            let (x, y) = if horizontal {
+                (start.x as i64 + offset, start.y as i64)
+            } else {
+                (start.x as i64, start.y as i64 + offset)
+            };
+
+            if x < 0 || y < 0 {
+                None
+            } else {
+                Some(UVec2::new(x as u32, y as u32))
+            }
+        })
+        .collect()
+}
+
+// things around a placed building which have to pick it up
+pub type NeighborQueries<'a> = (
+    Query<'a, &'static BuildingPart>,
+    Query<'a, Entity, Or<(With<StorageConsolidator>, With<Conveyor>, With<Pipeline>)>>,
+    Query<'a, &'static Street>,
+    Query<'a, &'static Track>,
+);
+
+pub fn update_neighbors(
+    commands: &mut Commands,
+    map_query: &mut MapQuery,
+    origin: UVec2,
+    footprint: &Footprint,
+    queries: &NeighborQueries,
+) {
+    let (part_query, consolidator_query, street_query, track_query) = queries;
+
+    for pos in footprint.positions(origin) {
+        let neighbors = map_query.get_tile_neighbors(pos, MAP_ID, BUILDING_LAYER_ID);
+        for (_pos, neighbor) in neighbors.iter() {
+            if let Some(neighbor) = neighbor {
+                let neighbor = part_query
+                    .get(*neighbor)
+                    .map(|part| part.main)
+                    .unwrap_or(*neighbor);
+
+                if let Ok(entity) = consolidator_query.get(neighbor) {
+                    commands.entity(entity).insert(RequiresUpdate);
+                }
+            }
+        }
+
+        update_neighbor_streets(commands, map_query, pos, street_query);
+        update_neighbor_streets(commands, map_query, pos, track_query);
     }
 }
 
@@ -46,7 +212,7 @@ fn update_neighbor_streets<T: 'static + Send + Sync>(
     commands: &mut Commands,
     map_query: &mut MapQuery,
     pos: UVec2,
-    street_query: Query<&T>,
+    street_query: &Query<&T>,
 ) {
     let neighbors = map_query.get_tile_neighbors(pos, MAP_ID, BUILDING_LAYER_ID);
     for (_pos, neighbor) in neighbors[0..4].iter() {
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    account::{MaintenanceCost, PurchaseCost},
+    assets::{
+        resource_specifications::ResourceSpecifications, ClickedTile, Occupied, Position,
+        RequiresUpdate,
+    },
+    construction::UnderConstruction,
+    helper::get_entity::get_entity,
+    rail::{Rail, Track},
+    setup::BUILDING_LAYER_ID,
+};
+
+use super::{update_neighbor_streets, SelectedTool, Tool};
+
+pub fn rail_placement(
+    mut commands: Commands,
+    track_query: Query<&Track>,
+    mut map_query: MapQuery,
+    selected_tool: Res<SelectedTool>,
+    clicked_tile: Res<ClickedTile>,
+    resources: Res<ResourceSpecifications>,
+) {
+    if selected_tool.tool != Tool::Rail || clicked_tile.occupied_building || !clicked_tile.can_build
+    {
+        return;
+    }
+
+    if let Some(pos) = clicked_tile.pos {
+        let entity = get_entity(&mut commands, &mut map_query, pos, BUILDING_LAYER_ID);
+
+        let price = Rail.price(&resources);
+
+        commands
+            .entity(entity)
+            .insert(Rail)
+            .insert(Track)
+            .insert(RequiresUpdate)
+            .insert(MaintenanceCost::new_from_cost(price))
+            .insert(UnderConstruction::from_fixed_cost(price))
+            .insert(Position { position: pos })
+            .insert(Occupied);
+
+        update_neighbor_streets(&mut commands, &mut map_query, pos, &track_query);
+    }
+}
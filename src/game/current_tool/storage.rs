@@ -4,59 +4,97 @@ use bevy_ecs_tilemap::prelude::*;
 use crate::game::{
     account::{MaintenanceCost, PurchaseCost},
     assets::{
-        resource_specifications::ResourceSpecifications, ClickedTile, Occupied, Position,
-        RequiresUpdate,
+        resource_specifications::ResourceSpecifications, BlockedForBuilding, ClickedTile, Editable,
+        Footprint, Occupied, Position, RequiresUpdate,
     },
     construction::UnderConstruction,
-    helper::get_entity::get_entity,
-    setup::{BUILDING_LAYER_ID, MAP_ID},
-    storage::{Storage, StorageConsolidator},
+    helper::{
+        footprint::{footprint_free, spawn_parts},
+        get_entity::get_entity,
+    },
+    highlight_tiles::HighlightTilesUpdateEvent,
+    setup::BUILDING_LAYER_ID,
+    statistics::Statistics,
+    storage::Storage,
 };
 
-use super::{SelectedTool, Tool};
+use super::{drag_area, drag_row, update_neighbors, Drag, NeighborQueries, SelectedTool, Tool};
 
 pub fn storage_placement(
     mut commands: Commands,
     mut map_query: MapQuery,
-    selected_tool: Res<SelectedTool>,
-    consolidator_query: Query<Entity, With<StorageConsolidator>>,
+    mut selected_tool: ResMut<SelectedTool>,
     clicked_tile: Res<ClickedTile>,
+    mouse_input: Res<Input<MouseButton>>,
     resources: Res<ResourceSpecifications>,
+    occupied_query: Query<(), With<Occupied>>,
+    blocked_query: Query<(), With<BlockedForBuilding>>,
+    neighbor_queries: NeighborQueries,
+    mut highlight: EventWriter<HighlightTilesUpdateEvent>,
 ) {
-    if clicked_tile.dragging {
-        return;
-    }
+    let storage = match &selected_tool.tool {
+        Tool::Storage(resource) => Storage::for_resource(resource, &resources),
+        Tool::RefrigeratedStorage(resource) => Storage {
+            resource: resource.clone(),
+            refrigerated: true,
+            ..Default::default()
+        },
+        Tool::Warehouse => Storage::new_warehouse(),
+        Tool::Silo(resource) => Storage::new_silo(resource),
+        _ => return,
+    };
+
+    let footprint = storage.footprint().unwrap_or(Footprint {
+        size: UVec2::new(1, 1),
+    });
+
+    // dragging places a row of storages
+    let row = match drag_area(&mut selected_tool, &clicked_tile, &mouse_input) {
+        Drag::Moved(start, end) => {
+            let positions = drag_row(start, end, &footprint)
+                .into_iter()
+                .flat_map(|pos| footprint.positions(pos))
+                .collect();
+            highlight.send(HighlightTilesUpdateEvent::from_positions(positions));
+            return;
+        }
+        Drag::Released(start, end) => drag_row(start, end, &footprint),
+        Drag::None => return,
+    };
+
+    highlight.send(HighlightTilesUpdateEvent::from_positions(vec![]));
 
-    if let Tool::Storage(resource) = &selected_tool.tool {
-        if !clicked_tile.occupied_building && clicked_tile.can_build {
-            if let Some(pos) = clicked_tile.pos {
-                let entity = get_entity(&mut commands, &mut map_query, pos, BUILDING_LAYER_ID);
-
-                let storage = Storage {
-                    resource: resource.clone(),
-                    ..Default::default()
-                };
-
-                let price = storage.price(&resources);
-
-                commands
-                    .entity(entity)
-                    .insert(storage)
-                    .insert(RequiresUpdate)
-                    .insert(Position { position: pos })
-                    .insert(MaintenanceCost::new_from_cost(price))
-                    .insert(UnderConstruction::from_fixed_cost(price))
-                    .insert(Occupied);
-
-                let neighbors = map_query.get_tile_neighbors(pos, MAP_ID, BUILDING_LAYER_ID);
-                for (_pos, neighbor) in neighbors.iter() {
-                    if let Some(neighbor) = neighbor {
-                        if let Ok(entity) = consolidator_query.get(*neighbor) {
-                            commands.entity(entity).insert(RequiresUpdate);
-                        }
-                    }
-                }
-            }
+    for pos in row {
+        if !footprint_free(&map_query, &occupied_query, &blocked_query, pos, &footprint) {
+            continue;
         }
+
+        let entity = get_entity(&mut commands, &mut map_query, pos, BUILDING_LAYER_ID);
+
+        let price = storage.price(&resources);
+
+        commands
+            .entity(entity)
+            .insert(storage.clone())
+            .insert(RequiresUpdate)
+            .insert(Editable)
+            .insert(Statistics::default())
+            .insert(Position { position: pos })
+            .insert(MaintenanceCost::new_from_cost(price))
+            .insert(UnderConstruction::from_fixed_cost(price))
+            .insert(Occupied);
+
+        if footprint.size != UVec2::new(1, 1) {
+            commands.entity(entity).insert(footprint);
+            spawn_parts(&mut commands, &mut map_query, entity, pos, &footprint);
+        }
+
+        update_neighbors(
+            &mut commands,
+            &mut map_query,
+            pos,
+            &footprint,
+            &neighbor_queries,
+        );
     }
 }
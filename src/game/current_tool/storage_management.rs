@@ -4,7 +4,7 @@ use bevy_ecs_tilemap::prelude::*;
 use crate::game::{
     account::{MaintenanceCost, PurchaseCost},
     assets::{
-        resource_specifications::ResourceSpecifications, ClickedTile, Occupied, Position,
+        resource_specifications::ResourceSpecifications, ClickedTile, Editable, Occupied, Position,
         RequiresUpdate,
     },
     construction::UnderConstruction,
@@ -38,7 +38,8 @@ pub fn storage_management_placement(
 
             commands
                 .entity(entity)
-                .insert(StorageManagement)
+                .insert(StorageManagement::default())
+                .insert(Editable)
                 .insert(StorageConsolidator::default())
                 .insert(MaintenanceCost::new_from_cost(price))
                 .insert(UnderConstruction::from_fixed_cost(price))
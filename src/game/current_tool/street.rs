@@ -43,11 +43,12 @@ pub fn street_placement(
             .insert(street)
             .insert(RequiresUpdate)
             .insert(MaintenanceCost::new_from_cost(price))
+            .insert(UnderConstruction::from_fixed_cost(price))
             .insert(Position { position: pos })
             .insert(CanDriveOver)
             .insert(Occupied);
 
-        update_neighbor_streets(&mut commands, &mut map_query, pos, street_query);
+        update_neighbor_streets(&mut commands, &mut map_query, pos, &street_query);
     }
 }
 
@@ -82,6 +83,6 @@ pub fn path_placement(
             .insert(CanDriveOver)
             .insert(Occupied);
 
-        update_neighbor_streets(&mut commands, &mut map_query, pos, street_query);
+        update_neighbor_streets(&mut commands, &mut map_query, pos, &street_query);
     }
 }
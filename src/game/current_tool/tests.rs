@@ -0,0 +1,101 @@
+use super::*;
+
+#[test]
+fn drag_rect_from_any_corner() {
+    let (origin, size) = drag_rect(UVec2::new(5, 2), UVec2::new(3, 6));
+
+    assert_eq!(origin, UVec2::new(3, 2));
+    assert_eq!(size, UVec2::new(3, 5));
+}
+
+#[test]
+fn drag_row_follows_longer_axis() {
+    let footprint = Footprint {
+        size: UVec2::new(1, 1),
+    };
+
+    let row = drag_row(UVec2::new(4, 4), UVec2::new(1, 5), &footprint);
+    assert_eq!(
+        row,
+        vec![
+            UVec2::new(4, 4),
+            UVec2::new(3, 4),
+            UVec2::new(2, 4),
+            UVec2::new(1, 4),
+        ]
+    );
+
+    let row = drag_row(UVec2::new(4, 4), UVec2::new(4, 4), &footprint);
+    assert_eq!(row, vec![UVec2::new(4, 4)]);
+}
+
+#[test]
+fn drag_row_spaces_footprints() {
+    let footprint = Footprint {
+        size: UVec2::new(2, 3),
+    };
+
+    let row = drag_row(UVec2::new(2, 2), UVec2::new(3, 9), &footprint);
+    assert_eq!(
+        row,
+        vec![UVec2::new(2, 2), UVec2::new(2, 5), UVec2::new(2, 8)]
+    );
+}
+
+#[test]
+fn drag_area_is_used_once_released() {
+    let mut selected_tool = SelectedTool::default();
+    let mut mouse_input = Input::<MouseButton>::default();
+    let mut clicked_tile = ClickedTile {
+        pos: Some(UVec2::new(1, 1)),
+        ..Default::default()
+    };
+
+    mouse_input.press(MouseButton::Left);
+    assert!(matches!(
+        drag_area(&mut selected_tool, &clicked_tile, &mouse_input),
+        Drag::Moved(_, _)
+    ));
+
+    clicked_tile.dragging = true;
+    clicked_tile.pos = Some(UVec2::new(3, 2));
+    assert!(matches!(
+        drag_area(&mut selected_tool, &clicked_tile, &mouse_input),
+        Drag::Moved(_, _)
+    ));
+
+    mouse_input.clear();
+    mouse_input.release(MouseButton::Left);
+    clicked_tile.pos = None;
+    match drag_area(&mut selected_tool, &clicked_tile, &mouse_input) {
+        Drag::Released(start, end) => {
+            assert_eq!(start, UVec2::new(1, 1));
+            assert_eq!(end, UVec2::new(3, 2));
+        }
+        _ => panic!("expected a released drag"),
+    }
+    assert!(selected_tool.drag.is_none());
+}
+
+#[test]
+fn drag_area_is_cancelled_off_the_map() {
+    let mut selected_tool = SelectedTool::default();
+    let mut mouse_input = Input::<MouseButton>::default();
+    let mut clicked_tile = ClickedTile {
+        pos: Some(UVec2::new(1, 1)),
+        ..Default::default()
+    };
+
+    mouse_input.press(MouseButton::Left);
+    drag_area(&mut selected_tool, &clicked_tile, &mouse_input);
+
+    // still held, but over a window
+    mouse_input.clear();
+    clicked_tile.dragging = true;
+    clicked_tile.pos = None;
+    assert!(matches!(
+        drag_area(&mut selected_tool, &clicked_tile, &mouse_input),
+        Drag::None
+    ));
+    assert!(selected_tool.drag.is_none());
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    account::{Account, AccountTransaction, MaintenanceCost, PurchaseCost, TransactionCategory},
+    assets::{resource_specifications::ResourceSpecifications, ClickedTile, Editable, Position},
+    construction::UnderConstruction,
+    rail::Track,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    train::Train,
+};
+
+use super::{SelectedTool, Tool};
+
+pub fn train_placement(
+    mut commands: Commands,
+    mut selected_tool: ResMut<SelectedTool>,
+    clicked_tile: Res<ClickedTile>,
+    map_query: MapQuery,
+    track_query: Query<(), (With<Track>, Without<UnderConstruction>)>,
+    train_query: Query<&Position, With<Train>>,
+    resources: Res<ResourceSpecifications>,
+    mut events: EventWriter<AccountTransaction>,
+    account: Res<Account>,
+) {
+    if clicked_tile.dragging || selected_tool.tool != Tool::Train {
+        return;
+    }
+
+    if let Some(pos) = clicked_tile.pos {
+        let on_track = match map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
+            Ok(entity) => track_query.get(entity).is_ok(),
+            Err(_) => false,
+        };
+
+        if !on_track || train_query.iter().any(|position| position.position == pos) {
+            return;
+        }
+
+        let train = Train::default();
+
+        let price = train.price(&resources);
+        if account.value < price {
+            return;
+        }
+
+        events.send(AccountTransaction {
+            amount: -price,
+            category: TransactionCategory::Vehicle,
+            source: None,
+        });
+
+        commands
+            .spawn()
+            .insert(Position { position: pos })
+            .insert(train)
+            .insert(MaintenanceCost::new_from_cost(price))
+            .insert(Editable);
+
+        selected_tool.tool = Tool::None;
+    }
+}
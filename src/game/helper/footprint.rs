@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    assets::{BlockedForBuilding, BuildingPart, Footprint, Occupied, Position, RequiresUpdate},
+    setup::{BUILDING_LAYER_ID, GROUND_LAYER_ID, MAP_ID},
+};
+
+use super::get_entity::get_entity;
+
+pub fn footprint_free(
+    map_query: &MapQuery,
+    occupied_query: &Query<(), With<Occupied>>,
+    blocked_query: &Query<(), With<BlockedForBuilding>>,
+    origin: UVec2,
+    footprint: &Footprint,
+) -> bool {
+    let (_entity, layer) = map_query.get_layer(MAP_ID, BUILDING_LAYER_ID).unwrap();
+    let size = layer.get_layer_size_in_tiles();
+
+    footprint.positions(origin).into_iter().all(|pos| {
+        if pos.x >= size.x || pos.y >= size.y {
+            return false;
+        }
+
+        let occupied = map_query
+            .get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID)
+            .map(|entity| occupied_query.get(entity).is_ok())
+            .unwrap_or(false);
+        let blocked = map_query
+            .get_tile_entity(pos, MAP_ID, GROUND_LAYER_ID)
+            .map(|entity| blocked_query.get(entity).is_ok())
+            .unwrap_or(false);
+
+        !occupied && !blocked
+    })
+}
+
+// occupies all tiles besides the origin with parts pointing to the main entity
+pub fn spawn_parts(
+    commands: &mut Commands,
+    map_query: &mut MapQuery,
+    main: Entity,
+    origin: UVec2,
+    footprint: &Footprint,
+) {
+    for pos in footprint.positions(origin).into_iter().skip(1) {
+        let entity = get_entity(commands, map_query, pos, BUILDING_LAYER_ID);
+
+        commands
+            .entity(entity)
+            .insert(BuildingPart { main })
+            .insert(RequiresUpdate)
+            .insert(Position { position: pos })
+            .insert(Occupied);
+    }
+}
+
+// all tiles covered by the building at the given tile
+pub fn covered_positions(
+    entity: Entity,
+    part_query: &Query<&BuildingPart>,
+    footprint_query: &Query<(&Footprint, &Position)>,
+    pos: UVec2,
+) -> (Entity, Vec<UVec2>) {
+    let main = part_query
+        .get(entity)
+        .map(|part| part.main)
+        .unwrap_or(entity);
+
+    match footprint_query.get(main) {
+        Ok((footprint, position)) => (main, footprint.positions(position.position)),
+        Err(_) => (main, vec![pos]),
+    }
+}
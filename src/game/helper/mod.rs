@@ -1,3 +1,4 @@
 pub mod eval_neighbor;
+pub mod footprint;
 pub mod get_entity;
 pub mod neighbor_structure;
@@ -0,0 +1,429 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use bevy_egui::EguiContext;
+
+use crate::game::{
+    account::{AccountTransaction, TransactionCategory},
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications, BlockedForBuilding, Building,
+        BuildingPart, Footprint, Occupied, Position,
+    },
+    blueprint::entity_size,
+    car::{Car, CarController},
+    construction::{ConstructionSpent, UnderConstruction},
+    current_tool::{bulldoze::DemolishEvent, update_neighbors, NeighborQueries},
+    helper::footprint::footprint_free,
+    production::Depot,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    state_manager::{
+        load_game::spawn_building, serialize_building, BuildingEntity, BuildingQueries,
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+// number of edits which can be undone
+pub const HISTORY_LENGTH: usize = 50;
+
+// buildings spawned by the game itself, e.g. loaded or restored, are not recorded as edits
+pub struct Unrecorded;
+
+#[derive(Clone)]
+pub struct PlacedBuilding {
+    pub position: UVec2,
+    pub building: BuildingEntity,
+    pub under_construction: Option<UnderConstruction>,
+    pub spent: Option<f64>,
+}
+
+#[derive(Clone)]
+pub enum EditCommand {
+    Placed(Vec<PlacedBuilding>),
+    // each removed building with the refund paid for it
+    Bulldozed(Vec<(PlacedBuilding, i64)>),
+    CarProgram {
+        car: Entity,
+        before: CarController,
+        after: CarController,
+    },
+    DepotEdit {
+        depot: Entity,
+        before: Depot,
+        after: Depot,
+    },
+}
+
+#[derive(Default)]
+pub struct EditHistory {
+    undo: Vec<EditCommand>,
+    redo: Vec<EditCommand>,
+}
+
+impl EditHistory {
+    pub fn push(&mut self, command: EditCommand) {
+        self.redo.clear();
+        self.undo.push(command);
+
+        if self.undo.len() > HISTORY_LENGTH {
+            self.undo.remove(0);
+        }
+    }
+
+    pub fn undo(&mut self) -> Option<EditCommand> {
+        let command = self.undo.pop()?;
+        self.redo.push(command.clone());
+        Some(command)
+    }
+
+    pub fn redo(&mut self) -> Option<EditCommand> {
+        let command = self.redo.pop()?;
+        self.undo.push(command.clone());
+        Some(command)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+pub enum HistoryEvent {
+    Undo,
+    Redo,
+}
+
+pub type SnapshotQueries<'a> = (
+    Query<'a, &'static BuildingPart>,
+    Query<
+        'a,
+        (
+            &'static Position,
+            Option<&'static UnderConstruction>,
+            Option<&'static ConstructionSpent>,
+        ),
+    >,
+    BuildingQueries<'a>,
+);
+
+// captures the building an entity belongs to, so it can be spawned again later
+pub fn snapshot(entity: Entity, queries: &SnapshotQueries) -> Option<PlacedBuilding> {
+    let (part_query, position_query, building_queries) = queries;
+
+    let entity = part_query
+        .get(entity)
+        .map(|part| part.main)
+        .unwrap_or(entity);
+    let (position, under_construction, spent) = position_query.get(entity).ok()?;
+
+    Some(PlacedBuilding {
+        position: position.position,
+        building: serialize_building(entity, building_queries)?,
+        under_construction: under_construction.cloned(),
+        spent: spent.map(|spent| spent.0),
+    })
+}
+
+pub fn record_placements(
+    mut commands: Commands,
+    mut history: ResMut<EditHistory>,
+    placed_query: Query<Entity, (Added<Occupied>, Without<BuildingPart>, Without<Unrecorded>)>,
+    unrecorded_query: Query<Entity, Added<Unrecorded>>,
+    snapshot_queries: SnapshotQueries,
+) {
+    let placed = placed_query
+        .iter()
+        .filter_map(|entity| snapshot(entity, &snapshot_queries))
+        .collect::<Vec<_>>();
+
+    if !placed.is_empty() {
+        history.push(EditCommand::Placed(placed));
+    }
+
+    for entity in unrecorded_query.iter() {
+        commands.entity(entity).remove::<Unrecorded>();
+    }
+}
+
+pub fn history_shortcuts(
+    keyboard_input: Res<Input<KeyCode>>,
+    egui_context: Res<EguiContext>,
+    mut history_events: EventWriter<HistoryEvent>,
+) {
+    if egui_context.ctx().wants_keyboard_input() {
+        return;
+    }
+
+    let control =
+        keyboard_input.pressed(KeyCode::LControl) || keyboard_input.pressed(KeyCode::RControl);
+    let shift = keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift);
+    if !control {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Z) {
+        if shift {
+            history_events.send(HistoryEvent::Redo);
+        } else {
+            history_events.send(HistoryEvent::Undo);
+        }
+    } else if keyboard_input.just_pressed(KeyCode::Y) {
+        history_events.send(HistoryEvent::Redo);
+    }
+}
+
+pub fn apply_history(
+    mut commands: Commands,
+    mut map_query: MapQuery,
+    mut history: ResMut<EditHistory>,
+    mut history_events: EventReader<HistoryEvent>,
+    buildings: Res<BuildingSpecifications>,
+    resources: Res<ResourceSpecifications>,
+    site_queries: (
+        Query<&BuildingPart>,
+        Query<(&Position, Option<&Building>, Option<&UnderConstruction>)>,
+    ),
+    free_queries: (
+        Query<(), With<Occupied>>,
+        Query<(), With<BlockedForBuilding>>,
+    ),
+    neighbor_queries: NeighborQueries,
+    mut car_query: Query<&mut Car>,
+    mut depot_query: Query<&mut Depot>,
+    mut transactions: EventWriter<AccountTransaction>,
+    mut demolish_events: EventWriter<DemolishEvent>,
+) {
+    for event in history_events.iter() {
+        let (command, undo) = match event {
+            HistoryEvent::Undo => (history.undo(), true),
+            HistoryEvent::Redo => (history.redo(), false),
+        };
+
+        match command {
+            Some(EditCommand::Placed(placed)) if undo => {
+                remove_buildings(
+                    &placed,
+                    &map_query,
+                    &site_queries,
+                    &mut transactions,
+                    &mut demolish_events,
+                );
+            }
+            Some(EditCommand::Placed(placed)) => {
+                // placed again as fresh sites at the end of the queue
+                let placed = placed
+                    .into_iter()
+                    .map(|mut item| {
+                        if let Some(construction) = &mut item.under_construction {
+                            construction.order = 0;
+                        }
+                        item
+                    })
+                    .collect::<Vec<_>>();
+
+                for item in placed.iter() {
+                    restore_building(
+                        item,
+                        &mut commands,
+                        &mut map_query,
+                        &buildings,
+                        &resources,
+                        &free_queries,
+                        &neighbor_queries,
+                    );
+                }
+            }
+            Some(EditCommand::Bulldozed(removed)) if undo => {
+                for (item, refund) in removed.iter() {
+                    let restored = restore_building(
+                        item,
+                        &mut commands,
+                        &mut map_query,
+                        &buildings,
+                        &resources,
+                        &free_queries,
+                        &neighbor_queries,
+                    );
+
+                    // the refund is paid back when the building returns
+                    if restored && *refund > 0 {
+                        transactions.send(AccountTransaction {
+                            amount: -refund,
+                            category: TransactionCategory::Construction,
+                            source: None,
+                        });
+                    }
+                }
+            }
+            Some(EditCommand::Bulldozed(removed)) => {
+                for (item, refund) in removed.iter() {
+                    if find_building(item, &map_query, &site_queries).is_none() {
+                        continue;
+                    }
+
+                    if *refund > 0 {
+                        transactions.send(AccountTransaction {
+                            amount: *refund,
+                            category: TransactionCategory::Construction,
+                            source: None,
+                        });
+                    }
+
+                    demolish_events.send(DemolishEvent {
+                        position: item.position,
+                    });
+                }
+            }
+            Some(EditCommand::CarProgram { car, before, after }) => {
+                if let Ok(mut car) = car_query.get_mut(car) {
+                    car.controller = if undo { before } else { after };
+                }
+            }
+            Some(EditCommand::DepotEdit {
+                depot,
+                before,
+                after,
+            }) => {
+                if let Ok(mut depot) = depot_query.get_mut(depot) {
+                    *depot = if undo { before } else { after };
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+// the entity still standing where a recorded building was placed
+fn find_building(
+    item: &PlacedBuilding,
+    map_query: &MapQuery,
+    site_queries: &(
+        Query<&BuildingPart>,
+        Query<(&Position, Option<&Building>, Option<&UnderConstruction>)>,
+    ),
+) -> Option<Entity> {
+    let (part_query, site_query) = site_queries;
+
+    let entity = map_query
+        .get_tile_entity(item.position, MAP_ID, BUILDING_LAYER_ID)
+        .ok()?;
+    let entity = part_query
+        .get(entity)
+        .map(|part| part.main)
+        .unwrap_or(entity);
+
+    let (position, building, _) = site_query.get(entity).ok()?;
+    if position.position != item.position {
+        return None;
+    }
+
+    match (&item.building, building) {
+        (BuildingEntity::Building(recorded), Some(building)) if recorded.id == building.id => {
+            Some(entity)
+        }
+        (BuildingEntity::Building(_), _) | (_, Some(_)) => None,
+        _ => Some(entity),
+    }
+}
+
+// undoing a placement gives back everything spent on sites still under construction
+fn remove_buildings(
+    placed: &[PlacedBuilding],
+    map_query: &MapQuery,
+    site_queries: &(
+        Query<&BuildingPart>,
+        Query<(&Position, Option<&Building>, Option<&UnderConstruction>)>,
+    ),
+    transactions: &mut EventWriter<AccountTransaction>,
+    demolish_events: &mut EventWriter<DemolishEvent>,
+) {
+    let (_, site_query) = site_queries;
+
+    for item in placed.iter() {
+        let entity = match find_building(item, map_query, site_queries) {
+            Some(entity) => entity,
+            None => continue,
+        };
+
+        if let Ok((_, _, Some(construction))) = site_query.get(entity) {
+            let refund = construction.spent as i64;
+            if refund > 0 {
+                transactions.send(AccountTransaction {
+                    amount: refund,
+                    category: TransactionCategory::Construction,
+                    source: None,
+                });
+            }
+        }
+
+        demolish_events.send(DemolishEvent {
+            position: item.position,
+        });
+    }
+}
+
+// false if something else was built on the site in the meantime
+fn restore_building(
+    item: &PlacedBuilding,
+    commands: &mut Commands,
+    map_query: &mut MapQuery,
+    buildings: &BuildingSpecifications,
+    resources: &ResourceSpecifications,
+    free_queries: &(
+        Query<(), With<Occupied>>,
+        Query<(), With<BlockedForBuilding>>,
+    ),
+    neighbor_queries: &NeighborQueries,
+) -> bool {
+    let (occupied_query, blocked_query) = free_queries;
+
+    let footprint = Footprint {
+        size: entity_size(&item.building, buildings),
+    };
+    if !footprint_free(
+        map_query,
+        occupied_query,
+        blocked_query,
+        item.position,
+        &footprint,
+    ) {
+        return false;
+    }
+
+    let entity = spawn_building(
+        commands,
+        map_query,
+        item.position,
+        &item.building,
+        item.under_construction.clone(),
+        buildings,
+        resources,
+    );
+    let entity = match entity {
+        Some(entity) => entity,
+        None => return false,
+    };
+    commands.entity(entity).insert(Unrecorded);
+
+    if let Some(spent) = item.spent {
+        commands.entity(entity).insert(ConstructionSpent(spent));
+    }
+
+    update_neighbors(
+        commands,
+        map_query,
+        item.position,
+        &footprint,
+        neighbor_queries,
+    );
+
+    true
+}
+
+pub fn reset_history(mut history: ResMut<EditHistory>) {
+    *history = EditHistory::default();
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use super::*;
+use crate::game::storage::Storage;
+
+fn depot_edit(depot: Entity, pickups: usize) -> EditCommand {
+    let mut after = Depot::default();
+    for x in 0..pickups {
+        after.pickups.insert(UVec2::new(x as u32, 0));
+    }
+
+    EditCommand::DepotEdit {
+        depot,
+        before: Depot::default(),
+        after,
+    }
+}
+
+fn pickups(command: Option<EditCommand>) -> usize {
+    match command {
+        Some(EditCommand::DepotEdit { after, .. }) => after.pickups.len(),
+        _ => panic!("expected a depot edit"),
+    }
+}
+
+#[test]
+fn undo_and_redo() {
+    let depot = Entity::new(1);
+    let mut history = EditHistory::default();
+    assert!(!history.can_undo());
+
+    history.push(depot_edit(depot, 1));
+    history.push(depot_edit(depot, 2));
+
+    assert_eq!(pickups(history.undo()), 2);
+    assert!(history.can_redo());
+    assert_eq!(pickups(history.undo()), 1);
+    assert!(history.undo().is_none());
+
+    assert_eq!(pickups(history.redo()), 1);
+    assert_eq!(pickups(history.redo()), 2);
+    assert!(history.redo().is_none());
+}
+
+#[test]
+fn new_edits_clear_redo() {
+    let depot = Entity::new(1);
+    let mut history = EditHistory::default();
+
+    history.push(depot_edit(depot, 1));
+    history.undo();
+    history.push(depot_edit(depot, 2));
+
+    assert!(!history.can_redo());
+    assert_eq!(pickups(history.undo()), 2);
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn limits_history_length() {
+    let depot = Entity::new(1);
+    let mut history = EditHistory::default();
+
+    for count in 0..HISTORY_LENGTH + 5 {
+        history.push(depot_edit(depot, count));
+    }
+
+    let mut undone = HashSet::new();
+    while let Some(command) = history.undo() {
+        undone.insert(pickups(Some(command)));
+    }
+
+    assert_eq!(undone.len(), HISTORY_LENGTH);
+    assert!(!undone.contains(&4));
+    assert!(undone.contains(&5));
+}
+
+#[test]
+fn records_placements() {
+    let mut world = World::default();
+    world.insert_resource(EditHistory::default());
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(record_placements.system());
+
+    world
+        .spawn()
+        .insert(Position {
+            position: UVec2::new(3, 4),
+        })
+        .insert(Storage::default())
+        .insert(UnderConstruction::from_fixed_cost(100))
+        .insert(Occupied);
+    let loaded = world
+        .spawn()
+        .insert(Position {
+            position: UVec2::new(5, 4),
+        })
+        .insert(Storage::default())
+        .insert(Occupied)
+        .insert(Unrecorded)
+        .id();
+
+    stage.run(&mut world);
+
+    assert!(world.get::<Unrecorded>(loaded).is_none());
+
+    let mut history = world.get_resource_mut::<EditHistory>().unwrap();
+    match history.undo() {
+        Some(EditCommand::Placed(placed)) => {
+            assert_eq!(placed.len(), 1);
+            assert_eq!(placed[0].position, UVec2::new(3, 4));
+            assert!(matches!(placed[0].building, BuildingEntity::Storage(_)));
+            assert!(placed[0].under_construction.is_some());
+        }
+        _ => panic!("expected a placement"),
+    }
+    assert!(!history.can_undo());
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::game::assets::resource_specifications::ResourceSpecifications;
+
+// traded amount at which export prices are halved and import prices doubled
+const SATURATION: f64 = 500.0;
+const MIN_EXPORT_FACTOR: f64 = 0.25;
+// share of the traded volume remembered after each tick
+const RECOVERY: f64 = 0.98;
+pub const IMPORT_SURCHARGE: f64 = 1.2;
+const EVENT_CHANCE: f64 = 0.005;
+const EVENT_TICKS: u32 = 240;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MarketResource {
+    pub exported: f64,
+    pub imported: f64,
+    // price change caused by a market event
+    pub modifier: f64,
+    pub event_ticks: u32,
+}
+
+impl Default for MarketResource {
+    fn default() -> Self {
+        Self {
+            exported: 0.0,
+            imported: 0.0,
+            modifier: 1.0,
+            event_ticks: 0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Market {
+    pub resources: HashMap<String, MarketResource>,
+}
+
+impl Market {
+    fn get(&self, resource: &str) -> MarketResource {
+        self.resources.get(resource).cloned().unwrap_or_default()
+    }
+
+    pub fn export_price(&self, resource: &str, resources: &ResourceSpecifications) -> f64 {
+        let cost = resources.get(resource).map(|r| r.cost).unwrap_or(0.0);
+        let market = self.get(resource);
+
+        let factor = (1.0 - market.exported / (2.0 * SATURATION)).max(MIN_EXPORT_FACTOR);
+
+        cost * factor * market.modifier
+    }
+
+    pub fn import_price(&self, resource: &str, resources: &ResourceSpecifications) -> f64 {
+        let cost = resources.get(resource).map(|r| r.cost).unwrap_or(0.0);
+        let market = self.get(resource);
+
+        let factor = 1.0 + market.imported / SATURATION;
+
+        cost * IMPORT_SURCHARGE * factor * market.modifier
+    }
+
+    pub fn track_export(&mut self, resource: &str, amount: f64) {
+        self.resources
+            .entry(resource.to_owned())
+            .or_default()
+            .exported += amount;
+    }
+
+    pub fn track_import(&mut self, resource: &str, amount: f64) {
+        self.resources
+            .entry(resource.to_owned())
+            .or_default()
+            .imported += amount;
+    }
+
+    pub fn recover(&mut self) {
+        for market in self.resources.values_mut() {
+            market.exported *= RECOVERY;
+            market.imported *= RECOVERY;
+
+            if market.event_ticks > 0 {
+                market.event_ticks -= 1;
+
+                if market.event_ticks == 0 {
+                    market.modifier = 1.0;
+                }
+            }
+        }
+    }
+
+    pub fn start_event(&mut self, resource: &str, modifier: f64) {
+        let market = self.resources.entry(resource.to_owned()).or_default();
+        market.modifier = modifier;
+        market.event_ticks = EVENT_TICKS;
+    }
+}
+
+pub fn reset_market(mut market: ResMut<Market>) {
+    *market = Market::default();
+}
+
+pub fn market(mut market: ResMut<Market>, resources: Res<ResourceSpecifications>) {
+    market.recover();
+
+    let mut random = thread_rng();
+    if !random.gen_bool(EVENT_CHANCE) {
+        return;
+    }
+
+    let traded: Vec<&String> = resources
+        .iter()
+        .filter(|(_id, resource)| resource.cost > f64::EPSILON && !resource.virtual_resource)
+        .map(|(id, _resource)| id)
+        .collect();
+
+    if let Some(resource) = traded.choose(&mut random) {
+        let modifier = random.gen_range(0.5..1.8);
+        market.start_event(resource, modifier);
+
+        log::info!(
+            "Market event: {} prices at {:.0}%",
+            resource,
+            modifier * 100.0
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::game::assets::resource_specifications::ResourceSpecification;
+
+const COKE: &str = "coke";
+
+fn resources() -> ResourceSpecifications {
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(
+        COKE.to_owned(),
+        ResourceSpecification {
+            cost: 10.0,
+            ..Default::default()
+        },
+    );
+    resources
+}
+
+#[test]
+fn base_prices() {
+    let market = Market::default();
+    let resources = resources();
+
+    assert!((market.export_price(COKE, &resources) - 10.0).abs() < f64::EPSILON);
+    assert!((market.import_price(COKE, &resources) - 12.0).abs() < f64::EPSILON);
+    assert!(market.export_price("unknown", &resources).abs() < f64::EPSILON);
+}
+
+#[test]
+fn trading_moves_prices() {
+    let mut market = Market::default();
+    let resources = resources();
+
+    market.track_export(COKE, SATURATION);
+    market.track_import(COKE, SATURATION);
+
+    assert!((market.export_price(COKE, &resources) - 5.0).abs() < f64::EPSILON);
+    assert!((market.import_price(COKE, &resources) - 24.0).abs() < f64::EPSILON);
+
+    // never given away for nothing
+    market.track_export(COKE, 10.0 * SATURATION);
+    assert!(
+        (market.export_price(COKE, &resources) - 10.0 * MIN_EXPORT_FACTOR).abs() < f64::EPSILON
+    );
+}
+
+#[test]
+fn prices_recover() {
+    let mut market = Market::default();
+    let resources = resources();
+
+    market.track_export(COKE, SATURATION);
+    let before = market.export_price(COKE, &resources);
+
+    market.recover();
+
+    let after = market.export_price(COKE, &resources);
+    assert!(after > before);
+    assert!(after < 10.0);
+}
+
+#[test]
+fn events_end() {
+    let mut market = Market::default();
+    let resources = resources();
+
+    market.start_event(COKE, 1.5);
+    assert!((market.export_price(COKE, &resources) - 15.0).abs() < f64::EPSILON);
+
+    for _ in 0..EVENT_TICKS {
+        market.recover();
+    }
+
+    assert!((market.export_price(COKE, &resources) - 10.0).abs() < f64::EPSILON);
+}
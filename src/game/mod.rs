@@ -1,24 +1,36 @@
 mod account;
 mod asset_tiles;
 mod assets;
+mod bank;
+mod blueprint;
 mod camera;
 mod car;
 mod constants;
 mod construction;
+mod contracts;
+mod conveyor;
 mod current_selection;
 mod current_tool;
 mod goals;
 mod helper;
 mod highlight_tiles;
+mod history;
+mod market;
+mod outcome;
 mod pathfinder;
+mod pipeline;
 mod production;
+mod rail;
 mod remove_update;
+mod scenario;
 mod setup;
 mod state_manager;
 mod statistics;
 mod storage;
 mod street;
 mod texture;
+mod time;
+mod train;
 mod ui;
 
 use bevy::{
@@ -29,8 +41,10 @@ use bevy_ecs_tilemap::prelude::*;
 use bevy_egui::EguiPlugin;
 
 use self::{
-    account::{Account, AccountTransaction},
+    account::{Account, AccountTransaction, Ledger},
     assets::{ClickedTile, Forest, MapSettings, RemovedBuildingEvent, StateName, Water},
+    bank::Bank,
+    blueprint::BlueprintManager,
     car::instructions::{
         CarGoToInstructionEvent, CarLoadInstructionEvent, CarUnloadInstructionEvent,
     },
@@ -38,15 +52,31 @@ use self::{
         CAR_DRIVE_TICK_SPEED, CAR_INSTRUCTION_TICK_SPEED, GOAL_UPDATE_TICK_SPEED,
         PRODUCTION_TICK_SPEED,
     },
+    construction::ConstructionSettings,
+    contracts::ContractManager,
     current_selection::CurrentlySelected,
-    current_tool::SelectedTool,
+    current_tool::{
+        bulldoze::{BulldozePreview, DemolishEvent},
+        SelectedTool,
+    },
     goals::GoalManager,
     highlight_tiles::{HighlightTiles, HighlightTilesUpdateEvent},
+    history::{EditHistory, HistoryEvent},
+    market::Market,
+    outcome::{GameOutcome, GameSummary},
     pathfinder::Pathfinding,
+    production::{Town, Workforce},
+    scenario::ScenarioManager,
     state_manager::{LoadGameEvent, NewGameEvent, SaveGameEvent},
     statistics::StatisticTracker,
     street::Street,
-    ui::state::{ConfirmDialogState, MainMenuState, SaveGameList},
+    time::GameTime,
+    ui::{
+        bank::BankWindow,
+        construction_queue::ConstructionQueueWindow,
+        finance::FinanceWindow,
+        state::{ConfirmDialogState, MainMenuState, SaveGameList},
+    },
 };
 
 #[derive(Default, Debug)]
@@ -79,6 +109,16 @@ pub enum IdleLabel {
     SpawnIdle,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemLabel)]
+pub enum HousingLabel {
+    Housing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemLabel)]
+pub enum ScenarioLabel {
+    Defaults,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, SystemLabel)]
 pub enum UILabel {
     InfoUI,
@@ -95,6 +135,8 @@ pub enum AppState {
     MainMenu,
     InGame,
     Paused,
+    Victory,
+    GameOver,
 }
 
 fn and_is_in_game(In(input): In<ShouldRun>, state: Res<State<AppState>>) -> ShouldRun {
@@ -118,15 +160,32 @@ impl Game {
             .init_resource::<MapSettings>()
             .init_resource::<GoalManager>()
             .init_resource::<Account>()
+            .init_resource::<Ledger>()
             .init_resource::<StateName>()
             .init_resource::<StatisticTracker>()
+            .init_resource::<Workforce>()
+            .init_resource::<Town>()
+            .init_resource::<Market>()
+            .init_resource::<Bank>()
+            .init_resource::<BankWindow>()
+            .init_resource::<FinanceWindow>()
+            .init_resource::<ConstructionQueueWindow>()
+            .init_resource::<GameTime>()
+            .init_resource::<ContractManager>()
+            .init_resource::<ConstructionSettings>()
+            .init_resource::<ScenarioManager>()
+            .init_resource::<GameOutcome>()
+            .init_resource::<GameSummary>()
             .init_resource::<ConfirmDialogState>()
             .init_resource::<SaveGameList>()
             .init_resource::<HighlightTiles>()
+            .init_resource::<EditHistory>()
+            .init_resource::<BulldozePreview>()
             .init_resource::<Pathfinding>()
             .init_resource::<Option<NewGameSetup>>()
             .insert_resource(assets::building_specifications::load_specifications())
             .insert_resource(assets::resource_specifications::load_specifications())
+            .insert_resource(BlueprintManager::new())
             .insert_resource(WindowDescriptor {
                 title: "Oligarchy".to_owned(),
                 ..Default::default()
@@ -141,6 +200,8 @@ impl Game {
             .add_event::<LoadGameEvent>()
             .add_event::<SaveGameEvent>()
             .add_event::<RemovedBuildingEvent>()
+            .add_event::<DemolishEvent>()
+            .add_event::<HistoryEvent>()
             .add_event::<AccountTransaction>()
             .add_event::<CarLoadInstructionEvent>()
             .add_event::<CarUnloadInstructionEvent>()
@@ -177,6 +238,11 @@ impl Game {
                     .with_system(ui::state::new_game_menu.system())
                     .label(Label::NewGameMenu),
             )
+            .add_system_set(
+                SystemSet::on_update(MainMenuState::Campaign)
+                    .with_system(ui::state::campaign_menu.system())
+                    .label(Label::NewGameMenu),
+            )
             .add_system_set(
                 SystemSet::on_update(MainMenuState::Load)
                     .with_system(ui::state::load_save_game_menu.system())
@@ -193,8 +259,35 @@ impl Game {
             .add_system_set(
                 SystemSet::on_enter(AppState::InGame)
                     .with_system(setup::game::setup.system())
-                    .with_system(account::reset_account.system())
-                    .with_system(goals::generate_goals.system()),
+                    .with_system(
+                        account::reset_account
+                            .system()
+                            .label(ScenarioLabel::Defaults),
+                    )
+                    .with_system(market::reset_market.system())
+                    .with_system(bank::reset_bank.system())
+                    .with_system(construction::reset_construction_settings.system())
+                    .with_system(history::reset_history.system())
+                    .with_system(account::reset_ledger.system())
+                    .with_system(time::reset_time.system())
+                    .with_system(contracts::reset_contracts.system())
+                    .with_system(outcome::reset_outcome.system())
+                    .with_system(production::reset_town.system())
+                    .with_system(
+                        production::reset_workforce
+                            .system()
+                            .label(ScenarioLabel::Defaults),
+                    )
+                    .with_system(
+                        goals::generate_goals
+                            .system()
+                            .label(ScenarioLabel::Defaults),
+                    )
+                    .with_system(
+                        scenario::start_scenario
+                            .system()
+                            .after(ScenarioLabel::Defaults),
+                    ),
             )
             .add_system_set(
                 SystemSet::new()
@@ -202,7 +295,11 @@ impl Game {
                         FixedTimestep::step(GOAL_UPDATE_TICK_SPEED as f64)
                             .chain(and_is_in_game.system()),
                     )
-                    .with_system(goals::update_goals.system()),
+                    .with_system(goals::update_goals.system())
+                    .with_system(contracts::update_contracts.system())
+                    .with_system(contracts::offer_contracts.system())
+                    .with_system(scenario::update_scenario.system())
+                    .with_system(outcome::check_outcome.system()),
             )
             .add_system_set(
                 SystemSet::on_exit(AppState::InGame).with_system(setup::game::teardown.system()),
@@ -278,12 +375,31 @@ impl Game {
                     .before(UILabel::UIEnd)
                     .with_system(ui::pause::pause_menu.system()),
             )
+            .add_system_set(
+                SystemSet::on_update(AppState::Victory)
+                    .before(UILabel::UIEnd)
+                    .with_system(ui::outcome::victory_ui.system()),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::GameOver)
+                    .before(UILabel::UIEnd)
+                    .with_system(ui::outcome::game_over_ui.system()),
+            )
             .add_system_set(
                 SystemSet::on_update(AppState::InGame)
                     .before(UILabel::UIEnd)
                     .with_system(ui::pause::pause_menu.system())
                     .with_system(ui::info::info_ui.system().label(UILabel::InfoUI))
                     .with_system(ui::goals::goals_ui.system().after(UILabel::InfoUI))
+                    .with_system(ui::contracts::contracts_ui.system().after(UILabel::InfoUI))
+                    .with_system(ui::bank::bank_ui.system().after(UILabel::InfoUI))
+                    .with_system(ui::finance::finance_ui.system().after(UILabel::InfoUI))
+                    .with_system(
+                        ui::construction_queue::construction_queue_ui
+                            .system()
+                            .after(UILabel::InfoUI),
+                    )
+                    .with_system(ui::scenario::scenario_ui.system().after(UILabel::InfoUI))
                     .with_system(
                         ui::import_export_station::edit_ui
                             .system()
@@ -311,7 +427,22 @@ impl Game {
                             .after(UILabel::InfoUI)
                             .before(Label::HighlightTiles),
                     )
+                    .with_system(ui::conveyor::edit_ui.system().after(UILabel::InfoUI))
+                    .with_system(ui::storage::edit_ui.system().after(UILabel::InfoUI))
+                    .with_system(
+                        ui::storage_management::edit_ui
+                            .system()
+                            .after(UILabel::InfoUI)
+                            .before(Label::HighlightTiles),
+                    )
+                    .with_system(
+                        ui::train_orders::orders_ui
+                            .system()
+                            .after(UILabel::InfoUI)
+                            .before(Label::HighlightTiles),
+                    )
                     .with_system(ui::construction::construction_ui.system())
+                    .with_system(ui::blueprint::blueprint_ui.system())
                     .with_system(ui::name::name_ui.system()),
             )
             .add_system_set(
@@ -324,6 +455,7 @@ impl Game {
                 SystemSet::on_update(AppState::InGame)
                     .after(Label::CurrentSelection)
                     .before(Label::Update)
+                    .with_system(current_tool::rotate_tool.system())
                     .with_system(current_tool::street::street_placement.system())
                     .with_system(current_tool::street::path_placement.system())
                     .with_system(current_tool::depot::depot_placement.system())
@@ -338,9 +470,22 @@ impl Game {
                     .with_system(
                         current_tool::delivery_station::delivery_station_placement.system(),
                     )
+                    .with_system(current_tool::town_shop::town_shop_placement.system())
                     .with_system(current_tool::car::car_placement.system())
+                    .with_system(current_tool::rail::rail_placement.system())
+                    .with_system(current_tool::train_station::train_station_placement.system())
+                    .with_system(current_tool::train::train_placement.system())
+                    .with_system(current_tool::conveyor::conveyor_placement.system())
+                    .with_system(current_tool::pipeline::pipeline_placement.system())
                     .with_system(current_tool::building::building_placement.system())
-                    .with_system(current_tool::bulldoze::bulldoze.system()),
+                    .with_system(current_tool::blueprint::blueprint_selection.system())
+                    .with_system(current_tool::blueprint::blueprint_placement.system())
+                    .with_system(current_tool::bulldoze::bulldoze.system())
+                    .with_system(current_tool::bulldoze::demolish.system())
+                    .with_system(history::history_shortcuts.system())
+                    .with_system(history::apply_history.system())
+                    .with_system(history::record_placements.system())
+                    .with_system(construction::enqueue_construction.system()),
             )
             .add_system_set(
                 SystemSet::on_update(AppState::InGame)
@@ -356,6 +501,11 @@ impl Game {
                             .system()
                             .after(Label::Pathfinding)
                             .before(Label::UpdateEnd),
+                    )
+                    .with_system(
+                        train::calculate_destination
+                            .system()
+                            .before(Label::UpdateEnd),
                     ),
             )
             .add_system_set(
@@ -367,10 +517,22 @@ impl Game {
                     )
                     .with_system(production::import_export_station::import_export_station.system())
                     .with_system(production::storage_management::storage_management.system())
+                    .with_system(production::conveyor::conveyor.system())
+                    .with_system(production::pipeline::pipeline.system())
+                    .with_system(production::spoilage::spoilage.system())
+                    .with_system(production::town_shop::town_shop.system())
+                    .with_system(market::market.system())
+                    .with_system(time::advance_time.system())
+                    .with_system(
+                        production::housing::housing
+                            .system()
+                            .label(HousingLabel::Housing),
+                    )
                     .with_system(
                         production::production_building::production_building
                             .system()
-                            .after(IdleLabel::SpawnIdle),
+                            .after(IdleLabel::SpawnIdle)
+                            .after(HousingLabel::Housing),
                     )
                     .with_system(
                         production::idle::spawn_idle
@@ -378,6 +540,7 @@ impl Game {
                             .label(IdleLabel::SpawnIdle),
                     )
                     .with_system(account::maintenance_cost.system())
+                    .with_system(bank::loan_interest.system())
                     .with_system(construction::construction.system()),
             )
             .add_system_set(
@@ -387,7 +550,8 @@ impl Game {
                         FixedTimestep::step(CAR_DRIVE_TICK_SPEED as f64)
                             .chain(and_is_in_game.system()),
                     )
-                    .with_system(car::drive_to_destination::drive_to_destination.system()),
+                    .with_system(car::drive_to_destination::drive_to_destination.system())
+                    .with_system(train::drive_to_destination::drive_to_destination.system()),
             )
             .add_system_set(
                 SystemSet::new()
@@ -415,13 +579,16 @@ impl Game {
                         car::instructions::goto
                             .system()
                             .after(CarLabel::Instruction),
-                    ),
+                    )
+                    .with_system(train::orders::train_orders.system()),
             )
             .add_system_set(
                 SystemSet::on_update(AppState::InGame)
                     .before(Label::UpdateEnd)
                     .with_system(storage::update_consolidators.system())
-                    .with_system(car::update_car.system()),
+                    .with_system(storage::apply_storage_tiers.system())
+                    .with_system(car::update_car.system())
+                    .with_system(train::update_train.system()),
             )
             .add_system_set(
                 SystemSet::on_update(AppState::InGame)
@@ -433,18 +600,27 @@ impl Game {
                     .with_system(asset_tiles::storage_update.system())
                     .with_system(asset_tiles::import_export_station_update.system())
                     .with_system(asset_tiles::delivery_station_update.system())
+                    .with_system(asset_tiles::town_shop_update.system())
                     .with_system(asset_tiles::storage_management_update.system())
+                    .with_system(asset_tiles::train_station_update.system())
+                    .with_system(asset_tiles::building_part_update.system())
                     .with_system(asset_tiles::ground_update.system())
                     .with_system(street::update_streets.system())
+                    .with_system(rail::update_rails.system())
+                    .with_system(conveyor::update_conveyors.system())
+                    .with_system(conveyor::update_conveyor_lines.system())
+                    .with_system(pipeline::update_pipelines.system())
                     .with_system(helper::neighbor_structure::update_tile::<Water>.system())
                     .with_system(helper::neighbor_structure::update_tile::<Forest>.system())
                     .with_system(helper::neighbor_structure::update_tile::<Street>.system())
-                    .with_system(car::spawn_car.system()),
+                    .with_system(car::spawn_car.system())
+                    .with_system(train::spawn_train.system()),
             )
             .add_system_set(
                 SystemSet::on_update(AppState::InGame)
                     .with_system(remove_update::remove_update.system())
                     .with_system(account::account_transactions.system())
+                    .with_system(account::record_transactions.system())
                     .label(Label::UpdateEnd),
             )
             .run();
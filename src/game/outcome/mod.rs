@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    account::Account,
+    assets::Building,
+    bank::Bank,
+    construction::UnderConstruction,
+    goals::GoalManager,
+    scenario::{ScenarioManager, ScenarioState},
+    statistics::{StatisticTracker, Statistics},
+    time::GameTime,
+    AppState,
+};
+
+// days the credit line may stay exceeded before the game is lost
+pub const BANKRUPTCY_DAYS: f64 = 7.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Victory,
+    Bankruptcy,
+    ScenarioFailed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GameOutcome {
+    // day the balance went below the credit line
+    pub bankrupt_since: Option<f64>,
+    // victory was already celebrated, keep playing
+    pub won: bool,
+}
+
+impl GameOutcome {
+    pub fn days_until_bankruptcy(&self, day: f64) -> Option<f64> {
+        self.bankrupt_since
+            .map(|since| (since + BANKRUPTCY_DAYS - day).max(0.0))
+    }
+
+    // available is the balance including the credit line
+    pub fn evaluate(
+        &mut self,
+        available: i64,
+        day: f64,
+        goals_reached: bool,
+        scenario: Option<ScenarioState>,
+    ) -> Option<Outcome> {
+        if available < 0 {
+            self.bankrupt_since.get_or_insert(day);
+        } else {
+            self.bankrupt_since = None;
+        }
+
+        if let Some(since) = self.bankrupt_since {
+            if day - since >= BANKRUPTCY_DAYS {
+                return Some(Outcome::Bankruptcy);
+            }
+        }
+
+        let won = match scenario {
+            Some(ScenarioState::Lost) => return Some(Outcome::ScenarioFailed),
+            Some(state) => state == ScenarioState::Won,
+            None => goals_reached,
+        };
+
+        if won && !self.won {
+            self.won = true;
+            return Some(Outcome::Victory);
+        }
+
+        None
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct GameSummary {
+    pub outcome: Option<Outcome>,
+    pub days: f64,
+    pub balance: i64,
+    pub exported: f64,
+    pub produced: f64,
+    pub buildings: usize,
+}
+
+pub fn check_outcome(
+    query: Query<&Statistics>,
+    building_query: Query<(), (With<Building>, Without<UnderConstruction>)>,
+    deleted_export_statistics: Res<StatisticTracker>,
+    account: Res<Account>,
+    bank: Res<Bank>,
+    time: Res<GameTime>,
+    goals: Res<GoalManager>,
+    scenarios: Res<ScenarioManager>,
+    mut outcome: ResMut<GameOutcome>,
+    mut summary: ResMut<GameSummary>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    let day = time.day();
+    let scenario = scenarios.active.as_ref().map(|active| active.state);
+
+    let available = bank.available(account.value);
+    let result = match outcome.evaluate(available, day, goals.goals.is_empty(), scenario) {
+        Some(result) => result,
+        None => return,
+    };
+
+    let total = |tracker: &StatisticTracker| tracker.get_all().values().sum::<f64>();
+
+    *summary = GameSummary {
+        outcome: Some(result),
+        days: day,
+        balance: account.value,
+        exported: query
+            .iter()
+            .fold(total(&deleted_export_statistics), |acc, statistics| {
+                acc + total(&statistics.export)
+            }),
+        produced: query
+            .iter()
+            .map(|statistics| total(&statistics.production))
+            .sum(),
+        buildings: building_query.iter().count(),
+    };
+
+    let _ = app_state.push(match result {
+        Outcome::Victory => AppState::Victory,
+        _ => AppState::GameOver,
+    });
+}
+
+pub fn reset_outcome(mut outcome: ResMut<GameOutcome>, mut summary: ResMut<GameSummary>) {
+    *outcome = GameOutcome::default();
+    *summary = GameSummary::default();
+}
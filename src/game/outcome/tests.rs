@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn bankruptcy_after_warning_period() {
+    let mut outcome = GameOutcome::default();
+
+    assert_eq!(outcome.evaluate(-100, 1.0, false, None), None);
+    assert_eq!(
+        outcome.days_until_bankruptcy(3.0),
+        Some(BANKRUPTCY_DAYS - 2.0)
+    );
+
+    assert_eq!(
+        outcome.evaluate(-100, 1.0 + BANKRUPTCY_DAYS, false, None),
+        Some(Outcome::Bankruptcy)
+    );
+}
+
+#[test]
+fn positive_balance_ends_warning() {
+    let mut outcome = GameOutcome::default();
+
+    assert_eq!(outcome.evaluate(-100, 1.0, false, None), None);
+    assert_eq!(outcome.evaluate(100, 2.0, false, None), None);
+    assert_eq!(outcome.days_until_bankruptcy(2.0), None);
+
+    assert_eq!(
+        outcome.evaluate(-100, 1.0 + BANKRUPTCY_DAYS, false, None),
+        None
+    );
+}
+
+#[test]
+fn victory_is_reported_once() {
+    let mut outcome = GameOutcome::default();
+
+    assert_eq!(
+        outcome.evaluate(100, 1.0, true, None),
+        Some(Outcome::Victory)
+    );
+    assert_eq!(outcome.evaluate(100, 2.0, true, None), None);
+}
+
+#[test]
+fn scenarios_decide_the_outcome() {
+    let mut outcome = GameOutcome::default();
+
+    assert_eq!(
+        outcome.evaluate(100, 1.0, true, Some(ScenarioState::Running)),
+        None
+    );
+    assert_eq!(
+        outcome.evaluate(100, 1.0, false, Some(ScenarioState::Won)),
+        Some(Outcome::Victory)
+    );
+    assert_eq!(
+        outcome.evaluate(100, 1.0, false, Some(ScenarioState::Lost)),
+        Some(Outcome::ScenarioFailed)
+    );
+}
+
+#[test]
+fn credit_line_delays_bankruptcy() {
+    let mut outcome = GameOutcome::default();
+    let bank = Bank::default();
+
+    assert_eq!(
+        outcome.evaluate(bank.available(-100), 1.0, false, None),
+        None
+    );
+    assert_eq!(outcome.days_until_bankruptcy(1.0), None);
+
+    let exceeded = bank.available(-bank.credit_limit - 100);
+    assert_eq!(outcome.evaluate(exceeded, 2.0, false, None), None);
+    assert_eq!(
+        outcome.evaluate(exceeded, 2.0 + BANKRUPTCY_DAYS, false, None),
+        Some(Outcome::Bankruptcy)
+    );
+}
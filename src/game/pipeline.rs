@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    account::PurchaseCost,
+    assets::{resource_specifications::ResourceSpecifications, Position, RequiresUpdate},
+    constants::MapTile,
+    construction::UnderConstruction,
+    helper::neighbor_structure::NeighborStructure,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    storage::Storage,
+};
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Pipeline;
+
+impl PurchaseCost for Pipeline {
+    fn price(&self, _resources: &ResourceSpecifications) -> i64 {
+        120
+    }
+}
+
+pub fn update_pipelines(
+    mut tile_query: Query<
+        (&mut Tile, &Position),
+        (
+            With<Pipeline>,
+            With<RequiresUpdate>,
+            Without<UnderConstruction>,
+        ),
+    >,
+    pipeline_query: Query<(), With<Pipeline>>,
+    storage_query: Query<&Storage>,
+    map_query: MapQuery,
+) {
+    for (mut tile, position) in tile_query.iter_mut() {
+        let neighbors = map_query.get_tile_neighbors(position.position, MAP_ID, BUILDING_LAYER_ID);
+        let connects = |neighbor: &(IVec2, Option<Entity>)| match neighbor.1 {
+            Some(entity) => {
+                pipeline_query.get(entity).is_ok()
+                    || match storage_query.get(entity) {
+                        Ok(storage) => storage.is_tank(),
+                        Err(_) => false,
+                    }
+            }
+            None => false,
+        };
+
+        // N, S, W, E, NW, NE, SW, SE.
+        let ns = NeighborStructure {
+            north: connects(&neighbors[0]),
+            south: connects(&neighbors[1]),
+            west: connects(&neighbors[2]),
+            east: connects(&neighbors[3]),
+        };
+
+        let ns_index: u16 = ns.into();
+        tile.texture_index = ns_index + MapTile::PipelineTilesOffset as u16;
+        tile.visible = true;
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    assets::{resource_specifications::ResourceSpecifications, BuildingPart},
+    construction::UnderConstruction,
+    conveyor::{Conveyor, ConveyorLine},
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    storage::Storage,
+};
+
+pub const CONVEYOR_RATE: f64 = 5.0;
+
+pub fn transfer_amount(
+    source: &Storage,
+    target: &Storage,
+    resource: &str,
+    resources: &ResourceSpecifications,
+) -> f64 {
+    source
+        .amount_of(resource)
+        .min(target.space_for(resource, resources))
+        .min(CONVEYOR_RATE)
+        .max(0.0)
+}
+
+pub fn conveyor(
+    conveyor_query: Query<(&Conveyor, &ConveyorLine), Without<UnderConstruction>>,
+    mut storage_query: Query<&mut Storage>,
+    part_query: Query<&BuildingPart>,
+    map_query: MapQuery,
+    resources: Res<ResourceSpecifications>,
+) {
+    // the other tiles of larger storages like silos belong to their origin
+    let building_at = |pos: UVec2| -> Option<Entity> {
+        let entity = map_query
+            .get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID)
+            .ok()?;
+
+        Some(
+            part_query
+                .get(entity)
+                .map(|part| part.main)
+                .unwrap_or(entity),
+        )
+    };
+
+    // every belt is handled once, by its first tile
+    for (conveyor, line) in conveyor_query.iter() {
+        let resource = match &conveyor.resource {
+            Some(resource) => resource,
+            None => continue,
+        };
+
+        let source = line.source.and_then(building_at);
+        let target = line.target.and_then(building_at);
+
+        if let (Some(source), Some(target)) = (source, target) {
+            let amount = match (
+                storage_query.get_component::<Storage>(source),
+                storage_query.get_component::<Storage>(target),
+            ) {
+                (Ok(source), Ok(target)) => transfer_amount(source, target, resource, &resources),
+                _ => continue,
+            };
+
+            if amount <= 0.0 {
+                continue;
+            }
+
+            storage_query
+                .get_mut(source)
+                .unwrap()
+                .take(resource, amount);
+            storage_query.get_mut(target).unwrap().add(resource, amount);
+        }
+    }
+}
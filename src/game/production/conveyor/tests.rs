@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::game::{
+    assets::Direction,
+    conveyor::{is_line_start, line_ends, trace_line},
+};
+
+const COAL: &str = "coal";
+const COKE: &str = "coke";
+
+fn storage(resource: &str, amount: f64) -> Storage {
+    Storage {
+        resource: resource.to_owned(),
+        amount,
+        capacity: 10.0,
+        ..Default::default()
+    }
+}
+
+fn belt(tiles: &[(u32, u32, Direction)]) -> HashMap<UVec2, Direction> {
+    tiles
+        .iter()
+        .map(|(x, y, direction)| (UVec2::new(*x, *y), *direction))
+        .collect()
+}
+
+#[test]
+fn moves_at_fixed_rate() {
+    let amount = transfer_amount(
+        &storage(COAL, 8.0),
+        &storage(COAL, 0.0),
+        COAL,
+        &ResourceSpecifications::new(),
+    );
+
+    assert!((amount - CONVEYOR_RATE).abs() < f64::EPSILON);
+}
+
+#[test]
+fn limited_by_source_and_target() {
+    let amount = transfer_amount(
+        &storage(COAL, 2.0),
+        &storage(COAL, 0.0),
+        COAL,
+        &ResourceSpecifications::new(),
+    );
+    assert!((amount - 2.0).abs() < f64::EPSILON);
+
+    let amount = transfer_amount(
+        &storage(COAL, 8.0),
+        &storage(COAL, 9.0),
+        COAL,
+        &ResourceSpecifications::new(),
+    );
+    assert!((amount - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn only_configured_resource() {
+    let amount = transfer_amount(
+        &storage(COKE, 8.0),
+        &storage(COAL, 0.0),
+        COAL,
+        &ResourceSpecifications::new(),
+    );
+
+    assert!(amount.abs() < f64::EPSILON);
+}
+
+#[test]
+fn traces_belt_from_any_tile() {
+    let belt = belt(&[
+        (1, 1, Direction::East),
+        (2, 1, Direction::East),
+        (3, 1, Direction::North),
+        (3, 2, Direction::North),
+    ]);
+    let size = UVec2::new(10, 10);
+
+    let line = trace_line(UVec2::new(3, 1), size, |pos| belt.get(&pos).copied());
+
+    assert_eq!(
+        line,
+        vec![
+            UVec2::new(1, 1),
+            UVec2::new(2, 1),
+            UVec2::new(3, 1),
+            UVec2::new(3, 2)
+        ]
+    );
+}
+
+#[test]
+fn circular_belt_terminates() {
+    let belt = belt(&[
+        (1, 1, Direction::East),
+        (2, 1, Direction::North),
+        (2, 2, Direction::West),
+        (1, 2, Direction::South),
+    ]);
+    let size = UVec2::new(10, 10);
+
+    let line = trace_line(UVec2::new(1, 1), size, |pos| belt.get(&pos).copied());
+
+    assert_eq!(line.len(), 4);
+}
+
+#[test]
+fn lines_start_where_no_belt_leads_in() {
+    let belt = belt(&[
+        (1, 1, Direction::East),
+        (2, 1, Direction::East),
+        (3, 1, Direction::North),
+        (3, 2, Direction::North),
+    ]);
+    let size = UVec2::new(10, 10);
+    let direction_at = |pos: UVec2| belt.get(&pos).copied();
+
+    assert!(is_line_start(UVec2::new(1, 1), size, direction_at));
+    assert!(!is_line_start(UVec2::new(2, 1), size, direction_at));
+    assert!(!is_line_start(UVec2::new(3, 2), size, direction_at));
+
+    let line = line_ends(UVec2::new(1, 1), size, direction_at);
+    assert_eq!(line.source, Some(UVec2::new(0, 1)));
+    assert_eq!(line.target, Some(UVec2::new(3, 3)));
+}
+
+#[test]
+fn circular_belt_has_no_start() {
+    let belt = belt(&[
+        (1, 1, Direction::East),
+        (2, 1, Direction::North),
+        (2, 2, Direction::West),
+        (1, 2, Direction::South),
+    ]);
+    let size = UVec2::new(10, 10);
+    let direction_at = |pos: UVec2| belt.get(&pos).copied();
+
+    for (x, y) in [(1, 1), (2, 1), (2, 2), (1, 2)].iter() {
+        assert!(!is_line_start(UVec2::new(*x, *y), size, direction_at));
+    }
+}
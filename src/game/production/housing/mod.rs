@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+
+use crate::game::{
+    assets::resource_specifications::ResourceSpecifications,
+    construction::UnderConstruction,
+    production::{Housing, ProductDependency, ProductionBuilding, Workforce},
+    statistics::Statistics,
+    storage::{fetch_from_storage, has_in_storage, Storage, StorageConsolidator},
+};
+
+// the needed resource or one of its substitutes, with the amount to consume
+fn available_supply<'a>(
+    dependency: &'a ProductDependency,
+    consolidator: &StorageConsolidator,
+    storage_query: &mut Query<&mut Storage>,
+    resources: &'a ResourceSpecifications,
+) -> Option<(&'a String, f64)> {
+    if has_in_storage(
+        consolidator,
+        storage_query,
+        &dependency.resource,
+        dependency.rate,
+    ) {
+        return Some((&dependency.resource, dependency.rate));
+    }
+
+    let resource = resources.get(&dependency.resource)?;
+    resource
+        .substitute
+        .iter()
+        .map(|(substitute, rate)| (substitute, dependency.rate / rate))
+        .find(|(substitute, amount)| {
+            has_in_storage(consolidator, storage_query, substitute, *amount)
+        })
+}
+
+pub fn housing(
+    mut housing_query: Query<
+        (&mut Housing, &StorageConsolidator, &mut Statistics),
+        Without<UnderConstruction>,
+    >,
+    building_query: Query<&ProductionBuilding, Without<UnderConstruction>>,
+    mut storage_query: Query<&mut Storage>,
+    resources: Res<ResourceSpecifications>,
+    mut workforce: ResMut<Workforce>,
+) {
+    let mut available = 0;
+
+    for (mut housing, consolidator, mut statistics) in housing_query.iter_mut() {
+        let supplies: Vec<Option<(&String, f64)>> = housing
+            .consumption
+            .iter()
+            .map(|dependency| {
+                available_supply(dependency, consolidator, &mut storage_query, &resources)
+            })
+            .collect();
+
+        let supplied = supplies.iter().all(|supply| supply.is_some());
+        if supplied {
+            for (resource, amount) in supplies.into_iter().flatten() {
+                fetch_from_storage(consolidator, &mut storage_query, resource, amount);
+                statistics.consumption.track(resource, amount);
+            }
+        }
+
+        housing.supplied = supplied;
+        available += housing.workers();
+    }
+
+    workforce.available = workforce.base.unwrap_or(0) + available;
+    workforce.required = building_query
+        .iter()
+        .filter(|building| {
+            !building.mothballed && building.products.iter().any(|(_product, active)| *active)
+        })
+        .map(|building| building.workers)
+        .sum();
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::game::{
+    assets::resource_specifications::ResourceSpecification, production::Product, storage::Storage,
+};
+
+const BREAD: &str = "bread";
+const PORK: &str = "pork";
+const BEEF: &str = "beef";
+
+fn setup() -> (World, SystemStage) {
+    let mut world = World::default();
+
+    let mut substitute = HashMap::new();
+    substitute.insert(BEEF.to_owned(), 1.0);
+
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(BREAD.to_owned(), ResourceSpecification::default());
+    resources.insert(
+        PORK.to_owned(),
+        ResourceSpecification {
+            substitute,
+            ..Default::default()
+        },
+    );
+    resources.insert(BEEF.to_owned(), ResourceSpecification::default());
+    world.insert_resource(resources);
+    world.insert_resource(Workforce {
+        base: Some(0),
+        ..Default::default()
+    });
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(housing.system());
+
+    (world, stage)
+}
+
+fn add_storage(world: &mut World, resource: &str, amount: f64) -> Entity {
+    world
+        .spawn()
+        .insert(Storage {
+            resource: resource.to_owned(),
+            amount,
+            capacity: 10.0,
+            ..Default::default()
+        })
+        .id()
+}
+
+fn add_housing(world: &mut World, connected_storage: Vec<Entity>) -> Entity {
+    world
+        .spawn()
+        .insert(Housing {
+            residents: 10,
+            consumption: vec![
+                ProductDependency {
+                    resource: BREAD.to_owned(),
+                    rate: 1.0,
+                },
+                ProductDependency {
+                    resource: PORK.to_owned(),
+                    rate: 0.5,
+                },
+            ],
+            ..Default::default()
+        })
+        .insert(StorageConsolidator {
+            connected_storage,
+            ..Default::default()
+        })
+        .insert(Statistics::default())
+        .id()
+}
+
+fn add_building(world: &mut World, workers: u32, active: bool) {
+    world.spawn().insert(ProductionBuilding {
+        products: vec![(Product::default(), active)],
+        workers,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn supplied_housing_provides_workers() {
+    let (mut world, mut stage) = setup();
+
+    let bread_id = add_storage(&mut world, BREAD, 5.0);
+    let pork_id = add_storage(&mut world, PORK, 5.0);
+    let housing_id = add_housing(&mut world, vec![bread_id, pork_id]);
+    add_building(&mut world, 20, true);
+    add_building(&mut world, 50, false);
+
+    stage.run(&mut world);
+
+    assert!(world.get::<Housing>(housing_id).unwrap().supplied);
+    assert!((world.get::<Storage>(bread_id).unwrap().amount - 4.0).abs() < f64::EPSILON);
+    assert!((world.get::<Storage>(pork_id).unwrap().amount - 4.5).abs() < f64::EPSILON);
+
+    let workforce = world.get_resource::<Workforce>().unwrap();
+    assert_eq!(workforce.available, 10);
+    assert_eq!(workforce.required, 20);
+    assert!((workforce.staffing() - 0.5).abs() < f64::EPSILON);
+}
+
+#[test]
+fn substitutes_are_consumed() {
+    let (mut world, mut stage) = setup();
+
+    let bread_id = add_storage(&mut world, BREAD, 5.0);
+    let beef_id = add_storage(&mut world, BEEF, 5.0);
+    let housing_id = add_housing(&mut world, vec![bread_id, beef_id]);
+
+    stage.run(&mut world);
+
+    assert!(world.get::<Housing>(housing_id).unwrap().supplied);
+    assert!((world.get::<Storage>(beef_id).unwrap().amount - 4.5).abs() < f64::EPSILON);
+}
+
+#[test]
+fn missing_supplies_halve_workers() {
+    let (mut world, mut stage) = setup();
+
+    let bread_id = add_storage(&mut world, BREAD, 5.0);
+    let housing_id = add_housing(&mut world, vec![bread_id]);
+
+    stage.run(&mut world);
+
+    assert!(!world.get::<Housing>(housing_id).unwrap().supplied);
+    // nothing is consumed while something is missing
+    assert!((world.get::<Storage>(bread_id).unwrap().amount - 5.0).abs() < f64::EPSILON);
+    assert_eq!(world.get_resource::<Workforce>().unwrap().available, 5);
+    assert!((world.get_resource::<Workforce>().unwrap().staffing() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn starting_town_provides_workers() {
+    let (mut world, mut stage) = setup();
+    world.insert_resource(Workforce {
+        base: Some(20),
+        ..Default::default()
+    });
+    add_building(&mut world, 20, true);
+
+    stage.run(&mut world);
+
+    let workforce = world.get_resource::<Workforce>().unwrap();
+    assert_eq!(workforce.available, 20);
+    assert!((workforce.staffing() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn saves_without_housing_are_fully_staffed() {
+    let (mut world, mut stage) = setup();
+    world.insert_resource(Workforce::unlimited());
+    add_building(&mut world, 20, true);
+
+    stage.run(&mut world);
+
+    let workforce = world.get_resource::<Workforce>().unwrap();
+    assert!((workforce.staffing() - 1.0).abs() < f64::EPSILON);
+}
@@ -4,11 +4,11 @@ mod tests;
 use bevy::prelude::*;
 
 const MAX_AMOUNT: f64 = 10.0;
-const IMPORT_SURCHARGE: f64 = 1.2;
 
 use crate::game::{
-    account::AccountTransaction,
+    account::{AccountTransaction, TransactionCategory},
     assets::resource_specifications::ResourceSpecifications,
+    market::Market,
     production::ImportExportStation,
     statistics::Statistics,
     storage::fetch_from_storage,
@@ -20,12 +20,18 @@ use crate::game::{
 use super::ImportExportDirection;
 
 pub fn import_export_station(
-    mut station_query: Query<(&ImportExportStation, &StorageConsolidator, &mut Statistics)>,
+    mut station_query: Query<(
+        Entity,
+        &ImportExportStation,
+        &StorageConsolidator,
+        &mut Statistics,
+    )>,
     mut storage_query: Query<&mut Storage>,
     resources: Res<ResourceSpecifications>,
     mut events: EventWriter<AccountTransaction>,
+    mut market: ResMut<Market>,
 ) {
-    for (station, consolidator, mut statistics) in station_query.iter_mut() {
+    for (entity, station, consolidator, mut statistics) in station_query.iter_mut() {
         for resource in &station.goods {
             if station.direction == ImportExportDirection::Export {
                 let amount =
@@ -35,12 +41,16 @@ pub fn import_export_station(
                     && fetch_from_storage(consolidator, &mut storage_query, resource, amount)
                 {
                     statistics.export.track(resource, amount);
+                    let price = market.export_price(resource, &resources);
+                    market.track_export(resource, amount);
                     let resource = resources.get(resource).unwrap();
 
                     log::info!("Exporting {} {:?}", amount, resource.name);
 
                     events.send(AccountTransaction {
-                        amount: (amount * resource.cost) as i64,
+                        amount: (amount * price) as i64,
+                        category: TransactionCategory::Export,
+                        source: Some(entity),
                     });
 
                     break;
@@ -49,18 +59,29 @@ pub fn import_export_station(
 
             if station.direction == ImportExportDirection::Import {
                 let amount =
-                    space_in_storage(consolidator, &mut storage_query, resource).min(MAX_AMOUNT);
+                    space_in_storage(consolidator, &mut storage_query, resource, &resources)
+                        .min(MAX_AMOUNT);
 
                 if amount > 0.0 {
-                    distribute_to_storage(consolidator, &mut storage_query, resource, amount);
+                    distribute_to_storage(
+                        consolidator,
+                        &mut storage_query,
+                        resource,
+                        amount,
+                        &resources,
+                    );
 
                     statistics.import.track(resource, amount);
+                    let price = market.import_price(resource, &resources);
+                    market.track_import(resource, amount);
                     let resource = resources.get(resource).unwrap();
 
                     log::info!("Importing {} {:?}", amount, resource.name);
 
                     events.send(AccountTransaction {
-                        amount: (IMPORT_SURCHARGE * amount * -resource.cost) as i64,
+                        amount: (amount * -price) as i64,
+                        category: TransactionCategory::Import,
+                        source: Some(entity),
                     });
 
                     break;
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::game::{
-    assets::resource_specifications::ResourceSpecification, production::ImportExportDirection,
+    assets::resource_specifications::ResourceSpecification,
+    market::{Market, IMPORT_SURCHARGE},
+    production::ImportExportDirection,
 };
 
 use super::*;
@@ -111,6 +113,7 @@ impl TestSetup {
         );
         world.insert_resource(resources);
         world.insert_resource(Events::<AccountTransaction>::default());
+        world.insert_resource(Market::default());
 
         let mut stage = SystemStage::parallel();
         stage.add_system(import_export_station.system());
@@ -121,6 +124,7 @@ impl TestSetup {
                 resource: RESOURCE.to_owned(),
                 amount: setup.amount_in_storage,
                 capacity: 10.0,
+                ..Default::default()
             })
             .id();
 
@@ -143,6 +147,7 @@ impl TestSetup {
                     resource: RESOURCE.to_owned(),
                     amount: setup.amount_in_storage,
                     capacity: 10.0,
+                    ..Default::default()
                 })
                 .id();
 
@@ -161,7 +166,10 @@ impl TestSetup {
                 direction: setup.station_direction,
             })
             .insert(Statistics::default())
-            .insert(StorageConsolidator { connected_storage })
+            .insert(StorageConsolidator {
+                connected_storage,
+                ..Default::default()
+            })
             .id();
 
         stage.run(&mut world);
@@ -1,17 +1,26 @@
+pub mod conveyor;
+pub mod housing;
 pub mod idle;
 pub mod import_export_station;
+pub mod pipeline;
 pub mod production_building;
+pub mod spoilage;
 pub mod storage_management;
+pub mod town_shop;
 
 use std::collections::HashSet;
 
 use bevy::prelude::*;
 use bevy_egui::egui::Ui;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use super::{
     account::PurchaseCost,
-    assets::{resource_specifications::ResourceSpecifications, InfoUI},
+    assets::{
+        building_specifications::BuildingSpecificationHousing,
+        resource_specifications::ResourceSpecifications, InfoUI,
+    },
+    constants::{START_WORKFORCE, TOWN_START_POPULATION},
 };
 
 #[derive(Default)]
@@ -19,10 +28,109 @@ pub struct Idle {
     pub entity: Option<Entity>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ProductionBuilding {
     pub products: Vec<(Product, bool)>,
+    #[serde(default)]
+    pub workers: u32,
+    // mothballed buildings stay idle at a reduced maintenance
+    #[serde(default)]
+    pub mothballed: bool,
+}
+
+#[derive(Clone, Default)]
+pub struct Housing {
+    pub residents: u32,
+    pub consumption: Vec<ProductDependency>,
+    // whether all consumed goods were available on the last tick
+    pub supplied: bool,
+}
+
+impl From<&BuildingSpecificationHousing> for Housing {
+    fn from(specification: &BuildingSpecificationHousing) -> Self {
+        Self {
+            residents: specification.residents,
+            consumption: specification.consumption.clone(),
+            supplied: false,
+        }
+    }
+}
+
+impl Housing {
+    // residents without supplies only work half of the time
+    pub fn workers(&self) -> u32 {
+        if self.supplied {
+            self.residents
+        } else {
+            self.residents / 2
+        }
+    }
+}
+
+impl InfoUI for Housing {
+    fn ui(&self, ui: &mut Ui, resources: &ResourceSpecifications) {
+        ui.label(format!("Residents: {}", self.residents));
+        ui.label(format!("Workers: {}", self.workers()));
+
+        if !self.supplied {
+            let missing: Vec<String> = self
+                .consumption
+                .iter()
+                .map(|dependency| {
+                    resources
+                        .get(&dependency.resource)
+                        .map(|resource| resource.name.clone())
+                        .unwrap_or_else(|| dependency.resource.clone())
+                })
+                .collect();
+
+            ui.label(format!("Needs {}", missing.join(", ")));
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Workforce {
+    // workers of the starting town, saves from before housing have no limit
+    pub base: Option<u32>,
+    #[serde(skip)]
+    pub available: u32,
+    #[serde(skip)]
+    pub required: u32,
+}
+
+impl Default for Workforce {
+    fn default() -> Self {
+        Self {
+            base: Some(START_WORKFORCE),
+            available: 0,
+            required: 0,
+        }
+    }
+}
+
+impl Workforce {
+    pub fn unlimited() -> Self {
+        Self {
+            base: None,
+            ..Default::default()
+        }
+    }
+
+    // share of required workers, production slows down accordingly
+    pub fn staffing(&self) -> f64 {
+        if self.base.is_none() || self.required == 0 {
+            1.0
+        } else {
+            (self.available as f64 / self.required as f64).min(1.0)
+        }
+    }
+}
+
+pub fn reset_workforce(mut workforce: ResMut<Workforce>) {
+    *workforce = Workforce::default();
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -99,7 +207,7 @@ impl InfoUI for ImportExportStation {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Depot {
     pub deliveries: HashSet<UVec2>,
@@ -130,9 +238,133 @@ impl PurchaseCost for DeliveryStation {
     }
 }
 
+// older saves stored a town per shop, those fields are ignored
 #[derive(Serialize, Deserialize, Clone, Default)]
+pub struct TownShop {}
+
+impl PurchaseCost for TownShop {
+    fn price(&self, _resources: &ResourceSpecifications) -> i64 {
+        3000
+    }
+}
+
+impl InfoUI for TownShop {
+    fn ui(&self, ui: &mut Ui, _resources: &ResourceSpecifications) {
+        ui.label("Town Shop");
+    }
+}
+
+// the town all shops sell to
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
-pub struct StorageManagement;
+pub struct Town {
+    pub population: f64,
+    // between 0 and 1, follows how well demand is met
+    pub happiness: f64,
+}
+
+impl Default for Town {
+    fn default() -> Self {
+        Self {
+            population: TOWN_START_POPULATION,
+            happiness: 0.5,
+        }
+    }
+}
+
+pub fn reset_town(mut town: ResMut<Town>) {
+    *town = Town::default();
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TrainStation;
+
+impl PurchaseCost for TrainStation {
+    fn price(&self, _resources: &ResourceSpecifications) -> i64 {
+        600
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageManagementMode {
+    Equalize,
+    FillTargetFirst,
+    KeepPercentage,
+    InputToOutput,
+}
+
+impl StorageManagementMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StorageManagementMode::Equalize => "Equalize",
+            StorageManagementMode::FillTargetFirst => "Fill targets first",
+            StorageManagementMode::KeepPercentage => "Keep percentage in targets",
+            StorageManagementMode::InputToOutput => "Move inputs to targets",
+        }
+    }
+}
+
+impl Default for StorageManagementMode {
+    fn default() -> Self {
+        StorageManagementMode::Equalize
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct StorageManagement {
+    #[serde(default)]
+    pub mode: StorageManagementMode,
+    #[serde(default)]
+    pub targets: HashSet<UVec2>,
+    #[serde(default)]
+    pub inputs: HashSet<UVec2>,
+    #[serde(default = "StorageManagement::default_percentage")]
+    pub percentage: f64,
+    #[serde(default = "StorageManagement::default_transfers")]
+    pub transfers_per_tick: usize,
+}
+
+impl StorageManagement {
+    fn default_percentage() -> f64 {
+        0.5
+    }
+
+    fn default_transfers() -> usize {
+        4
+    }
+
+    // saves from before the settings existed store storage management as a unit
+    pub fn deserialize_legacy<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Format {
+            Settings(StorageManagement),
+            Legacy(()),
+        }
+
+        match Format::deserialize(deserializer)? {
+            Format::Settings(management) => Ok(management),
+            Format::Legacy(()) => Ok(Self::default()),
+        }
+    }
+}
+
+impl Default for StorageManagement {
+    fn default() -> Self {
+        Self {
+            mode: StorageManagementMode::Equalize,
+            targets: HashSet::new(),
+            inputs: HashSet::new(),
+            percentage: StorageManagement::default_percentage(),
+            transfers_per_tick: StorageManagement::default_transfers(),
+        }
+    }
+}
 
 impl PurchaseCost for StorageManagement {
     fn price(&self, _resources: &ResourceSpecifications) -> i64 {
@@ -145,5 +377,8 @@ impl InfoUI for StorageManagement {
         ui.horizontal(|ui| {
             ui.label("Storage Management");
         });
+        ui.horizontal(|ui| {
+            ui.label(format!("Mode: {}", self.mode.name()));
+        });
     }
 }
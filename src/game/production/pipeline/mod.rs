@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    assets::Position,
+    construction::UnderConstruction,
+    pipeline::Pipeline,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    storage::Storage,
+};
+
+pub const PIPELINE_RATE: f64 = 10.0;
+
+pub fn balance_tanks(tanks: &[Entity], storage_query: &mut Query<&mut Storage>) {
+    let mut by_resource: HashMap<String, Vec<Entity>> = HashMap::new();
+    for tank in tanks.iter() {
+        if let Ok(storage) = storage_query.get_component::<Storage>(*tank) {
+            by_resource
+                .entry(storage.resource.clone())
+                .or_default()
+                .push(*tank);
+        }
+    }
+
+    for (_resource, tanks) in by_resource.into_iter() {
+        if tanks.len() < 2 {
+            continue;
+        }
+
+        let (amount, capacity) = tanks.iter().fold((0.0, 0.0), |(amount, capacity), tank| {
+            let storage = storage_query.get_component::<Storage>(*tank).unwrap();
+            (amount + storage.amount, capacity + storage.capacity)
+        });
+
+        if capacity <= 0.0 {
+            continue;
+        }
+
+        let level = amount / capacity;
+        let differences: Vec<(Entity, f64)> = tanks
+            .iter()
+            .map(|tank| {
+                let storage = storage_query.get_component::<Storage>(*tank).unwrap();
+                (*tank, storage.amount - storage.capacity * level)
+            })
+            .collect();
+
+        let surplus: f64 = differences
+            .iter()
+            .map(|(_tank, difference)| difference.max(0.0))
+            .sum();
+
+        if surplus <= f64::EPSILON {
+            continue;
+        }
+
+        // every tank moves the same share towards the common level
+        let factor = surplus.min(PIPELINE_RATE) / surplus;
+        for (tank, difference) in differences.into_iter() {
+            let mut storage = storage_query.get_mut(tank).unwrap();
+            storage.amount = (storage.amount - difference * factor).max(0.0);
+        }
+    }
+}
+
+pub fn pipeline(
+    pipeline_query: Query<&Position, (With<Pipeline>, Without<UnderConstruction>)>,
+    construction_query: Query<(), With<UnderConstruction>>,
+    mut storage_query: Query<&mut Storage>,
+    map_query: MapQuery,
+) {
+    let mut visited = HashSet::new();
+
+    for position in pipeline_query.iter() {
+        if !visited.insert(position.position) {
+            continue;
+        }
+
+        let mut tanks = vec![];
+        let mut open = vec![position.position];
+
+        while let Some(pos) = open.pop() {
+            let neighbors = map_query.get_tile_neighbors(pos, MAP_ID, BUILDING_LAYER_ID);
+
+            for (neighbor_pos, neighbor) in neighbors[0..4].iter() {
+                let entity = match neighbor {
+                    Some(entity) => *entity,
+                    None => continue,
+                };
+
+                if pipeline_query.get(entity).is_ok() {
+                    if visited.insert(neighbor_pos.as_u32()) {
+                        open.push(neighbor_pos.as_u32());
+                    }
+                } else if construction_query.get(entity).is_err() && !tanks.contains(&entity) {
+                    if let Ok(storage) = storage_query.get_component::<Storage>(entity) {
+                        if storage.is_tank() {
+                            tanks.push(entity);
+                        }
+                    }
+                }
+            }
+        }
+
+        balance_tanks(&tanks, &mut storage_query);
+    }
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+const MILK: &str = "milk";
+const OIL: &str = "sunflower.oil";
+
+struct TestTanks(Vec<Entity>);
+
+fn balance_system(tanks: Res<TestTanks>, mut storage_query: Query<&mut Storage>) {
+    balance_tanks(&tanks.0, &mut storage_query);
+}
+
+struct TestSetup {
+    world: World,
+    tanks: Vec<Entity>,
+}
+
+impl TestSetup {
+    fn new() -> Self {
+        Self {
+            world: World::default(),
+            tanks: vec![],
+        }
+    }
+
+    fn add_tank(&mut self, resource: &str, amount: f64, capacity: f64) -> Entity {
+        let entity = self
+            .world
+            .spawn()
+            .insert(Storage {
+                amount,
+                capacity,
+                ..Storage::new_tank(resource)
+            })
+            .id();
+
+        self.tanks.push(entity);
+        entity
+    }
+
+    fn run(&mut self) {
+        self.world.insert_resource(TestTanks(self.tanks.clone()));
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(balance_system.system());
+        stage.run(&mut self.world);
+    }
+
+    fn assert_amount(&self, entity: Entity, amount: f64) {
+        let actual = self.world.get::<Storage>(entity).unwrap().amount;
+        assert!(
+            (actual - amount).abs() < 1e-9,
+            "expected tank to be {} but it was {}",
+            amount,
+            actual
+        );
+    }
+}
+
+#[test]
+fn balances_levels() {
+    let mut setup = TestSetup::new();
+    let full = setup.add_tank(MILK, 6.0, 10.0);
+    let empty = setup.add_tank(MILK, 0.0, 10.0);
+
+    setup.run();
+
+    setup.assert_amount(full, 3.0);
+    setup.assert_amount(empty, 3.0);
+}
+
+#[test]
+fn limited_by_rate() {
+    let mut setup = TestSetup::new();
+    let full = setup.add_tank(MILK, 100.0, 100.0);
+    let empty = setup.add_tank(MILK, 0.0, 100.0);
+
+    setup.run();
+
+    setup.assert_amount(full, 100.0 - PIPELINE_RATE);
+    setup.assert_amount(empty, PIPELINE_RATE);
+}
+
+#[test]
+fn balances_by_percentage() {
+    let mut setup = TestSetup::new();
+    let small = setup.add_tank(MILK, 6.0, 10.0);
+    let big = setup.add_tank(MILK, 0.0, 20.0);
+
+    setup.run();
+
+    setup.assert_amount(small, 2.0);
+    setup.assert_amount(big, 4.0);
+}
+
+#[test]
+fn does_not_mix_liquids() {
+    let mut setup = TestSetup::new();
+    let milk = setup.add_tank(MILK, 6.0, 10.0);
+    let oil = setup.add_tank(OIL, 0.0, 10.0);
+
+    setup.run();
+
+    setup.assert_amount(milk, 6.0);
+    setup.assert_amount(oil, 0.0);
+}
@@ -5,10 +5,10 @@ use bevy::prelude::*;
 use rand::{prelude::SliceRandom, thread_rng};
 
 use crate::game::{
-    account::AccountTransaction,
+    account::{AccountTransaction, TransactionCategory},
     assets::resource_specifications::ResourceSpecifications,
     construction::UnderConstruction,
-    production::{Idle, ProductionBuilding},
+    production::{Idle, ProductionBuilding, Workforce},
     statistics::Statistics,
     storage::{distribute_to_storage, fetch_from_storage, has_in_storage, has_space_in_storage},
     storage::{Storage, StorageConsolidator},
@@ -29,29 +29,45 @@ pub fn production_building(
     mut storage_query: Query<&mut Storage>,
     resources: Res<ResourceSpecifications>,
     mut events: EventWriter<AccountTransaction>,
+    workforce: Res<Workforce>,
 ) {
     for (entity, building, consolidator, mut statistics, idle) in building_query.iter_mut() {
+        if building.mothballed {
+            if idle.is_none() {
+                commands.entity(entity).insert(Idle::default());
+            }
+
+            continue;
+        }
+
+        let inputs = consolidator.inputs();
+        let outputs = consolidator.outputs();
+
+        // understaffed buildings run at a reduced rate
+        let staffing = if building.workers > 0 {
+            workforce.staffing()
+        } else {
+            1.0
+        };
+
         let mut available_products = vec![];
 
         for (index, (product, active)) in building.products.iter().enumerate() {
-            if !active {
+            if !active || staffing <= 0.0 {
                 continue;
             };
 
-            let mut modifier = 1.0;
+            let mut modifier = staffing;
             let mut consumed_resources = vec![];
 
             let mut has_requisites = true;
             for requisite in product.requisites.iter() {
-                if !has_in_storage(
-                    consolidator,
-                    &mut storage_query,
-                    &requisite.resource,
-                    requisite.rate,
-                ) {
+                let rate = requisite.rate * staffing;
+
+                if !has_in_storage(&inputs, &mut storage_query, &requisite.resource, rate) {
                     has_requisites = false;
                 }
-                consumed_resources.push((&requisite.resource, requisite.rate));
+                consumed_resources.push((&requisite.resource, rate));
             }
             if !has_requisites {
                 continue;
@@ -59,7 +75,7 @@ pub fn production_building(
 
             for enhancer in &product.enhancers {
                 let enhancer_present = has_in_storage(
-                    consolidator,
+                    &inputs,
                     &mut storage_query,
                     &enhancer.resource,
                     enhancer.rate,
@@ -74,7 +90,7 @@ pub fn production_building(
                 let enhancer_resource = resources.get(&enhancer.resource).unwrap();
                 for (substitute, rate) in enhancer_resource.substitute.iter() {
                     if has_in_storage(
-                        consolidator,
+                        &inputs,
                         &mut storage_query,
                         substitute,
                         enhancer.rate / rate,
@@ -87,10 +103,11 @@ pub fn production_building(
             }
 
             if has_space_in_storage(
-                consolidator,
+                &outputs,
                 &mut storage_query,
                 &product.resource,
                 product.rate * modifier,
+                &resources,
             ) {
                 available_products.push((index, modifier, consumed_resources));
             }
@@ -113,15 +130,16 @@ pub fn production_building(
         let consumed_resources = &available_products[0].2;
 
         for (resource, amount) in consumed_resources {
-            fetch_from_storage(consolidator, &mut storage_query, resource, *amount);
+            fetch_from_storage(&inputs, &mut storage_query, resource, *amount);
             statistics.consumption.track(resource, *amount);
         }
 
         distribute_to_storage(
-            consolidator,
+            &outputs,
             &mut storage_query,
             &product.resource,
             product.rate * modifier,
+            &resources,
         );
 
         statistics
@@ -137,16 +155,18 @@ pub fn production_building(
 
         for byproduct in &product.byproducts {
             if has_space_in_storage(
-                consolidator,
+                &outputs,
                 &mut storage_query,
                 &byproduct.resource,
                 byproduct.rate * modifier,
+                &resources,
             ) {
                 distribute_to_storage(
-                    consolidator,
+                    &outputs,
                     &mut storage_query,
                     &byproduct.resource,
                     byproduct.rate * modifier,
+                    &resources,
                 );
 
                 statistics
@@ -158,6 +178,8 @@ pub fn production_building(
         if product.cost > 0.0 {
             events.send(AccountTransaction {
                 amount: -product.cost as i64,
+                category: TransactionCategory::ProductionCost,
+                source: Some(entity),
             });
         }
     }
@@ -4,9 +4,9 @@ use super::*;
 use crate::game::{
     account::AccountTransaction,
     assets::resource_specifications::ResourceSpecification,
-    production::{Product, ProductDependency, ProductEnhancer},
+    production::{Product, ProductDependency, ProductEnhancer, Workforce},
     statistics::Statistics,
-    storage::Storage,
+    storage::{ConsolidatorPorts, Storage},
 };
 use bevy::{app::Events, prelude::*};
 
@@ -29,6 +29,7 @@ impl TestSetup {
                 resource: resource.to_owned(),
                 amount,
                 capacity: 10.0,
+                ..Default::default()
             })
             .id()
     }
@@ -120,6 +121,7 @@ impl TestSetup {
 
         world.insert_resource(resource_specifications);
         world.insert_resource(Events::<AccountTransaction>::default());
+        world.insert_resource(Workforce::default());
 
         let mut stage = SystemStage::parallel();
         stage.add_system(production_building.system());
@@ -166,9 +168,11 @@ fn produces_resource() {
                     false,
                 ),
             ],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id, coke_storage_id],
+            ..Default::default()
         })
         .id();
 
@@ -234,9 +238,11 @@ fn produces_byproducts() {
                 },
                 true,
             )],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id, coke_storage_id],
+            ..Default::default()
         })
         .id();
 
@@ -256,6 +262,7 @@ fn produces_byproducts() {
         .unwrap()
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id, coke_storage_id, slug_storage_id],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
@@ -303,9 +310,11 @@ fn increases_production_with_enhancers() {
                 },
                 true,
             )],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id, coke_storage_id, slug_storage_id],
+            ..Default::default()
         })
         .id();
 
@@ -332,6 +341,7 @@ fn increases_production_with_enhancers() {
                 slug_storage_id,
                 enhancer_storage_id,
             ],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
@@ -381,6 +391,7 @@ fn increases_production_with_enhancer_substitute() {
                 },
                 true,
             )],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![
@@ -389,6 +400,7 @@ fn increases_production_with_enhancer_substitute() {
                 slug_storage_id,
                 enhancer_storage_id,
             ],
+            ..Default::default()
         })
         .id();
 
@@ -428,9 +440,11 @@ fn no_production_is_marked_idle() {
                 },
                 true,
             )],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![],
+            ..Default::default()
         })
         .id();
 
@@ -444,6 +458,7 @@ fn no_production_is_marked_idle() {
         .unwrap()
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id, coke_storage_id],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
@@ -483,9 +498,11 @@ fn random_production() {
                     true,
                 ),
             ],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id, coke_storage_id],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
@@ -515,9 +532,11 @@ fn ignores_under_construction() {
                 },
                 true,
             )],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id],
+            ..Default::default()
         })
         .insert(UnderConstruction::from_fixed_cost(0))
         .id();
@@ -549,12 +568,119 @@ fn with_cost() {
                 },
                 true,
             )],
+            ..Default::default()
         })
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
     setup.assert_storage_amount(coke_storage_id, 1.0);
     setup.assert_event_sum(-10);
 }
+
+#[test]
+fn uses_storage_on_port_sides() {
+    let mut setup = TestSetup::new();
+
+    let input_coke_storage_id = setup.add_storage(COKE, 0.0);
+    let output_coke_storage_id = setup.add_storage(COKE, 0.0);
+    let input_coal_storage_id = setup.add_storage(COAL, 10.0);
+    let output_coal_storage_id = setup.add_storage(COAL, 10.0);
+
+    setup
+        .world
+        .spawn()
+        .insert(Statistics::default())
+        .insert(ProductionBuilding {
+            products: vec![(
+                Product {
+                    resource: COKE.to_owned(),
+                    rate: 1.0,
+                    requisites: vec![ProductDependency {
+                        resource: COAL.to_owned(),
+                        rate: 2.0,
+                    }],
+                    ..Default::default()
+                },
+                true,
+            )],
+            ..Default::default()
+        })
+        .insert(StorageConsolidator {
+            connected_storage: vec![
+                input_coke_storage_id,
+                output_coke_storage_id,
+                input_coal_storage_id,
+                output_coal_storage_id,
+            ],
+            ports: Some(ConsolidatorPorts {
+                inputs: vec![input_coke_storage_id, input_coal_storage_id],
+                outputs: vec![output_coke_storage_id, output_coal_storage_id],
+            }),
+            ..Default::default()
+        });
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(input_coke_storage_id, 0.0);
+    setup.assert_storage_amount(output_coke_storage_id, 1.0);
+    setup.assert_storage_amount(input_coal_storage_id, 8.0);
+    setup.assert_storage_amount(output_coal_storage_id, 10.0);
+}
+
+#[test]
+fn understaffed_buildings_produce_less() {
+    let mut setup = TestSetup::new();
+    setup.world.insert_resource(Workforce {
+        available: 10,
+        required: 40,
+        ..Default::default()
+    });
+
+    let coke_storage_id = setup.add_storage(COKE, 0.0);
+    let coal_storage_id = setup.add_storage(COAL, 10.0);
+
+    setup
+        .world
+        .spawn()
+        .insert(Statistics::default())
+        .insert(ProductionBuilding {
+            products: vec![(
+                Product {
+                    resource: COKE.to_owned(),
+                    rate: 1.0,
+                    requisites: vec![ProductDependency {
+                        resource: COAL.to_owned(),
+                        rate: 2.0,
+                    }],
+                    ..Default::default()
+                },
+                true,
+            )],
+            workers: 40,
+            ..Default::default()
+        })
+        .insert(StorageConsolidator {
+            connected_storage: vec![coal_storage_id, coke_storage_id],
+            ..Default::default()
+        });
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(coke_storage_id, 0.25);
+    setup.assert_storage_amount(coal_storage_id, 9.5);
+
+    // nobody to work at all
+    setup.world.insert_resource(Workforce {
+        available: 0,
+        required: 40,
+        ..Default::default()
+    });
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(coke_storage_id, 0.25);
+    setup.assert_storage_amount(coal_storage_id, 9.5);
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests;
+
+use bevy::prelude::*;
+
+use crate::game::{
+    assets::resource_specifications::ResourceSpecifications, statistics::Statistics,
+    storage::Storage, train::Train,
+};
+
+pub fn spoilage(
+    mut storage_query: Query<(&mut Storage, Option<&mut Statistics>)>,
+    mut train_query: Query<&mut Train>,
+    resources: Res<ResourceSpecifications>,
+) {
+    for (mut storage, statistics) in storage_query.iter_mut() {
+        if storage.is_empty() {
+            continue;
+        }
+
+        let losses = storage.spoil(&resources);
+
+        if let Some(mut statistics) = statistics {
+            for (resource, amount) in losses {
+                statistics.spoiled.track(&resource, amount);
+            }
+        }
+    }
+
+    // goods left in a parked train decay like anywhere else
+    for mut train in train_query.iter_mut() {
+        for wagon in train.wagons.iter_mut() {
+            if !wagon.is_empty() {
+                wagon.spoil(&resources);
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use super::*;
+use crate::game::{
+    assets::resource_specifications::ResourceSpecification,
+    constants::PRODUCTION_TICK_SPEED,
+    storage::{spoilage_factor, Storage},
+    train::Train,
+};
+
+const MILK: &str = "milk";
+const COAL: &str = "coal";
+
+fn setup() -> (World, SystemStage) {
+    let mut world = World::default();
+
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(
+        MILK.to_owned(),
+        ResourceSpecification {
+            perishable: Some(PRODUCTION_TICK_SPEED),
+            ..Default::default()
+        },
+    );
+    resources.insert(COAL.to_owned(), ResourceSpecification::default());
+    world.insert_resource(resources);
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(spoilage.system());
+
+    (world, stage)
+}
+
+fn storage(resource: &str, refrigerated: bool) -> Storage {
+    Storage {
+        resource: resource.to_owned(),
+        amount: 8.0,
+        capacity: 10.0,
+        refrigerated,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn perishable_goods_decay() {
+    let (mut world, mut stage) = setup();
+
+    let milk_id = world
+        .spawn()
+        .insert(storage(MILK, false))
+        .insert(Statistics::default())
+        .id();
+    let coal_id = world.spawn().insert(storage(COAL, false)).id();
+
+    stage.run(&mut world);
+
+    assert!((world.get::<Storage>(milk_id).unwrap().amount - 4.0).abs() < f64::EPSILON);
+    assert!((world.get::<Storage>(coal_id).unwrap().amount - 8.0).abs() < f64::EPSILON);
+
+    let statistics = world.get::<Statistics>(milk_id).unwrap();
+    assert!((statistics.spoiled.get(MILK) - 4.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn refrigeration_slows_decay() {
+    let (mut world, mut stage) = setup();
+
+    let milk_id = world.spawn().insert(storage(MILK, true)).id();
+
+    stage.run(&mut world);
+
+    let expected = 8.0 * spoilage_factor(PRODUCTION_TICK_SPEED, true);
+    let amount = world.get::<Storage>(milk_id).unwrap().amount;
+    assert!((amount - expected).abs() < 1e-9);
+    assert!(amount > 4.0);
+}
+
+#[test]
+fn warehouse_contents_decay() {
+    let (mut world, mut stage) = setup();
+
+    let mut warehouse = Storage::new_warehouse();
+    warehouse.add(MILK, 2.0);
+    warehouse.add(COAL, 2.0);
+    let warehouse_id = world.spawn().insert(warehouse).id();
+
+    stage.run(&mut world);
+
+    let warehouse = world.get::<Storage>(warehouse_id).unwrap();
+    assert!((warehouse.amount_of(MILK) - 1.0).abs() < f64::EPSILON);
+    assert!((warehouse.amount_of(COAL) - 2.0).abs() < f64::EPSILON);
+    assert!((warehouse.amount - 3.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn wagon_contents_decay() {
+    let (mut world, mut stage) = setup();
+
+    let train_id = world
+        .spawn()
+        .insert(Train {
+            wagons: vec![storage(MILK, false), storage(COAL, false)],
+            ..Default::default()
+        })
+        .id();
+
+    stage.run(&mut world);
+
+    let train = world.get::<Train>(train_id).unwrap();
+    assert!((train.wagons[0].amount - 4.0).abs() < f64::EPSILON);
+    assert!((train.wagons[1].amount - 8.0).abs() < f64::EPSILON);
+}
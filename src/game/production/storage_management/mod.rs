@@ -8,22 +8,161 @@ use bevy::prelude::*;
 const MAX_AMOUNT: f64 = 5.0;
 
 use crate::game::{
+    assets::Position,
     construction::UnderConstruction,
     storage::{Storage, StorageConsolidator},
 };
 use collecting_hashmap::CollectingHashMap;
 use combinations::Combinations;
 
-use super::StorageManagement;
+use super::{StorageManagement, StorageManagementMode};
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum StorageRole {
+    Target,
+    Input,
+    Other,
+}
+
+struct ManagedStorage {
+    storage: Storage,
+    role: StorageRole,
+}
+
+// (amount, from, to)
+type Transfer = (f64, Entity, Entity);
+
+fn equalize(storages: &HashMap<Entity, ManagedStorage>, entities: &[Entity]) -> Vec<Transfer> {
+    let mut transfers = vec![];
+
+    let combinations: Vec<_> = if entities.len() == 2 {
+        vec![entities.to_vec()]
+    } else {
+        Combinations::new(entities.to_vec(), 2).collect()
+    };
+
+    for ab in combinations {
+        let a = &storages.get(&ab[0]).unwrap().storage;
+        let b = &storages.get(&ab[1]).unwrap().storage;
+
+        let difference = (a.amount - b.amount).abs();
+
+        if difference > 0.0 {
+            let lesser = if a.amount < b.amount { ab[0] } else { ab[1] };
+            let larger = if a.amount >= b.amount { ab[0] } else { ab[1] };
+
+            transfers.push((difference / 2.0, larger, lesser));
+        }
+    }
+
+    transfers
+}
+
+// move as much as possible from every source into every destination
+fn fill(
+    storages: &HashMap<Entity, ManagedStorage>,
+    sources: &[Entity],
+    destinations: &[Entity],
+) -> Vec<Transfer> {
+    let mut transfers = vec![];
+
+    for source in sources.iter() {
+        let from = &storages.get(source).unwrap().storage;
+
+        for destination in destinations.iter() {
+            let to = &storages.get(destination).unwrap().storage;
+            let amount = from.amount.min(to.capacity - to.amount);
+
+            if amount > 0.0 {
+                transfers.push((amount, *source, *destination));
+            }
+        }
+    }
+
+    transfers
+}
+
+fn keep_percentage(
+    storages: &HashMap<Entity, ManagedStorage>,
+    targets: &[Entity],
+    others: &[Entity],
+    percentage: f64,
+) -> Vec<Transfer> {
+    let mut transfers = vec![];
+
+    for target in targets.iter() {
+        let storage = &storages.get(target).unwrap().storage;
+        let wanted = storage.capacity * percentage;
+
+        for other in others.iter() {
+            let other_storage = &storages.get(other).unwrap().storage;
+
+            if storage.amount < wanted {
+                let amount = (wanted - storage.amount).min(other_storage.amount);
+                if amount > 0.0 {
+                    transfers.push((amount, *other, *target));
+                }
+            } else if storage.amount > wanted {
+                let amount =
+                    (storage.amount - wanted).min(other_storage.capacity - other_storage.amount);
+                if amount > 0.0 {
+                    transfers.push((amount, *target, *other));
+                }
+            }
+        }
+    }
+
+    transfers
+}
+
+fn transfers_for_resource(
+    management: &StorageManagement,
+    storages: &HashMap<Entity, ManagedStorage>,
+    entities: &[Entity],
+) -> Vec<Transfer> {
+    let with_role = |role: StorageRole| -> Vec<Entity> {
+        entities
+            .iter()
+            .filter(|entity| storages.get(*entity).unwrap().role == role)
+            .cloned()
+            .collect()
+    };
+
+    let targets = with_role(StorageRole::Target);
+    let others: Vec<Entity> = entities
+        .iter()
+        .filter(|entity| storages.get(*entity).unwrap().role != StorageRole::Target)
+        .cloned()
+        .collect();
+
+    match management.mode {
+        StorageManagementMode::Equalize => equalize(storages, entities),
+        StorageManagementMode::FillTargetFirst => {
+            let transfers = fill(storages, &others, &targets);
+
+            if transfers.is_empty() && others.len() >= 2 {
+                equalize(storages, &others)
+            } else {
+                transfers
+            }
+        }
+        StorageManagementMode::KeepPercentage => {
+            keep_percentage(storages, &targets, &others, management.percentage)
+        }
+        StorageManagementMode::InputToOutput => {
+            fill(storages, &with_role(StorageRole::Input), &targets)
+        }
+    }
+}
 
 pub fn storage_management(
-    mut storage_management: Query<
-        &StorageConsolidator,
-        (Without<UnderConstruction>, With<StorageManagement>),
+    storage_management: Query<
+        (&StorageManagement, &StorageConsolidator),
+        Without<UnderConstruction>,
     >,
-    mut storage_query: Query<(Entity, &mut Storage)>,
+    mut storage_query: Query<(Entity, &mut Storage, Option<&Position>)>,
 ) {
-    for consolidator in storage_management.iter_mut() {
+    for (management, consolidator) in storage_management.iter() {
         if consolidator.connected_storage.len() < 2 {
             continue;
         }
@@ -31,55 +170,70 @@ pub fn storage_management(
         let mut storages = HashMap::new();
         let mut storages_by_resource = CollectingHashMap::new();
         for entity in consolidator.connected_storage.iter() {
-            let (entity, storage) = storage_query.get_mut(*entity).unwrap();
-            let storage = storage.clone();
-
-            storages_by_resource.insert(storage.resource.clone(), entity);
-            storages.insert(entity, storage);
-        }
-
-        let mut unequal_distributions = vec![];
+            let (entity, storage, position) = match storage_query.get_mut(*entity) {
+                Ok(result) => result,
+                Err(_) => continue,
+            };
 
-        for (_resource, entities) in storages_by_resource.iter() {
-            if entities.len() < 2 {
+            if storage.is_warehouse() {
                 continue;
             }
 
-            let combinations: Vec<_> = if entities.len() == 2 {
-                vec![entities.clone()]
-            } else {
-                Combinations::new(entities.clone(), 2).collect()
+            let role = match position {
+                Some(position) if management.targets.contains(&position.position) => {
+                    StorageRole::Target
+                }
+                Some(position) if management.inputs.contains(&position.position) => {
+                    StorageRole::Input
+                }
+                _ => StorageRole::Other,
             };
 
-            for ab in combinations {
-                let a = storages.get(&ab[0]).unwrap();
-                let b = storages.get(&ab[1]).unwrap();
+            storages_by_resource.insert(storage.resource.clone(), entity);
+            storages.insert(
+                entity,
+                ManagedStorage {
+                    storage: storage.clone(),
+                    role,
+                },
+            );
+        }
 
-                let difference = (a.amount - b.amount).abs();
+        let mut changed = false;
 
-                if difference > 0.0 {
-                    let lesser = if a.amount < b.amount { ab[0] } else { ab[1] };
-                    let larger = if a.amount >= b.amount { ab[0] } else { ab[1] };
+        for _ in 0..management.transfers_per_tick {
+            let mut transfers = vec![];
 
-                    unequal_distributions.push((difference, lesser, larger));
+            for (_resource, entities) in storages_by_resource.iter() {
+                if entities.len() < 2 {
+                    continue;
                 }
+
+                transfers.extend(transfers_for_resource(management, &storages, entities));
             }
-        }
 
-        unequal_distributions.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+            transfers.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
 
-        if !unequal_distributions.is_empty() {
-            let (diff, lesser, larger) = unequal_distributions[0];
-            let diff = (diff / 2.0).min(MAX_AMOUNT);
+            let (amount, from, to) = match transfers.first() {
+                Some(transfer) => *transfer,
+                None => break,
+            };
+            let amount = amount.min(MAX_AMOUNT);
 
-            {
-                let mut lesser = storage_query.get_mut(lesser).unwrap().1;
-                lesser.amount += diff;
-            }
+            storages.get_mut(&from).unwrap().storage.amount -= amount;
+            storages.get_mut(&to).unwrap().storage.amount += amount;
+            changed = true;
+        }
 
-            {
-                let mut larger = storage_query.get_mut(larger).unwrap().1;
-                larger.amount -= diff;
+        if !changed {
+            continue;
+        }
+
+        for (entity, managed) in storages.into_iter() {
+            if let Ok((_entity, mut storage, _position)) = storage_query.get_mut(entity) {
+                if (storage.amount - managed.storage.amount).abs() > f64::EPSILON {
+                    storage.amount = managed.storage.amount;
+                }
             }
         }
     }
@@ -1,7 +1,11 @@
 use super::*;
 use crate::game::{
-    assets::resource_specifications::{ResourceSpecification, ResourceSpecifications},
-    production::StorageManagement,
+    assets::{
+        resource_specifications::{ResourceSpecification, ResourceSpecifications},
+        Position,
+    },
+    production::{StorageManagement, StorageManagementMode},
+    state_manager::BuildingEntity,
     statistics::Statistics,
     storage::Storage,
 };
@@ -23,10 +27,31 @@ impl TestSetup {
                 resource: resource.to_owned(),
                 amount,
                 capacity: 10.0,
+                ..Default::default()
             })
             .id()
     }
 
+    fn add_storage_at(&mut self, resource: &str, amount: f64, x: u32) -> Entity {
+        let entity = self.add_storage(resource, amount);
+        self.world.entity_mut(entity).insert(Position {
+            position: UVec2::new(x, 0),
+        });
+
+        entity
+    }
+
+    fn add_management(&mut self, management: StorageManagement, connected_storage: Vec<Entity>) {
+        self.world
+            .spawn()
+            .insert(Statistics::default())
+            .insert(management)
+            .insert(StorageConsolidator {
+                connected_storage,
+                ..Default::default()
+            });
+    }
+
     fn get_storage_amount(&self, entity: Entity) -> f64 {
         self.world.get::<Storage>(entity).unwrap().amount
     }
@@ -68,9 +93,13 @@ fn moves_some_resources() {
         .world
         .spawn()
         .insert(Statistics::default())
-        .insert(StorageManagement)
+        .insert(StorageManagement {
+            transfers_per_tick: 1,
+            ..Default::default()
+        })
         .insert(StorageConsolidator {
             connected_storage: vec![coal_storage_id, second_coal_storage_id],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
@@ -93,7 +122,10 @@ fn moves_resource_with_highest_distance() {
         .world
         .spawn()
         .insert(Statistics::default())
-        .insert(StorageManagement)
+        .insert(StorageManagement {
+            transfers_per_tick: 1,
+            ..Default::default()
+        })
         .insert(StorageConsolidator {
             connected_storage: vec![
                 coal_storage_id,
@@ -101,6 +133,7 @@ fn moves_resource_with_highest_distance() {
                 coke_storage_id,
                 second_coke_storage_id,
             ],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
@@ -124,13 +157,17 @@ fn move_with_three() {
         .world
         .spawn()
         .insert(Statistics::default())
-        .insert(StorageManagement)
+        .insert(StorageManagement {
+            transfers_per_tick: 1,
+            ..Default::default()
+        })
         .insert(StorageConsolidator {
             connected_storage: vec![
                 coal_storage_id,
                 second_coal_storage_id,
                 third_coal_storage_id,
             ],
+            ..Default::default()
         });
 
     setup.stage.run(&mut setup.world);
@@ -139,3 +176,124 @@ fn move_with_three() {
     setup.assert_storage_amount(second_coal_storage_id, 95.0);
     setup.assert_storage_amount(third_coal_storage_id, 75.0);
 }
+
+#[test]
+fn several_transfers_per_tick() {
+    let mut setup = TestSetup::new();
+
+    let coal_storage_id = setup.add_storage(COAL, 0.0);
+    let second_coal_storage_id = setup.add_storage(COAL, 100.0);
+
+    setup.add_management(
+        StorageManagement {
+            transfers_per_tick: 3,
+            ..Default::default()
+        },
+        vec![coal_storage_id, second_coal_storage_id],
+    );
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(coal_storage_id, 15.0);
+    setup.assert_storage_amount(second_coal_storage_id, 85.0);
+}
+
+#[test]
+fn fill_target_first() {
+    let mut setup = TestSetup::new();
+
+    let target_id = setup.add_storage_at(COAL, 8.0, 0);
+    let coal_storage_id = setup.add_storage_at(COAL, 0.0, 1);
+    let second_coal_storage_id = setup.add_storage_at(COAL, 6.0, 2);
+
+    setup.add_management(
+        StorageManagement {
+            mode: StorageManagementMode::FillTargetFirst,
+            targets: vec![UVec2::new(0, 0)].into_iter().collect(),
+            transfers_per_tick: 2,
+            ..Default::default()
+        },
+        vec![target_id, coal_storage_id, second_coal_storage_id],
+    );
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(target_id, 10.0);
+    setup.assert_storage_amount(coal_storage_id, 2.0);
+    setup.assert_storage_amount(second_coal_storage_id, 2.0);
+}
+
+#[test]
+fn keep_percentage() {
+    let mut setup = TestSetup::new();
+
+    let target_id = setup.add_storage_at(COAL, 9.0, 0);
+    let coal_storage_id = setup.add_storage_at(COAL, 0.0, 1);
+
+    setup.add_management(
+        StorageManagement {
+            mode: StorageManagementMode::KeepPercentage,
+            targets: vec![UVec2::new(0, 0)].into_iter().collect(),
+            percentage: 0.3,
+            transfers_per_tick: 1,
+            ..Default::default()
+        },
+        vec![target_id, coal_storage_id],
+    );
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(target_id, 4.0);
+    setup.assert_storage_amount(coal_storage_id, 5.0);
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(target_id, 3.0);
+    setup.assert_storage_amount(coal_storage_id, 6.0);
+}
+
+#[test]
+fn input_to_output() {
+    let mut setup = TestSetup::new();
+
+    let output_id = setup.add_storage_at(COAL, 0.0, 0);
+    let input_id = setup.add_storage_at(COAL, 3.0, 1);
+    let untouched_id = setup.add_storage_at(COAL, 9.0, 2);
+
+    setup.add_management(
+        StorageManagement {
+            mode: StorageManagementMode::InputToOutput,
+            targets: vec![UVec2::new(0, 0)].into_iter().collect(),
+            inputs: vec![UVec2::new(1, 0)].into_iter().collect(),
+            ..Default::default()
+        },
+        vec![output_id, input_id, untouched_id],
+    );
+
+    setup.stage.run(&mut setup.world);
+
+    setup.assert_storage_amount(output_id, 3.0);
+    setup.assert_storage_amount(input_id, 0.0);
+    setup.assert_storage_amount(untouched_id, 9.0);
+}
+
+#[test]
+fn loads_legacy_management() {
+    let loaded: BuildingEntity = serde_yaml::from_str("StorageManagement: ~").unwrap();
+    match loaded {
+        BuildingEntity::StorageManagement(management) => {
+            assert_eq!(management.mode, StorageManagementMode::Equalize);
+            assert_eq!(management.transfers_per_tick, 4);
+        }
+        _ => panic!("expected storage management"),
+    }
+
+    let loaded: BuildingEntity =
+        serde_yaml::from_str("StorageManagement:\n  mode: KeepPercentage\n").unwrap();
+    match loaded {
+        BuildingEntity::StorageManagement(management) => {
+            assert_eq!(management.mode, StorageManagementMode::KeepPercentage);
+        }
+        _ => panic!("expected storage management"),
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+const HAPPINESS_CHANGE: f64 = 0.05;
+const GROWTH_RATE: f64 = 0.01;
+const MIN_POPULATION: f64 = 100.0;
+
+use crate::game::{
+    account::{AccountTransaction, TransactionCategory},
+    assets::resource_specifications::ResourceSpecifications,
+    construction::UnderConstruction,
+    market::Market,
+    production::{Town, TownShop},
+    statistics::Statistics,
+    storage::{amount_in_storage, fetch_from_storage, Storage, StorageConsolidator},
+};
+
+pub fn town_shop(
+    mut shop_query: Query<
+        (Entity, &StorageConsolidator, &mut Statistics),
+        (With<TownShop>, Without<UnderConstruction>),
+    >,
+    mut storage_query: Query<&mut Storage>,
+    resources: Res<ResourceSpecifications>,
+    market: Res<Market>,
+    mut town: ResMut<Town>,
+    mut events: EventWriter<AccountTransaction>,
+) {
+    let demand: HashMap<&String, f64> = resources
+        .iter()
+        .filter_map(|(id, resource)| match resource.demand {
+            Some(demand) if demand > 0.0 => Some((id, demand * town.population / 1000.0)),
+            _ => None,
+        })
+        .collect();
+    let mut unmet = demand.clone();
+    let mut served = false;
+
+    // shops take turns serving what the town still demands
+    for (entity, consolidator, mut statistics) in shop_query.iter_mut() {
+        served = true;
+        let mut revenue = 0.0;
+
+        for (id, left) in unmet.iter_mut() {
+            let amount = amount_in_storage(consolidator, &mut storage_query, id).min(*left);
+
+            if amount > 0.0 && fetch_from_storage(consolidator, &mut storage_query, id, amount) {
+                statistics.sold.track(id, amount);
+                revenue += amount * market.export_price(id, &resources);
+                *left -= amount;
+            }
+        }
+
+        if revenue > 0.0 {
+            events.send(AccountTransaction {
+                amount: revenue as i64,
+                category: TransactionCategory::Sales,
+                source: Some(entity),
+            });
+        }
+    }
+
+    // without shops the town is left as it is
+    if !served {
+        return;
+    }
+
+    let mut satisfaction = 0.0;
+    for (id, total) in demand.iter() {
+        satisfaction += (total - unmet[id]) / total;
+    }
+    if !demand.is_empty() {
+        satisfaction /= demand.len() as f64;
+    }
+
+    town.happiness += (satisfaction - town.happiness) * HAPPINESS_CHANGE;
+    town.population =
+        (town.population * (1.0 + (town.happiness - 0.5) * GROWTH_RATE)).max(MIN_POPULATION);
+}
@@ -0,0 +1,157 @@
+use super::*;
+use crate::game::assets::resource_specifications::ResourceSpecification;
+use bevy::app::Events;
+
+const BREAD: &str = "bread";
+const VODKA: &str = "vodka";
+const COAL: &str = "coal";
+
+fn setup() -> (World, SystemStage) {
+    let mut world = World::default();
+
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(
+        BREAD.to_owned(),
+        ResourceSpecification {
+            cost: 20.0,
+            demand: Some(2.0),
+            ..Default::default()
+        },
+    );
+    resources.insert(
+        VODKA.to_owned(),
+        ResourceSpecification {
+            cost: 50.0,
+            demand: Some(1.0),
+            ..Default::default()
+        },
+    );
+    resources.insert(
+        COAL.to_owned(),
+        ResourceSpecification {
+            cost: 5.0,
+            ..Default::default()
+        },
+    );
+    world.insert_resource(resources);
+    world.insert_resource(Market::default());
+    world.insert_resource(Events::<AccountTransaction>::default());
+    world.insert_resource(Town {
+        population: 1000.0,
+        happiness: 0.5,
+    });
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(town_shop.system());
+
+    (world, stage)
+}
+
+fn add_storage(world: &mut World, resource: &str, amount: f64) -> Entity {
+    world
+        .spawn()
+        .insert(Storage {
+            resource: resource.to_owned(),
+            amount,
+            capacity: 10.0,
+            ..Default::default()
+        })
+        .id()
+}
+
+fn add_shop(world: &mut World, connected_storage: Vec<Entity>) -> Entity {
+    world
+        .spawn()
+        .insert(TownShop::default())
+        .insert(StorageConsolidator {
+            connected_storage,
+            ..Default::default()
+        })
+        .insert(Statistics::default())
+        .id()
+}
+
+fn revenue(world: &World) -> i64 {
+    let events = world.get_resource::<Events<AccountTransaction>>().unwrap();
+    let mut reader = events.get_reader();
+
+    reader.iter(events).map(|event| event.amount).sum()
+}
+
+#[test]
+fn sells_demanded_goods() {
+    let (mut world, mut stage) = setup();
+
+    let bread_id = add_storage(&mut world, BREAD, 10.0);
+    let vodka_id = add_storage(&mut world, VODKA, 10.0);
+    let coal_id = add_storage(&mut world, COAL, 10.0);
+    let shop_id = add_shop(&mut world, vec![bread_id, vodka_id, coal_id]);
+
+    stage.run(&mut world);
+
+    assert!((world.get::<Storage>(bread_id).unwrap().amount - 8.0).abs() < f64::EPSILON);
+    assert!((world.get::<Storage>(vodka_id).unwrap().amount - 9.0).abs() < f64::EPSILON);
+    assert!((world.get::<Storage>(coal_id).unwrap().amount - 10.0).abs() < f64::EPSILON);
+    assert_eq!(revenue(&world), 90);
+
+    let town = world.get_resource::<Town>().unwrap();
+    assert!(town.happiness > 0.5);
+    assert!(town.population > 1000.0);
+
+    let statistics = world.get::<Statistics>(shop_id).unwrap();
+    assert!((statistics.sold.get(BREAD) - 2.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn unmet_demand_lowers_happiness() {
+    let (mut world, mut stage) = setup();
+
+    let bread_id = add_storage(&mut world, BREAD, 1.0);
+    add_shop(&mut world, vec![bread_id]);
+
+    stage.run(&mut world);
+
+    assert!((world.get::<Storage>(bread_id).unwrap().amount).abs() < f64::EPSILON);
+    assert_eq!(revenue(&world), 20);
+
+    // a quarter of the demand was met
+    let town = world.get_resource::<Town>().unwrap();
+    assert!((town.happiness - (0.5 - 0.25 * HAPPINESS_CHANGE)).abs() < f64::EPSILON);
+    assert!(town.population < 1000.0);
+}
+
+#[test]
+fn sells_at_market_price() {
+    let (mut world, mut stage) = setup();
+
+    // the bread price is halved by earlier exports
+    world
+        .get_resource_mut::<Market>()
+        .unwrap()
+        .track_export(BREAD, 500.0);
+
+    let bread_id = add_storage(&mut world, BREAD, 10.0);
+    add_shop(&mut world, vec![bread_id]);
+
+    stage.run(&mut world);
+
+    assert_eq!(revenue(&world), 20);
+}
+
+#[test]
+fn shops_share_the_town_demand() {
+    let (mut world, mut stage) = setup();
+
+    let first_bread = add_storage(&mut world, BREAD, 10.0);
+    let second_bread = add_storage(&mut world, BREAD, 10.0);
+    add_shop(&mut world, vec![first_bread]);
+    add_shop(&mut world, vec![second_bread]);
+
+    stage.run(&mut world);
+
+    // a second shop does not double what the town buys
+    let left = world.get::<Storage>(first_bread).unwrap().amount
+        + world.get::<Storage>(second_bread).unwrap().amount;
+    assert!((left - 18.0).abs() < f64::EPSILON);
+    assert_eq!(revenue(&world), 40);
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    account::PurchaseCost,
+    assets::{resource_specifications::ResourceSpecifications, Position, RequiresUpdate},
+    constants::MapTile,
+    construction::UnderConstruction,
+    helper::neighbor_structure::NeighborStructure,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+};
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Rail;
+
+// tiles trains can travel on (rails and train stations)
+pub struct Track;
+
+impl PurchaseCost for Rail {
+    fn price(&self, _resources: &ResourceSpecifications) -> i64 {
+        150
+    }
+}
+
+pub fn update_rails(
+    mut tile_query: Query<
+        (&mut Tile, &Position),
+        (With<Rail>, With<RequiresUpdate>, Without<UnderConstruction>),
+    >,
+    track_query: Query<(), With<Track>>,
+    map_query: MapQuery,
+) {
+    for (mut tile, position) in tile_query.iter_mut() {
+        let neighbors = map_query.get_tile_neighbors(position.position, MAP_ID, BUILDING_LAYER_ID);
+        let is_track = |neighbor: &(IVec2, Option<Entity>)| match neighbor.1 {
+            Some(entity) => track_query.get(entity).is_ok(),
+            None => false,
+        };
+
+        // N, S, W, E, NW, NE, SW, SE.
+        let ns = NeighborStructure {
+            north: is_track(&neighbors[0]),
+            south: is_track(&neighbors[1]),
+            west: is_track(&neighbors[2]),
+            east: is_track(&neighbors[3]),
+        };
+
+        let ns_index: u16 = ns.into();
+        tile.texture_index = ns_index + MapTile::RailTilesOffset as u16;
+        tile.visible = true;
+    }
+}
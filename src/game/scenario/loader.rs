@@ -0,0 +1,122 @@
+use glob::glob;
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, File},
+    io::prelude::*,
+    path::Path,
+};
+
+use crate::game::state_manager::helper::generate_campaign_progress_path;
+
+use super::{Campaign, Scenario, ScenarioManager};
+
+fn read_file(file_name: &str) -> Option<String> {
+    let path = Path::new(file_name);
+    let mut file = match File::open(&path) {
+        Ok(file) => file,
+        Err(why) => {
+            log::error!("Could not read file: {}", why);
+            return None;
+        }
+    };
+
+    let mut content = String::new();
+    let _ = file.read_to_string(&mut content);
+
+    Some(content)
+}
+
+impl ScenarioManager {
+    pub fn load_file(&mut self, file_name: &str) {
+        let content = match read_file(file_name) {
+            Some(content) => content,
+            None => return,
+        };
+
+        let state: Result<Vec<Scenario>, serde_yaml::Error> = serde_yaml::from_str(&content);
+
+        match state {
+            Ok(mut state) => {
+                for scenario in state.iter() {
+                    log::info!("load scenario spec {}", scenario.id);
+                }
+                self.scenarios.append(&mut state);
+            }
+            Err(why) => log::error!("Could not load state: {}", why),
+        }
+    }
+
+    pub fn load_campaign_file(&mut self, file_name: &str) {
+        let content = match read_file(file_name) {
+            Some(content) => content,
+            None => return,
+        };
+
+        let state: Result<Vec<Campaign>, serde_yaml::Error> = serde_yaml::from_str(&content);
+
+        match state {
+            Ok(mut state) => {
+                for campaign in state.iter() {
+                    log::info!("load campaign spec {}", campaign.id);
+                }
+                self.campaigns.append(&mut state);
+            }
+            Err(why) => log::error!("Could not load state: {}", why),
+        }
+    }
+
+    pub fn load_specifications(&mut self) {
+        self.scenarios = vec![];
+        for file in glob("assets/scenarios/**/*.yml").expect("Failed to read files") {
+            self.load_file(&format!("{}", file.unwrap().display()));
+        }
+
+        self.campaigns = vec![];
+        for file in glob("assets/campaigns/**/*.yml").expect("Failed to read files") {
+            self.load_campaign_file(&format!("{}", file.unwrap().display()));
+        }
+    }
+
+    pub fn load_progress(&mut self) {
+        let file_name = generate_campaign_progress_path();
+        if !Path::new(&file_name).exists() {
+            return;
+        }
+
+        if let Some(content) = read_file(&file_name) {
+            let state: Result<HashMap<String, usize>, serde_yaml::Error> =
+                serde_yaml::from_str(&content);
+
+            match state {
+                Ok(state) => self.unlocked = state,
+                Err(why) => log::error!("Could not load campaign progress: {}", why),
+            }
+        }
+    }
+
+    pub fn save_progress(&self) {
+        let file_name = generate_campaign_progress_path();
+        let path = Path::new(&file_name);
+
+        if let Some(parent) = path.parent() {
+            let _ = create_dir_all(parent);
+        }
+
+        let content = match serde_yaml::to_string(&self.unlocked) {
+            Ok(content) => content,
+            Err(why) => {
+                log::error!("Could not serialize campaign progress: {}", why);
+                return;
+            }
+        };
+
+        match File::create(&path) {
+            Ok(mut file) => {
+                if let Err(why) = file.write_all(content.as_bytes()) {
+                    log::error!("Could not write file: {}", why);
+                }
+            }
+            Err(why) => log::error!("Could not create file: {}", why),
+        }
+    }
+}
@@ -0,0 +1,322 @@
+mod loader;
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use num_format::{Locale, ToFormattedString};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    account::Account,
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications, Building, MapSettings, MapSize,
+    },
+    constants::{CURRENCY, UNIT},
+    construction::UnderConstruction,
+    goals::GoalManager,
+    production::Workforce,
+    statistics::{StatisticTracker, Statistics},
+    time::GameTime,
+};
+
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum ScenarioObjective {
+    Export { resource: String, amount: f64 },
+    Produce { resource: String, amount: f64 },
+    Balance { amount: i64 },
+    Build { building: String, count: usize },
+}
+
+impl ScenarioObjective {
+    pub fn target(&self) -> f64 {
+        match self {
+            ScenarioObjective::Export { amount, .. } => *amount,
+            ScenarioObjective::Produce { amount, .. } => *amount,
+            ScenarioObjective::Balance { amount } => *amount as f64,
+            ScenarioObjective::Build { count, .. } => *count as f64,
+        }
+    }
+
+    pub fn description(
+        &self,
+        resources: &ResourceSpecifications,
+        buildings: &BuildingSpecifications,
+    ) -> String {
+        let resource_name = |id: &String| {
+            resources
+                .get(id)
+                .map(|resource| resource.name.clone())
+                .unwrap_or_else(|| id.clone())
+        };
+
+        match self {
+            ScenarioObjective::Export { resource, amount } => format!(
+                "Export {}{} {}",
+                (*amount as i64).to_formatted_string(&Locale::en),
+                UNIT,
+                resource_name(resource)
+            ),
+            ScenarioObjective::Produce { resource, amount } => format!(
+                "Produce {}{} {}",
+                (*amount as i64).to_formatted_string(&Locale::en),
+                UNIT,
+                resource_name(resource)
+            ),
+            ScenarioObjective::Balance { amount } => format!(
+                "Have {} {}",
+                amount.to_formatted_string(&Locale::en),
+                CURRENCY
+            ),
+            ScenarioObjective::Build { building, count } => format!(
+                "Build {}x {}",
+                count,
+                buildings
+                    .get(building)
+                    .map(|building| building.name.clone())
+                    .unwrap_or_else(|| building.clone())
+            ),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum ScenarioCondition {
+    // lost when the balance drops below the amount
+    Balance { amount: i64 },
+    // lost when the objectives are not reached in time
+    Days { days: f64 },
+}
+
+impl ScenarioCondition {
+    pub fn description(&self) -> String {
+        match self {
+            ScenarioCondition::Balance { amount } => format!(
+                "Balance below {} {}",
+                amount.to_formatted_string(&Locale::en),
+                CURRENCY
+            ),
+            ScenarioCondition::Days { days } => format!("More than {:.0} days", days),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub map: MapSize,
+    pub money: i64,
+    // workers of the starting town, the default one when missing
+    #[serde(default)]
+    pub workforce: Option<u32>,
+    // all buildings are allowed when missing
+    #[serde(default)]
+    pub buildings: Option<Vec<String>>,
+    pub objectives: Vec<ScenarioObjective>,
+    #[serde(default)]
+    pub lose: Vec<ScenarioCondition>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Campaign {
+    pub id: String,
+    pub name: String,
+    pub scenarios: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScenarioState {
+    Running,
+    Won,
+    Lost,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ActiveScenario {
+    pub scenario: String,
+    pub campaign: Option<String>,
+    pub objective: usize,
+    pub current: f64,
+    pub state: ScenarioState,
+}
+
+pub struct ScenarioManager {
+    pub scenarios: Vec<Scenario>,
+    pub campaigns: Vec<Campaign>,
+    pub active: Option<ActiveScenario>,
+    // number of finished scenarios per campaign
+    pub unlocked: HashMap<String, usize>,
+    // open the campaign screen when returning to the main menu
+    pub show_campaign: bool,
+}
+
+impl Default for ScenarioManager {
+    fn default() -> Self {
+        let mut new = Self {
+            scenarios: vec![],
+            campaigns: vec![],
+            active: None,
+            unlocked: HashMap::new(),
+            show_campaign: false,
+        };
+        new.load_specifications();
+        new.load_progress();
+        new
+    }
+}
+
+impl ScenarioManager {
+    pub fn get(&self, id: &str) -> Option<&Scenario> {
+        self.scenarios.iter().find(|scenario| scenario.id == id)
+    }
+
+    pub fn current(&self) -> Option<&Scenario> {
+        self.active
+            .as_ref()
+            .and_then(|active| self.get(&active.scenario))
+    }
+
+    pub fn start(&mut self, id: &str, campaign: Option<&str>) -> Option<MapSettings> {
+        let map = self.get(id)?.map.clone();
+
+        self.active = Some(ActiveScenario {
+            scenario: id.to_owned(),
+            campaign: campaign.map(|campaign| campaign.to_owned()),
+            objective: 0,
+            current: 0.0,
+            state: ScenarioState::Running,
+        });
+
+        Some(MapSettings::from_size(map))
+    }
+
+    pub fn is_unlocked(&self, campaign: &Campaign, index: usize) -> bool {
+        index <= *self.unlocked.get(&campaign.id).unwrap_or(&0)
+    }
+
+    fn campaign_position(&self) -> Option<(&Campaign, usize)> {
+        let active = self.active.as_ref()?;
+        let campaign_id = active.campaign.as_ref()?;
+        let campaign = self
+            .campaigns
+            .iter()
+            .find(|campaign| &campaign.id == campaign_id)?;
+        let index = campaign
+            .scenarios
+            .iter()
+            .position(|scenario| scenario == &active.scenario)?;
+
+        Some((campaign, index))
+    }
+
+    // the scenario following the active one in its campaign
+    pub fn next(&self) -> Option<(String, String)> {
+        let (campaign, index) = self.campaign_position()?;
+
+        campaign
+            .scenarios
+            .get(index + 1)
+            .map(|scenario| (scenario.clone(), campaign.id.clone()))
+    }
+
+    pub fn allows_building(&self, id: &str) -> bool {
+        match self
+            .current()
+            .and_then(|scenario| scenario.buildings.as_ref())
+        {
+            Some(buildings) => buildings.iter().any(|building| building == id),
+            None => true,
+        }
+    }
+
+    fn complete_campaign_scenario(&mut self) {
+        let completed = self
+            .campaign_position()
+            .map(|(campaign, index)| (campaign.id.clone(), index + 1));
+
+        if let Some((campaign, count)) = completed {
+            let unlocked = self.unlocked.entry(campaign).or_insert(0);
+            *unlocked = (*unlocked).max(count);
+            self.save_progress();
+        }
+    }
+}
+
+pub fn start_scenario(
+    manager: Res<ScenarioManager>,
+    mut account: ResMut<Account>,
+    mut goals: ResMut<GoalManager>,
+    mut workforce: ResMut<Workforce>,
+) {
+    if let Some(scenario) = manager.current() {
+        account.value = scenario.money;
+        goals.goals.clear();
+
+        if let Some(workers) = scenario.workforce {
+            workforce.base = Some(workers);
+        }
+    }
+}
+
+pub fn update_scenario(
+    query: Query<&Statistics>,
+    building_query: Query<&Building, Without<UnderConstruction>>,
+    deleted_export_statistics: Res<StatisticTracker>,
+    account: Res<Account>,
+    time: Res<GameTime>,
+    mut manager: ResMut<ScenarioManager>,
+) {
+    let scenario = match manager.current() {
+        Some(scenario) => scenario.clone(),
+        None => return,
+    };
+
+    let active = manager.active.as_mut().unwrap();
+    if active.state != ScenarioState::Running {
+        return;
+    }
+
+    while let Some(objective) = scenario.objectives.get(active.objective) {
+        active.current = match objective {
+            ScenarioObjective::Export { resource, .. } => query.iter().fold(
+                deleted_export_statistics.get(resource),
+                |acc, statistics| acc + statistics.export.get(resource),
+            ),
+            ScenarioObjective::Produce { resource, .. } => query
+                .iter()
+                .map(|statistics| statistics.production.get(resource))
+                .sum(),
+            ScenarioObjective::Balance { .. } => account.value as f64,
+            ScenarioObjective::Build { building, .. } => building_query
+                .iter()
+                .filter(|placed| &placed.id == building)
+                .count() as f64,
+        };
+
+        if active.current < objective.target() {
+            break;
+        }
+
+        active.objective += 1;
+    }
+
+    let day = time.day();
+    if active.objective >= scenario.objectives.len() {
+        active.state = ScenarioState::Won;
+        manager.complete_campaign_scenario();
+    } else if scenario.lose.iter().any(|condition| match condition {
+        ScenarioCondition::Balance { amount } => account.value < *amount,
+        ScenarioCondition::Days { days } => day > *days,
+    }) {
+        active.state = ScenarioState::Lost;
+    }
+}
@@ -0,0 +1,138 @@
+use super::*;
+use crate::game::{constants::DAY_LENGTH, goals::Goal};
+
+fn scenario() -> Scenario {
+    Scenario {
+        id: "test".to_owned(),
+        name: "Test".to_owned(),
+        description: "".to_owned(),
+        map: MapSize::Small,
+        money: 1000,
+        workforce: Some(60),
+        buildings: Some(vec!["field".to_owned()]),
+        objectives: vec![
+            ScenarioObjective::Export {
+                resource: "coke".to_owned(),
+                amount: 10.0,
+            },
+            ScenarioObjective::Balance { amount: 2000 },
+        ],
+        lose: vec![ScenarioCondition::Days { days: 5.0 }],
+    }
+}
+
+fn manager() -> ScenarioManager {
+    let mut manager = ScenarioManager {
+        scenarios: vec![scenario()],
+        campaigns: vec![],
+        active: None,
+        unlocked: HashMap::new(),
+        show_campaign: false,
+    };
+    manager.start("test", None);
+
+    manager
+}
+
+fn setup() -> (World, SystemStage) {
+    let mut world = World::default();
+
+    world.insert_resource(manager());
+    world.insert_resource(Account::default());
+    world.insert_resource(StatisticTracker::default());
+    world.insert_resource(GameTime::default());
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(update_scenario.system());
+
+    (world, stage)
+}
+
+fn active(world: &World) -> ActiveScenario {
+    world
+        .get_resource::<ScenarioManager>()
+        .unwrap()
+        .active
+        .clone()
+        .unwrap()
+}
+
+#[test]
+fn specifications_load() {
+    let manager = ScenarioManager::default();
+
+    assert!(!manager.scenarios.is_empty());
+    assert!(!manager.campaigns.is_empty());
+
+    for campaign in manager.campaigns.iter() {
+        for scenario in campaign.scenarios.iter() {
+            assert!(manager.get(scenario).is_some());
+        }
+    }
+}
+
+#[test]
+fn objectives_complete_in_order() {
+    let (mut world, mut stage) = setup();
+
+    // the balance is reached, but the export comes first
+    world.get_resource_mut::<Account>().unwrap().value = 5000;
+    stage.run(&mut world);
+
+    assert_eq!(active(&world).objective, 0);
+    assert_eq!(active(&world).state, ScenarioState::Running);
+
+    let mut statistics = Statistics::default();
+    statistics.export.track("coke", 10.0);
+    world.spawn().insert(statistics);
+
+    stage.run(&mut world);
+
+    assert_eq!(active(&world).objective, 2);
+    assert_eq!(active(&world).state, ScenarioState::Won);
+}
+
+#[test]
+fn scenario_lost_after_deadline() {
+    let (mut world, mut stage) = setup();
+
+    world.get_resource_mut::<GameTime>().unwrap().elapsed = 6.0 * DAY_LENGTH;
+    stage.run(&mut world);
+
+    assert_eq!(active(&world).state, ScenarioState::Lost);
+}
+
+#[test]
+fn start_applies_scenario_settings() {
+    let mut world = World::default();
+
+    let mut goals = GoalManager::default();
+    goals.goals.insert(
+        "coke".to_owned(),
+        Goal {
+            amount: 10.0,
+            current: 0.0,
+        },
+    );
+
+    world.insert_resource(manager());
+    world.insert_resource(Account { value: 250000 });
+    world.insert_resource(goals);
+    world.insert_resource(Workforce::default());
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(start_scenario.system());
+    stage.run(&mut world);
+
+    assert_eq!(world.get_resource::<Account>().unwrap().value, 1000);
+    assert_eq!(world.get_resource::<Workforce>().unwrap().base, Some(60));
+    assert!(world
+        .get_resource::<GoalManager>()
+        .unwrap()
+        .goals
+        .is_empty());
+
+    let manager = world.get_resource::<ScenarioManager>().unwrap();
+    assert!(manager.allows_building("field"));
+    assert!(!manager.allows_building("bakery"));
+}
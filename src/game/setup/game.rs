@@ -6,6 +6,7 @@ use crate::game::{
     car::Car,
     constants::{MapTile, CHUNK_SIZE, TILE_MAP_HEIGHT, TILE_MAP_WIDTH, TILE_SIZE},
     current_tool::SelectedTool,
+    train::Train,
     ui::state::MainMenuState,
 };
 
@@ -15,6 +16,7 @@ pub fn teardown(
     mut commands: Commands,
     mut map_query: MapQuery,
     car_query: Query<Entity, With<Car>>,
+    train_query: Query<Entity, With<Train>>,
     sprite_query: Query<Entity, With<TextureAtlasSprite>>,
     mut menu_state: ResMut<State<MainMenuState>>,
 ) {
@@ -28,6 +30,10 @@ pub fn teardown(
         commands.entity(entity).despawn_recursive();
     }
 
+    for entity in train_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
     let _ = menu_state.set(MainMenuState::Main);
 }
 
@@ -9,7 +9,9 @@ use crate::game::{
         MapSettings, Occupied, Position, RequiresUpdate,
     },
     constants::CHUNK_SIZE,
+    construction::ConstructionSpent,
     helper::get_entity::get_entity,
+    history::Unrecorded,
     pathfinder::{cost_fn, Pathfinding},
     setup::{BUILDING_LAYER_ID, MAP_ID},
     street::{Street, StreetType},
@@ -102,7 +104,10 @@ pub fn generate_street(
                             .insert(MaintenanceCost::new_from_cost(price))
                             .insert(Position { position: pos })
                             .insert(CanDriveOver)
-                            .insert(Occupied);
+                            .insert(Occupied)
+                            .insert(Unrecorded)
+                            // generated for free, bulldozing refunds nothing
+                            .insert(ConstructionSpent(0.0));
 
                         map_query.notify_chunk_for_tile(pos, MAP_ID, BUILDING_LAYER_ID);
                     }
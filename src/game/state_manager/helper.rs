@@ -20,3 +20,23 @@ pub fn generate_save_game_path() -> String {
 
     save_game_path.to_str().unwrap().to_string()
 }
+
+pub fn generate_campaign_progress_path() -> String {
+    let proj_dirs = ProjectDirs::from(QUALIFIER, ORGANIZATION, APPLICATION)
+        .expect("Could not find project directories");
+
+    let progress_path = proj_dirs.config_dir().join("campaigns.yml");
+
+    progress_path.to_str().unwrap().to_string()
+}
+
+pub fn generate_blueprint_path() -> String {
+    let proj_dirs = ProjectDirs::from(QUALIFIER, ORGANIZATION, APPLICATION)
+        .expect("Could not find project directories");
+
+    let blueprint_path = proj_dirs.config_dir().join("blueprints");
+
+    create_dir_all(&blueprint_path).expect("Could not create blueprint path.");
+
+    blueprint_path.to_str().unwrap().to_string()
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
@@ -8,17 +8,29 @@ use crate::game::{
     assets::{
         building_specifications::BuildingSpecifications,
         resource_specifications::ResourceSpecifications, BlockedForBuilding, Building,
-        CanDriveOver, Editable, Forest, Occupied, Position, RequiresUpdate, StateName, Water,
+        CanDriveOver, Editable, Footprint, Forest, Occupied, Position, RequiresUpdate, StateName,
+        Water,
     },
+    bank::Bank,
     car::{Car, CarController, DepotController},
+    construction::{ConstructionSettings, ConstructionSpent, UnderConstruction},
+    contracts::ContractManager,
     goals::GoalManager,
-    production::{Product, ProductionBuilding},
+    helper::footprint::spawn_parts,
+    history::Unrecorded,
+    market::Market,
+    outcome::GameOutcome,
+    production::{Housing, Product, ProductionBuilding, Town, Workforce},
+    rail::Track,
+    scenario::ScenarioManager,
     setup::{BUILDING_LAYER_ID, GROUND_LAYER_ID, MAP_ID},
     state_manager::{
         BuildingEntity, GameEntity, GameEntityType, GameState, LoadGameEvent, Vehicle,
     },
-    statistics::StatisticTracker,
+    statistics::{StatisticTracker, Statistics},
     storage::StorageConsolidator,
+    time::GameTime,
+    train::Train,
 };
 
 use super::VehicleController;
@@ -29,14 +41,36 @@ pub fn load_game(
     buildings: Res<BuildingSpecifications>,
     mut load_game: EventReader<LoadGameEvent>,
     mut goals: ResMut<GoalManager>,
-    mut account: ResMut<Account>,
+    economy: (
+        ResMut<Account>,
+        ResMut<Market>,
+        ResMut<Bank>,
+        ResMut<Workforce>,
+        ResMut<Town>,
+    ),
+    mut time: ResMut<GameTime>,
+    mut contracts: ResMut<ContractManager>,
+    mut scenarios: ResMut<ScenarioManager>,
+    mut outcome: ResMut<GameOutcome>,
+    mut construction: ResMut<ConstructionSettings>,
     mut state_name: ResMut<StateName>,
     mut deleted_export_statistics: ResMut<StatisticTracker>,
     resources: Res<ResourceSpecifications>,
 ) {
+    let (mut account, mut market, mut bank, mut workforce, mut town) = economy;
+
     for event in load_game.iter() {
         goals.goals = event.state.goals.clone();
         *account = event.state.account.clone();
+        *market = event.state.market.clone();
+        *bank = event.state.bank.clone();
+        *workforce = event.state.workforce.clone();
+        *town = event.state.town.clone();
+        *time = event.state.time.clone();
+        *contracts = event.state.contracts.clone();
+        scenarios.active = event.state.scenario.clone();
+        *outcome = event.state.outcome.clone();
+        *construction = event.state.construction.clone();
         *state_name = event.state.state_name.clone();
         *deleted_export_statistics = event.state.deleted_export_statistics.clone();
 
@@ -59,11 +93,27 @@ fn load_state(
 ) {
     let mut uuids = HashMap::new();
 
+    // footprints overlapping tiles claimed by other saved entities stay a single tile
+    let mut claimed = state
+        .entities
+        .iter()
+        .filter(|game_entity| {
+            matches!(
+                game_entity.entity,
+                GameEntityType::Building(_) | GameEntityType::Water | GameEntityType::Forest
+            )
+        })
+        .map(|game_entity| game_entity.pos)
+        .collect::<HashSet<_>>();
+
     for game_entity in &state.entities {
         match &game_entity.entity {
             GameEntityType::Vehicle(vehicle) => {
                 insert_car(commands, vehicle, game_entity, resources, &uuids);
             }
+            GameEntityType::Train(train) => {
+                insert_train(commands, train, game_entity, resources);
+            }
             GameEntityType::Building(building) => {
                 let entity = insert_building(
                     commands,
@@ -72,9 +122,12 @@ fn load_state(
                     map_query,
                     buildings,
                     resources,
+                    Some(&mut claimed),
                 );
 
                 if let Some(entity) = entity {
+                    commands.entity(entity).insert(Unrecorded);
+
                     let uuid = game_entity.uuid.to_owned();
                     uuids.insert(uuid, entity);
                 }
@@ -145,7 +198,10 @@ fn insert_car(
         controller,
     };
 
-    let price = (car.clone(), vehicle.storage.clone()).price(resources);
+    let mut storage = vehicle.storage.clone();
+    storage.migrate_tank(resources);
+
+    let price = (car.clone(), storage.clone()).price(resources);
 
     let entity = commands
         .spawn()
@@ -154,7 +210,34 @@ fn insert_car(
             position: game_entity.pos,
         })
         .insert(car)
-        .insert(vehicle.storage.clone())
+        .insert(storage)
+        .insert(Editable)
+        .insert(MaintenanceCost::new_from_cost(price))
+        .id();
+
+    if let Some(name) = &game_entity.name {
+        commands.entity(entity).insert(name.clone());
+    }
+}
+
+fn insert_train(
+    commands: &mut Commands,
+    train: &Train,
+    game_entity: &GameEntity,
+    resources: &ResourceSpecifications,
+) {
+    let mut train = train.clone();
+    for wagon in train.wagons.iter_mut() {
+        wagon.migrate_tank(resources);
+    }
+
+    let price = train.price(resources);
+    let entity = commands
+        .spawn()
+        .insert(Position {
+            position: game_entity.pos,
+        })
+        .insert(train)
         .insert(Editable)
         .insert(MaintenanceCost::new_from_cost(price))
         .id();
@@ -164,6 +247,81 @@ fn insert_car(
     }
 }
 
+// spawns a building outside of loading a save, e.g. from a blueprint or an undone edit
+pub fn spawn_building(
+    commands: &mut Commands,
+    map_query: &mut MapQuery,
+    pos: UVec2,
+    building: &BuildingEntity,
+    under_construction: Option<UnderConstruction>,
+    buildings: &BuildingSpecifications,
+    resources: &ResourceSpecifications,
+) -> Option<Entity> {
+    let statistics = match building {
+        BuildingEntity::Building(_)
+        | BuildingEntity::Storage(_)
+        | BuildingEntity::ImportExportStation(_)
+        | BuildingEntity::TownShop(_) => Some(Statistics::default()),
+        _ => None,
+    };
+
+    let game_entity = GameEntity {
+        uuid: "".to_owned(),
+        pos,
+        entity: GameEntityType::Building(building.clone()),
+        name: None,
+        statistics,
+        under_construction,
+        spent: None,
+    };
+
+    insert_building(
+        commands,
+        building,
+        &game_entity,
+        map_query,
+        buildings,
+        resources,
+        None,
+    )
+}
+
+// buildings from older saves may have grown a footprint over their neighbors or the map edge
+fn claim_footprint(
+    claimed: Option<&mut HashSet<UVec2>>,
+    map_query: &MapQuery,
+    origin: UVec2,
+    footprint: &Footprint,
+) -> bool {
+    let claimed = match claimed {
+        Some(claimed) => claimed,
+        // placed in game, the footprint was checked before
+        None => return true,
+    };
+
+    let (_entity, layer) = map_query.get_layer(MAP_ID, BUILDING_LAYER_ID).unwrap();
+    let size = layer.get_layer_size_in_tiles();
+
+    let parts = footprint
+        .positions(origin)
+        .into_iter()
+        .skip(1)
+        .collect::<Vec<_>>();
+    if parts
+        .iter()
+        .any(|pos| pos.x >= size.x || pos.y >= size.y || claimed.contains(pos))
+    {
+        log::warn!(
+            "No room for footprint at {:?}, keeping a single tile",
+            origin
+        );
+        return false;
+    }
+
+    claimed.extend(parts);
+    true
+}
+
 fn insert_building(
     commands: &mut Commands,
     building: &BuildingEntity,
@@ -171,6 +329,7 @@ fn insert_building(
     map_query: &mut MapQuery,
     buildings: &BuildingSpecifications,
     resources: &ResourceSpecifications,
+    claimed: Option<&mut HashSet<UVec2>>,
 ) -> Option<Entity> {
     let tile = Tile {
         visible: false,
@@ -201,12 +360,18 @@ fn insert_building(
                 commands.entity(entity).insert(under_construction.clone());
             }
 
+            if let Some(spent) = game_entity.spent {
+                commands.entity(entity).insert(ConstructionSpent(spent));
+            }
+
             match building {
                 BuildingEntity::Building(c) => {
                     let building = buildings.get(&c.id).unwrap();
-                    commands
-                        .entity(entity)
-                        .insert(Building { id: c.id.clone() });
+                    commands.entity(entity).insert(Building {
+                        id: c.id.clone(),
+                        rotation: c.rotation,
+                        any_side: c.any_side,
+                    });
 
                     if !building.products.is_empty() {
                         let products = building
@@ -224,10 +389,36 @@ fn insert_building(
                         commands
                             .entity(entity)
                             .insert(StorageConsolidator::default())
-                            .insert(ProductionBuilding { products })
-                            .insert(MaintenanceCost::new_from_cost(building.price(resources)))
+                            .insert(ProductionBuilding {
+                                products,
+                                workers: building.workers,
+                                mothballed: c.mothballed,
+                            })
+                            .insert(MaintenanceCost::new_from_specification(
+                                building,
+                                building.price(resources),
+                            ))
                             .insert(Editable);
                     }
+
+                    if let Some(housing) = &building.housing {
+                        commands
+                            .entity(entity)
+                            .insert(StorageConsolidator::default())
+                            .insert(Housing::from(housing))
+                            .insert(MaintenanceCost::new_from_specification(
+                                building,
+                                building.price(resources),
+                            ));
+                    }
+
+                    let footprint = building.rotated_footprint(c.rotation).filter(|footprint| {
+                        claim_footprint(claimed, map_query, game_entity.pos, footprint)
+                    });
+                    if let Some(footprint) = footprint {
+                        commands.entity(entity).insert(footprint);
+                        spawn_parts(commands, map_query, entity, game_entity.pos, &footprint);
+                    }
                 }
                 BuildingEntity::Street(c) => {
                     commands
@@ -236,12 +427,52 @@ fn insert_building(
                         .insert(CanDriveOver)
                         .insert(MaintenanceCost::new_from_cost(c.price(resources)));
                 }
-                BuildingEntity::Storage(c) => {
+                BuildingEntity::Rail(c) => {
+                    commands
+                        .entity(entity)
+                        .insert(c.clone())
+                        .insert(Track)
+                        .insert(MaintenanceCost::new_from_cost(c.price(resources)));
+                }
+                BuildingEntity::TrainStation(c) => {
+                    commands
+                        .entity(entity)
+                        .insert(c.clone())
+                        .insert(Track)
+                        .insert(MaintenanceCost::new_from_cost(c.price(resources)))
+                        .insert(StorageConsolidator::default());
+                }
+                BuildingEntity::Conveyor(c) => {
+                    commands
+                        .entity(entity)
+                        .insert(c.clone())
+                        .insert(Editable)
+                        .insert(MaintenanceCost::new_from_cost(c.price(resources)));
+                }
+                BuildingEntity::Pipeline(c) => {
                     commands
                         .entity(entity)
                         .insert(c.clone())
                         .insert(MaintenanceCost::new_from_cost(c.price(resources)));
                 }
+                BuildingEntity::Storage(c) => {
+                    let mut c = c.clone();
+                    c.migrate_tank(resources);
+
+                    commands
+                        .entity(entity)
+                        .insert(c.clone())
+                        .insert(MaintenanceCost::new_from_cost(c.price(resources)))
+                        .insert(Editable);
+
+                    let footprint = c.footprint().filter(|footprint| {
+                        claim_footprint(claimed, map_query, game_entity.pos, footprint)
+                    });
+                    if let Some(footprint) = footprint {
+                        commands.entity(entity).insert(footprint);
+                        spawn_parts(commands, map_query, entity, game_entity.pos, &footprint);
+                    }
+                }
                 BuildingEntity::Depot(c) => {
                     commands
                         .entity(entity)
@@ -257,11 +488,19 @@ fn insert_building(
                         .insert(CanDriveOver)
                         .insert(StorageConsolidator::default());
                 }
+                BuildingEntity::TownShop(c) => {
+                    commands
+                        .entity(entity)
+                        .insert(c.clone())
+                        .insert(MaintenanceCost::new_from_cost(c.price(resources)))
+                        .insert(StorageConsolidator::default());
+                }
                 BuildingEntity::StorageManagement(c) => {
                     commands
                         .entity(entity)
                         .insert(c.clone())
                         .insert(MaintenanceCost::new_from_cost(c.price(resources)))
+                        .insert(Editable)
                         .insert(StorageConsolidator::default());
                 }
                 BuildingEntity::ImportExportStation(c) => {
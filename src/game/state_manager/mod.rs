@@ -9,14 +9,27 @@ use serde::{Deserialize, Serialize};
 
 use crate::game::{
     account::Account,
-    assets::{Direction, MapSettings, Name, StateName},
+    assets::{Building, Direction, MapSettings, Name, StateName},
+    bank::Bank,
     car::UserController,
-    construction::UnderConstruction,
+    construction::{ConstructionSettings, UnderConstruction},
+    contracts::ContractManager,
+    conveyor::Conveyor,
     goals::Goal,
-    production::{DeliveryStation, Depot, ImportExportStation, StorageManagement},
+    market::Market,
+    outcome::GameOutcome,
+    pipeline::Pipeline,
+    production::{
+        DeliveryStation, Depot, ImportExportStation, ProductionBuilding, StorageManagement, Town,
+        TownShop, TrainStation, Workforce,
+    },
+    rail::Rail,
+    scenario::ActiveScenario,
     statistics::{StatisticTracker, Statistics},
     storage::Storage,
     street::Street,
+    time::GameTime,
+    train::Train,
 };
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -24,17 +37,109 @@ use crate::game::{
 pub struct SerializedBuilding {
     pub id: String,
     pub active_products: Vec<bool>,
+    #[serde(default)]
+    pub rotation: u8,
+    // saves without this field keep their layouts wired on every side
+    #[serde(default = "SerializedBuilding::saved_before_ports")]
+    pub any_side: bool,
+    #[serde(default)]
+    pub mothballed: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+impl SerializedBuilding {
+    fn saved_before_ports() -> bool {
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub enum BuildingEntity {
     Storage(Storage),
     ImportExportStation(ImportExportStation),
     DeliveryStation(DeliveryStation),
+    TownShop(TownShop),
+    #[serde(deserialize_with = "StorageManagement::deserialize_legacy")]
     StorageManagement(StorageManagement),
     Depot(Depot),
     Street(Street),
     Building(SerializedBuilding),
+    Rail(Rail),
+    TrainStation(TrainStation),
+    Conveyor(Conveyor),
+    Pipeline(Pipeline),
+}
+
+// read access to every component a building can be serialized from
+pub type BuildingQueries<'a> = (
+    Query<'a, (&'static Building, Option<&'static ProductionBuilding>)>,
+    Query<'a, &'static Storage>,
+    Query<'a, &'static ImportExportStation>,
+    Query<'a, &'static DeliveryStation>,
+    Query<'a, &'static StorageManagement>,
+    Query<'a, &'static Depot>,
+    Query<'a, &'static Street>,
+    Query<'a, &'static Rail>,
+    Query<'a, &'static TrainStation>,
+    Query<'a, &'static Conveyor>,
+    Query<'a, &'static Pipeline>,
+    Query<'a, &'static TownShop>,
+);
+
+pub fn serialize_building(entity: Entity, queries: &BuildingQueries) -> Option<BuildingEntity> {
+    let (
+        building_query,
+        storage_query,
+        import_export_station_query,
+        delivery_station_query,
+        storage_management_query,
+        depot_query,
+        street_query,
+        rail_query,
+        train_station_query,
+        conveyor_query,
+        pipeline_query,
+        town_shop_query,
+    ) = queries;
+
+    let building = if let Ok((building, production_building)) = building_query.get(entity) {
+        BuildingEntity::Building(SerializedBuilding {
+            id: building.id.clone(),
+            active_products: production_building
+                .map(|pb| pb.products.iter().map(|(_, active)| *active).collect())
+                .unwrap_or_default(),
+            rotation: building.rotation,
+            any_side: building.any_side,
+            mothballed: production_building
+                .map(|pb| pb.mothballed)
+                .unwrap_or_default(),
+        })
+    } else if let Ok(c) = storage_query.get(entity) {
+        BuildingEntity::Storage(c.clone())
+    } else if let Ok(c) = import_export_station_query.get(entity) {
+        BuildingEntity::ImportExportStation(c.clone())
+    } else if let Ok(c) = delivery_station_query.get(entity) {
+        BuildingEntity::DeliveryStation(c.clone())
+    } else if let Ok(c) = storage_management_query.get(entity) {
+        BuildingEntity::StorageManagement(c.clone())
+    } else if let Ok(c) = depot_query.get(entity) {
+        BuildingEntity::Depot(c.clone())
+    } else if let Ok(c) = street_query.get(entity) {
+        BuildingEntity::Street(c.clone())
+    } else if let Ok(c) = rail_query.get(entity) {
+        BuildingEntity::Rail(c.clone())
+    } else if let Ok(c) = train_station_query.get(entity) {
+        BuildingEntity::TrainStation(c.clone())
+    } else if let Ok(c) = conveyor_query.get(entity) {
+        BuildingEntity::Conveyor(c.clone())
+    } else if let Ok(c) = pipeline_query.get(entity) {
+        BuildingEntity::Pipeline(c.clone())
+    } else if let Ok(c) = town_shop_query.get(entity) {
+        BuildingEntity::TownShop(c.clone())
+    } else {
+        return None;
+    };
+
+    Some(building)
 }
 
 #[derive(Serialize, Deserialize)]
@@ -54,6 +159,7 @@ pub struct Vehicle {
 pub enum GameEntityType {
     Building(BuildingEntity),
     Vehicle(Vehicle),
+    Train(Train),
     Water,
     Forest,
 }
@@ -66,6 +172,8 @@ pub struct GameEntity {
     pub name: Option<Name>,
     pub statistics: Option<Statistics>,
     pub under_construction: Option<UnderConstruction>,
+    #[serde(default)]
+    pub spent: Option<f64>,
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -77,6 +185,24 @@ pub struct GameState {
     pub entities: Vec<GameEntity>,
     pub goals: HashMap<String, Goal>,
     pub account: Account,
+    #[serde(default)]
+    pub market: Market,
+    #[serde(default)]
+    pub bank: Bank,
+    #[serde(default)]
+    pub time: GameTime,
+    #[serde(default)]
+    pub contracts: ContractManager,
+    #[serde(default)]
+    pub scenario: Option<ActiveScenario>,
+    #[serde(default)]
+    pub outcome: GameOutcome,
+    #[serde(default)]
+    pub construction: ConstructionSettings,
+    #[serde(default = "Workforce::unlimited")]
+    pub workforce: Workforce,
+    #[serde(default)]
+    pub town: Town,
 }
 
 pub struct NewGameEvent;
@@ -7,12 +7,21 @@ use uuid::Uuid;
 use crate::game::{
     account::Account,
     assets::{Building, Forest, MapSettings, Name, Position, StateName, Water},
+    bank::Bank,
     car::{Car, CarController},
-    construction::UnderConstruction,
+    construction::{ConstructionSettings, ConstructionSpent, UnderConstruction},
+    contracts::ContractManager,
+    conveyor::Conveyor,
     goals::GoalManager,
+    market::Market,
+    outcome::GameOutcome,
+    pipeline::Pipeline,
     production::{
-        DeliveryStation, Depot, ImportExportStation, ProductionBuilding, StorageManagement,
+        DeliveryStation, Depot, ImportExportStation, ProductionBuilding, StorageManagement, Town,
+        TownShop, TrainStation, Workforce,
     },
+    rail::Rail,
+    scenario::ScenarioManager,
     setup::{BUILDING_LAYER_ID, GROUND_LAYER_ID, MAP_ID},
     state_manager::{
         BuildingEntity, GameEntity, GameEntityType, GameState, SaveGameEvent, SerializedBuilding,
@@ -21,6 +30,8 @@ use crate::game::{
     statistics::{StatisticTracker, Statistics},
     storage::Storage,
     street::Street,
+    time::GameTime,
+    train::Train,
     ui::state::SaveGameList,
 };
 
@@ -46,6 +57,7 @@ pub fn save_game(
         Query<(Entity, &Car, &Position)>,
         Query<&Statistics>,
         Query<&UnderConstruction>,
+        Query<&ConstructionSpent>,
         Query<&Storage>,
         Query<&ImportExportStation>,
         Query<&DeliveryStation>,
@@ -55,12 +67,31 @@ pub fn save_game(
         Query<(), With<Water>>,
         Query<(), With<Forest>>,
         Query<(&Building, Option<&ProductionBuilding>)>,
+        Query<&TownShop>,
     ),
+    transport_queries: (
+        Query<&Rail>,
+        Query<&TrainStation>,
+        Query<&Conveyor>,
+        Query<&Pipeline>,
+    ),
+    train_query: Query<(Entity, &Train, &Position)>,
     map_query: MapQuery,
     mut save_game: EventReader<SaveGameEvent>,
     map_settings: Res<MapSettings>,
     goals: Res<GoalManager>,
-    account: Res<Account>,
+    economy: (
+        Res<Account>,
+        Res<Market>,
+        Res<Bank>,
+        Res<Workforce>,
+        Res<Town>,
+    ),
+    time: Res<GameTime>,
+    contracts: Res<ContractManager>,
+    scenarios: Res<ScenarioManager>,
+    outcome: Res<GameOutcome>,
+    construction: Res<ConstructionSettings>,
     state_name: Res<StateName>,
     deleted_export_statistics: Res<StatisticTracker>,
     mut save_game_list: ResMut<SaveGameList>,
@@ -70,6 +101,7 @@ pub fn save_game(
         car_query,
         statistics_query,
         under_construction_query,
+        spent_query,
         storage_query,
         import_export_station_query,
         delivery_station_query,
@@ -79,13 +111,25 @@ pub fn save_game(
         water_query,
         forest_query,
         building_query,
+        town_shop_query,
     ) = queries;
+    let (rail_query, train_station_query, conveyor_query, pipeline_query) = transport_queries;
+    let (account, market, bank, workforce, town) = economy;
 
     for event in save_game.iter() {
         let mut state = GameState {
             settings: map_settings.clone(),
             goals: goals.goals.clone(),
             account: account.clone(),
+            market: market.clone(),
+            bank: bank.clone(),
+            time: time.clone(),
+            contracts: contracts.clone(),
+            scenario: scenarios.active.clone(),
+            outcome: outcome.clone(),
+            construction: construction.clone(),
+            workforce: workforce.clone(),
+            town: town.clone(),
             state_name: state_name.clone(),
             deleted_export_statistics: deleted_export_statistics.clone(),
             ..Default::default()
@@ -108,6 +152,7 @@ pub fn save_game(
                             entity: GameEntityType::Water,
                             statistics: None,
                             under_construction: None,
+                            spent: None,
                         });
                     }
 
@@ -119,6 +164,7 @@ pub fn save_game(
                             entity: GameEntityType::Forest,
                             statistics: None,
                             under_construction: None,
+                            spent: None,
                         });
                     }
                 }
@@ -143,12 +189,15 @@ pub fn save_game(
                             None
                         };
 
+                    let spent = spent_query.get(entity).ok().map(|spent| spent.0);
+
                     if let Ok((building, production_building)) = building_query.get(entity) {
                         let active_products = if let Some(pb) = production_building {
                             pb.products.iter().map(|(_, active)| *active).collect()
                         } else {
                             vec![]
                         };
+                        let mothballed = production_building.map_or(false, |pb| pb.mothballed);
 
                         state.entities.push(GameEntity {
                             uuid: uuids.get(entity),
@@ -158,10 +207,14 @@ pub fn save_game(
                                 SerializedBuilding {
                                     id: building.id.clone(),
                                     active_products,
+                                    rotation: building.rotation,
+                                    any_side: building.any_side,
+                                    mothballed,
                                 },
                             )),
                             statistics: statistics.clone(),
                             under_construction: under_construction.clone(),
+                            spent,
                         });
                     }
 
@@ -175,6 +228,7 @@ pub fn save_game(
                             )),
                             statistics: statistics.clone(),
                             under_construction: under_construction.clone(),
+                            spent,
                         });
                     }
 
@@ -188,6 +242,7 @@ pub fn save_game(
                             )),
                             statistics: statistics.clone(),
                             under_construction: under_construction.clone(),
+                            spent,
                         });
                     }
 
@@ -201,6 +256,21 @@ pub fn save_game(
                             )),
                             statistics: statistics.clone(),
                             under_construction: under_construction.clone(),
+                            spent,
+                        });
+                    }
+
+                    if let Ok(building) = town_shop_query.get(entity) {
+                        state.entities.push(GameEntity {
+                            uuid: uuids.get(entity),
+                            pos,
+                            name: name.clone(),
+                            entity: GameEntityType::Building(BuildingEntity::TownShop(
+                                building.clone(),
+                            )),
+                            statistics: statistics.clone(),
+                            under_construction: under_construction.clone(),
+                            spent,
                         });
                     }
 
@@ -214,6 +284,7 @@ pub fn save_game(
                             )),
                             statistics: statistics.clone(),
                             under_construction: under_construction.clone(),
+                            spent,
                         });
                     }
 
@@ -225,6 +296,63 @@ pub fn save_game(
                             entity: GameEntityType::Building(BuildingEntity::Depot(depot.clone())),
                             statistics: statistics.clone(),
                             under_construction: under_construction.clone(),
+                            spent,
+                        });
+                    }
+
+                    if let Ok(building) = rail_query.get(entity) {
+                        state.entities.push(GameEntity {
+                            uuid: uuids.get(entity),
+                            pos,
+                            name: name.clone(),
+                            entity: GameEntityType::Building(BuildingEntity::Rail(
+                                building.clone(),
+                            )),
+                            statistics: statistics.clone(),
+                            under_construction: under_construction.clone(),
+                            spent,
+                        });
+                    }
+
+                    if let Ok(building) = train_station_query.get(entity) {
+                        state.entities.push(GameEntity {
+                            uuid: uuids.get(entity),
+                            pos,
+                            name: name.clone(),
+                            entity: GameEntityType::Building(BuildingEntity::TrainStation(
+                                building.clone(),
+                            )),
+                            statistics: statistics.clone(),
+                            under_construction: under_construction.clone(),
+                            spent,
+                        });
+                    }
+
+                    if let Ok(building) = conveyor_query.get(entity) {
+                        state.entities.push(GameEntity {
+                            uuid: uuids.get(entity),
+                            pos,
+                            name: name.clone(),
+                            entity: GameEntityType::Building(BuildingEntity::Conveyor(
+                                building.clone(),
+                            )),
+                            statistics: statistics.clone(),
+                            under_construction: under_construction.clone(),
+                            spent,
+                        });
+                    }
+
+                    if let Ok(building) = pipeline_query.get(entity) {
+                        state.entities.push(GameEntity {
+                            uuid: uuids.get(entity),
+                            pos,
+                            name: name.clone(),
+                            entity: GameEntityType::Building(BuildingEntity::Pipeline(
+                                building.clone(),
+                            )),
+                            statistics: statistics.clone(),
+                            under_construction: under_construction.clone(),
+                            spent,
                         });
                     }
 
@@ -238,6 +366,7 @@ pub fn save_game(
                             )),
                             statistics: statistics.clone(),
                             under_construction: under_construction.clone(),
+                            spent,
                         });
                     }
                 }
@@ -270,6 +399,7 @@ pub fn save_game(
                 name: name.clone(),
                 statistics: None,
                 under_construction: None,
+                spent: None,
                 entity: GameEntityType::Vehicle(Vehicle {
                     direction: car.direction,
                     controller,
@@ -278,6 +408,24 @@ pub fn save_game(
             });
         }
 
+        for (entity, train, position) in train_query.iter() {
+            let name = if let Ok(name) = name_query.get(entity) {
+                Some(name.clone())
+            } else {
+                None
+            };
+
+            state.entities.push(GameEntity {
+                uuid: uuids.get(entity),
+                pos: position.position,
+                name,
+                statistics: None,
+                under_construction: None,
+                spent: None,
+                entity: GameEntityType::Train(train.clone()),
+            });
+        }
+
         let path = Path::new(&event.file_name);
         let mut file = File::create(&path).unwrap();
 
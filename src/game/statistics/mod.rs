@@ -47,4 +47,8 @@ pub struct Statistics {
     pub consumption: StatisticTracker,
     pub export: StatisticTracker,
     pub import: StatisticTracker,
+    #[serde(default)]
+    pub spoiled: StatisticTracker,
+    #[serde(default)]
+    pub sold: StatisticTracker,
 }
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests;
 
+use std::{cmp::Reverse, collections::HashMap};
+
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 use bevy_egui::egui::Ui;
@@ -11,17 +13,58 @@ use crate::game::constants::UNIT;
 
 use super::{
     account::PurchaseCost,
-    assets::{resource_specifications::ResourceSpecifications, InfoUI, Position, RequiresUpdate},
-    constants::STORAGE_SIZE,
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications, Building, BuildingPart, Direction,
+        Footprint, InfoUI, Position, RequiresUpdate,
+    },
+    constants::{
+        MapTile, PRODUCTION_TICK_SPEED, REFRIGERATION_FACTOR, SILO_SIZE, STORAGE_SIZE,
+        TILE_MAP_WIDTH, WAREHOUSE_SIZE,
+    },
+    construction::UnderConstruction,
     setup::{BUILDING_LAYER_ID, MAP_ID},
 };
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum StorageKind {
+    Single,
+    Warehouse,
+    Silo,
+    Tank,
+}
+
+impl Default for StorageKind {
+    fn default() -> Self {
+        StorageKind::Single
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StorageReservation {
+    pub building: UVec2,
+    pub amount: f64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct Storage {
     pub resource: String,
     pub amount: f64,
     pub capacity: f64,
+    #[serde(default)]
+    pub kind: StorageKind,
+    #[serde(default)]
+    pub contents: HashMap<String, f64>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub reservation: Option<StorageReservation>,
+    #[serde(default)]
+    pub refrigerated: bool,
+    #[serde(default)]
+    pub tier: usize,
 }
 
 impl Default for Storage {
@@ -30,11 +73,99 @@ impl Default for Storage {
             resource: "".to_owned(),
             amount: 0.0,
             capacity: STORAGE_SIZE,
+            kind: StorageKind::Single,
+            contents: HashMap::new(),
+            priority: 0,
+            reservation: None,
+            refrigerated: false,
+            tier: 0,
         }
     }
 }
 
 impl Storage {
+    pub fn new_warehouse() -> Self {
+        Self {
+            capacity: WAREHOUSE_SIZE,
+            kind: StorageKind::Warehouse,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_silo(resource: &str) -> Self {
+        Self {
+            resource: resource.to_owned(),
+            capacity: SILO_SIZE,
+            kind: StorageKind::Silo,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_tank(resource: &str) -> Self {
+        Self {
+            resource: resource.to_owned(),
+            kind: StorageKind::Tank,
+            ..Default::default()
+        }
+    }
+
+    // liquids are kept in tanks, everything else in ordinary storages
+    pub fn for_resource(resource: &str, resources: &ResourceSpecifications) -> Self {
+        if is_liquid(resource, resources) {
+            Self::new_tank(resource)
+        } else {
+            Self {
+                resource: resource.to_owned(),
+                ..Default::default()
+            }
+        }
+    }
+
+    // saves from before tanks were a storage kind kept liquids in single storages
+    pub fn migrate_tank(&mut self, resources: &ResourceSpecifications) {
+        if self.kind == StorageKind::Single && is_liquid(&self.resource, resources) {
+            self.kind = StorageKind::Tank;
+        }
+    }
+
+    pub fn is_silo(&self) -> bool {
+        self.kind == StorageKind::Silo
+    }
+
+    pub fn footprint(&self) -> Option<Footprint> {
+        if self.is_silo() {
+            Some(Footprint {
+                size: UVec2::new(2, 2),
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn base_capacity(&self) -> f64 {
+        match self.kind {
+            StorageKind::Single | StorageKind::Tank => STORAGE_SIZE,
+            StorageKind::Warehouse => WAREHOUSE_SIZE,
+            StorageKind::Silo => SILO_SIZE,
+        }
+    }
+
+    pub fn tier_capacity(&self, tier: usize) -> f64 {
+        self.base_capacity() * STORAGE_TIERS[tier].capacity
+    }
+
+    pub fn next_tier(&self) -> Option<&'static StorageTier> {
+        STORAGE_TIERS.get(self.tier + 1)
+    }
+
+    pub fn is_warehouse(&self) -> bool {
+        self.kind == StorageKind::Warehouse
+    }
+
+    pub fn is_tank(&self) -> bool {
+        self.kind == StorageKind::Tank
+    }
+
     pub fn is_full(&self) -> bool {
         self.amount >= self.capacity
     }
@@ -46,34 +177,318 @@ impl Storage {
     pub fn percentage(&self) -> f64 {
         self.amount / self.capacity
     }
+
+    pub fn accepts(&self, resource: &str, resources: &ResourceSpecifications) -> bool {
+        let liquid = is_liquid(resource, resources);
+
+        match self.kind {
+            StorageKind::Single | StorageKind::Silo => !liquid && self.resource == resource,
+            StorageKind::Tank => liquid && self.resource == resource,
+            StorageKind::Warehouse => {
+                let virtual_resource = resources
+                    .get(resource)
+                    .map_or(false, |resource| resource.virtual_resource);
+
+                !liquid && !virtual_resource
+            }
+        }
+    }
+
+    pub fn amount_of(&self, resource: &str) -> f64 {
+        match self.kind {
+            StorageKind::Warehouse => *self.contents.get(resource).unwrap_or(&0.0),
+            _ if self.resource == resource => self.amount,
+            _ => 0.0,
+        }
+    }
+
+    // amount a building at the given position may take, keeping reservations of others
+    pub fn available_for(&self, resource: &str, building: UVec2) -> f64 {
+        let amount = self.amount_of(resource);
+
+        match &self.reservation {
+            Some(reservation) if reservation.building != building => {
+                (amount - reservation.amount).max(0.0)
+            }
+            _ => amount,
+        }
+    }
+
+    pub fn space_for(&self, resource: &str, resources: &ResourceSpecifications) -> f64 {
+        if self.accepts(resource, resources) {
+            (self.capacity - self.amount).max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn add(&mut self, resource: &str, amount: f64) {
+        self.amount += amount;
+
+        if self.is_warehouse() {
+            *self.contents.entry(resource.to_owned()).or_insert(0.0) += amount;
+        }
+    }
+
+    pub fn take(&mut self, resource: &str, amount: f64) {
+        self.amount -= amount;
+
+        if self.is_warehouse() {
+            let left = self.contents.entry(resource.to_owned()).or_insert(0.0);
+            *left -= amount;
+
+            if *left <= 0.0 {
+                self.contents.remove(resource);
+            }
+        }
+    }
+
+    // adds as much as fits and returns the rest
+    pub fn absorb(
+        &mut self,
+        resource: &str,
+        amount: f64,
+        resources: &ResourceSpecifications,
+    ) -> f64 {
+        let added = self.space_for(resource, resources).min(amount);
+        if added > 0.0 {
+            self.add(resource, added);
+        }
+
+        amount - added
+    }
+
+    pub fn goods(&self) -> Vec<(String, f64)> {
+        match self.kind {
+            StorageKind::Single | StorageKind::Silo | StorageKind::Tank => {
+                vec![(self.resource.clone(), self.amount)]
+            }
+            StorageKind::Warehouse => self
+                .contents
+                .iter()
+                .map(|(resource, amount)| (resource.clone(), *amount))
+                .collect(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.amount = 0.0;
+        self.contents.clear();
+        self.reservation = None;
+    }
+
+    // removes goods decayed during one production tick and returns the losses
+    pub fn spoil(&mut self, resources: &ResourceSpecifications) -> Vec<(String, f64)> {
+        let mut losses = vec![];
+        for (resource, amount) in self.goods() {
+            let half_life = match resources.get(&resource).and_then(|r| r.perishable) {
+                Some(half_life) if amount > 0.0 && half_life > 0.0 => half_life,
+                _ => continue,
+            };
+
+            let loss = amount * (1.0 - spoilage_factor(half_life, self.refrigerated));
+            self.take(&resource, loss);
+            losses.push((resource, loss));
+        }
+
+        losses
+    }
+
+    // only empty single resource storages can be switched to another resource
+    pub fn can_retarget(&self) -> bool {
+        !self.is_warehouse() && self.is_empty()
+    }
+}
+
+pub fn is_liquid(resource: &str, resources: &ResourceSpecifications) -> bool {
+    resources
+        .get(resource)
+        .map_or(false, |resource| resource.liquid)
+}
+
+pub struct StorageTier {
+    pub capacity: f64,
+    pub labor: i64,
+    pub steel_slabs: f64,
+}
+
+pub const STORAGE_TIERS: [StorageTier; 3] = [
+    StorageTier {
+        capacity: 1.0,
+        labor: 0,
+        steel_slabs: 0.0,
+    },
+    StorageTier {
+        capacity: 2.0,
+        labor: 1500,
+        steel_slabs: 10.0,
+    },
+    StorageTier {
+        capacity: 4.0,
+        labor: 3000,
+        steel_slabs: 25.0,
+    },
+];
+
+// atlas index of a silo quarter, offset from the lower left tile
+pub fn silo_tile(offset: UVec2) -> u16 {
+    let row = 1 - offset.y.min(1);
+
+    MapTile::SiloOffset as u16 + offset.x as u16 + (row * TILE_MAP_WIDTH as u32) as u16
+}
+
+pub fn spoilage_factor(half_life: f64, refrigerated: bool) -> f64 {
+    let half_life = if refrigerated {
+        half_life * REFRIGERATION_FACTOR
+    } else {
+        half_life
+    };
+
+    0.5_f64.powf(PRODUCTION_TICK_SPEED / half_life)
 }
 
 impl PurchaseCost for Storage {
     fn price(&self, resources: &ResourceSpecifications) -> i64 {
+        if self.is_warehouse() {
+            return (self.capacity * 8.0) as i64 + 2000;
+        }
+
         let resource = resources
             .get(&self.resource)
             .unwrap_or_else(|| panic!("expected to find resource {} in spec", self.resource));
 
-        ((resource.cost * self.capacity) / 25.0) as i64 + 1000
+        let price = ((resource.cost * self.capacity) / 25.0) as i64 + 1000;
+
+        if self.refrigerated {
+            price + price / 2
+        } else {
+            price
+        }
     }
 }
 
 impl InfoUI for Storage {
     fn ui(&self, ui: &mut Ui, resources: &ResourceSpecifications) {
+        if self.is_warehouse() {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Warehouse {:.2}{} / {:.2}{}",
+                    self.amount, UNIT, self.capacity, UNIT,
+                ));
+            });
+
+            let mut contents: Vec<(&String, &f64)> = self.contents.iter().collect();
+            contents.sort_by(|a, b| a.0.cmp(b.0));
+            for (id, amount) in contents {
+                let name = resources
+                    .get(id)
+                    .map(|r| r.name.clone())
+                    .unwrap_or_default();
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} {:.2}{}", name, amount, UNIT));
+                });
+            }
+            return;
+        }
+
         ui.horizontal(|ui| {
             let resource = resources.get(&self.resource).unwrap();
+            let kind = if self.is_tank() {
+                " Tank"
+            } else if self.refrigerated {
+                " (Refrigerated)"
+            } else {
+                ""
+            };
 
             ui.label(format!(
-                "{} {:.2}{} / {:.2}{}",
-                resource.name, self.amount, UNIT, self.capacity, UNIT,
+                "{}{} {:.2}{} / {:.2}{}",
+                resource.name, kind, self.amount, UNIT, self.capacity, UNIT,
             ));
         });
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
+pub struct ConsolidatorPorts {
+    pub inputs: Vec<Entity>,
+    pub outputs: Vec<Entity>,
+}
+
+#[derive(Default, Clone)]
 pub struct StorageConsolidator {
     pub connected_storage: Vec<Entity>,
+    pub position: UVec2,
+    // without ports every connected storage is used for inputs and outputs
+    pub ports: Option<ConsolidatorPorts>,
+}
+
+impl StorageConsolidator {
+    fn restricted(&self, storage: &[Entity]) -> StorageConsolidator {
+        StorageConsolidator {
+            connected_storage: storage.to_vec(),
+            position: self.position,
+            ports: None,
+        }
+    }
+
+    pub fn inputs(&self) -> StorageConsolidator {
+        match &self.ports {
+            Some(ports) => self.restricted(&ports.inputs),
+            None => self.clone(),
+        }
+    }
+
+    pub fn outputs(&self) -> StorageConsolidator {
+        match &self.ports {
+            Some(ports) => self.restricted(&ports.outputs),
+            None => self.clone(),
+        }
+    }
+}
+
+// side of a building facing a tile next to it, diagonal neighbors only touch a corner
+pub fn neighbor_side(pos: UVec2, origin: UVec2, size: UVec2) -> Option<Direction> {
+    let vertical = if pos.y >= origin.y + size.y {
+        Some(Direction::North)
+    } else if pos.y < origin.y {
+        Some(Direction::South)
+    } else {
+        None
+    };
+    let horizontal = if pos.x < origin.x {
+        Some(Direction::West)
+    } else if pos.x >= origin.x + size.x {
+        Some(Direction::East)
+    } else {
+        None
+    };
+
+    match (vertical, horizontal) {
+        (Some(side), None) | (None, Some(side)) => Some(side),
+        _ => None,
+    }
+}
+
+// storage with higher priority is filled and drained first, equal priorities in random order
+fn ordered_storage(
+    consolidator: &StorageConsolidator,
+    storage_query: &Query<&mut Storage>,
+) -> Vec<Entity> {
+    let mut entities = consolidator.connected_storage.clone();
+    let mut random = thread_rng();
+    entities.shuffle(&mut random);
+
+    entities.sort_by_key(|entity| {
+        let priority = storage_query
+            .get_component::<Storage>(*entity)
+            .map(|storage| storage.priority)
+            .unwrap_or(0);
+
+        Reverse(priority)
+    });
+
+    entities
 }
 
 pub fn distribute_to_storage(
@@ -81,25 +496,24 @@ pub fn distribute_to_storage(
     storage_query: &mut Query<&mut Storage>,
     resource: &str,
     amount: f64,
+    resources: &ResourceSpecifications,
 ) {
     assert!(amount > 0.0);
     let mut amount_left = amount;
 
-    let mut entities = consolidator.connected_storage.clone();
-    let mut random = thread_rng();
-    entities.shuffle(&mut random);
+    let entities = ordered_storage(consolidator, storage_query);
 
     for storage in entities.iter() {
         if let Ok(mut storage) = storage_query.get_mut(*storage) {
-            if storage.resource == resource && storage.amount < storage.capacity {
-                let space_left = storage.capacity - storage.amount;
+            let space_left = storage.space_for(resource, resources);
+            if space_left > 0.0 {
                 let amount_added = if space_left < amount_left {
                     space_left
                 } else {
                     amount_left
                 };
 
-                storage.amount += amount_added;
+                storage.add(resource, amount_added);
                 amount_left -= amount_added;
             }
         }
@@ -121,9 +535,7 @@ pub fn amount_in_storage(
 
     for storage in consolidator.connected_storage.iter() {
         if let Ok(storage) = storage_query.get_mut(*storage) {
-            if storage.resource == resource {
-                amount += storage.amount
-            }
+            amount += storage.available_for(resource, consolidator.position);
         }
     }
 
@@ -144,14 +556,12 @@ pub fn space_in_storage(
     consolidator: &StorageConsolidator,
     storage_query: &mut Query<&mut Storage>,
     resource: &str,
+    resources: &ResourceSpecifications,
 ) -> f64 {
     let mut amount_found = 0.0;
     for storage in consolidator.connected_storage.iter() {
         if let Ok(storage) = storage_query.get_mut(*storage) {
-            if storage.resource == resource && storage.amount < storage.capacity {
-                let space_left = storage.capacity - storage.amount;
-                amount_found += space_left;
-            }
+            amount_found += storage.space_for(resource, resources);
         }
     }
 
@@ -163,10 +573,11 @@ pub fn has_space_in_storage(
     storage_query: &mut Query<&mut Storage>,
     resource: &str,
     amount: f64,
+    resources: &ResourceSpecifications,
 ) -> bool {
     assert!(amount > 0.0);
 
-    space_in_storage(consolidator, storage_query, resource) >= amount
+    space_in_storage(consolidator, storage_query, resource, resources) >= amount
 }
 
 pub fn fetch_from_storage(
@@ -180,19 +591,18 @@ pub fn fetch_from_storage(
     }
     let mut amount_left = amount;
 
-    let mut entities = consolidator.connected_storage.clone();
-    let mut random = thread_rng();
-    entities.shuffle(&mut random);
+    let entities = ordered_storage(consolidator, storage_query);
 
     for storage in entities.iter() {
         if let Ok(mut storage) = storage_query.get_mut(*storage) {
-            if storage.resource == resource && storage.amount > 0.0 {
-                let amount_taken = if storage.amount < amount_left {
-                    storage.amount
+            let amount_stored = storage.available_for(resource, consolidator.position);
+            if amount_stored > 0.0 {
+                let amount_taken = if amount_stored < amount_left {
+                    amount_stored
                 } else {
                     amount_left
                 };
-                storage.amount -= amount_taken;
+                storage.take(resource, amount_taken);
                 amount_left -= amount_taken;
             }
         }
@@ -208,20 +618,116 @@ pub fn fetch_from_storage(
 pub fn update_consolidators(
     map_query: MapQuery,
     storage_query: Query<(Entity, &Storage)>,
-    mut consolidator_query: Query<(&mut StorageConsolidator, &Position), With<RequiresUpdate>>,
+    part_query: Query<&BuildingPart>,
+    building_query: Query<&Building>,
+    buildings: Res<BuildingSpecifications>,
+    mut consolidator_query: Query<
+        (
+            Entity,
+            &mut StorageConsolidator,
+            &Position,
+            Option<&Footprint>,
+        ),
+        With<RequiresUpdate>,
+    >,
 ) {
-    for (mut consolidator, position) in consolidator_query.iter_mut() {
-        let neighbors = map_query.get_tile_neighbors(position.position, MAP_ID, BUILDING_LAYER_ID);
+    for (entity, mut consolidator, position, footprint) in consolidator_query.iter_mut() {
+        let size = footprint
+            .map(|footprint| footprint.size)
+            .unwrap_or_else(|| UVec2::new(1, 1));
+
+        let ports = building_query.get(entity).ok().and_then(|building| {
+            if building.any_side {
+                return None;
+            }
+
+            let specification = buildings.get(&building.id)?;
+            let ports = specification.ports.as_ref()?;
+
+            let rotate = |directions: &Vec<Direction>| -> Vec<Direction> {
+                directions
+                    .iter()
+                    .map(|direction| direction.rotated(building.rotation))
+                    .collect()
+            };
+
+            Some((rotate(&ports.inputs), rotate(&ports.outputs)))
+        });
 
         let mut connected_storage = vec![];
-        for (_, neighbor) in neighbors.iter() {
-            if let Some(neighbor) = neighbor {
-                if storage_query.get(*neighbor).is_ok() {
-                    connected_storage.push(*neighbor);
+        let mut connected_ports = ConsolidatorPorts::default();
+
+        // buildings with a footprint connect to storage around all of their tiles
+        let positions = match footprint {
+            Some(footprint) => footprint.positions(position.position),
+            None => vec![position.position],
+        };
+
+        for pos in positions {
+            let neighbors = map_query.get_tile_neighbors(pos, MAP_ID, BUILDING_LAYER_ID);
+
+            for (neighbor_pos, neighbor) in neighbors.iter() {
+                if let Some(neighbor) = neighbor {
+                    // tiles of a silo all belong to the storage on its main tile
+                    let neighbor = part_query
+                        .get(*neighbor)
+                        .map(|part| part.main)
+                        .unwrap_or(*neighbor);
+
+                    if neighbor == entity || storage_query.get(neighbor).is_err() {
+                        continue;
+                    }
+
+                    if !connected_storage.contains(&neighbor) {
+                        connected_storage.push(neighbor);
+                    }
+
+                    if let Some((inputs, outputs)) = &ports {
+                        let side = neighbor_side(neighbor_pos.as_u32(), position.position, size);
+
+                        if side.map_or(false, |side| inputs.contains(&side))
+                            && !connected_ports.inputs.contains(&neighbor)
+                        {
+                            connected_ports.inputs.push(neighbor);
+                        }
+                        if side.map_or(false, |side| outputs.contains(&side))
+                            && !connected_ports.outputs.contains(&neighbor)
+                        {
+                            connected_ports.outputs.push(neighbor);
+                        }
+                    }
                 }
             }
         }
 
         consolidator.connected_storage = connected_storage;
+        consolidator.position = position.position;
+        consolidator.ports = ports.map(|_| connected_ports);
+    }
+}
+
+// applies the capacity of an upgrade once its construction is finished
+// upgrades raise the tier once their site is finished
+pub fn apply_storage_tiers(mut storage_query: Query<(&mut Storage, Option<&UnderConstruction>)>) {
+    for (mut storage, construction) in storage_query.iter_mut() {
+        if let Some(construction) = construction {
+            match construction.tier {
+                Some(tier) if construction.is_finished() => {
+                    if storage.tier < tier {
+                        storage.tier = tier;
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        if storage.tier == 0 {
+            continue;
+        }
+
+        let capacity = storage.tier_capacity(storage.tier);
+        if storage.capacity < capacity {
+            storage.capacity = capacity;
+        }
     }
 }
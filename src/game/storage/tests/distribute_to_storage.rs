@@ -6,7 +6,13 @@ fn distribute_to_storage_test_system(
     params: Res<TestAmount>,
 ) {
     for consolidator in consolidator_query.iter() {
-        distribute_to_storage(consolidator, &mut storage_query, COKE, params.amount);
+        distribute_to_storage(
+            consolidator,
+            &mut storage_query,
+            COKE,
+            params.amount,
+            &ResourceSpecifications::new(),
+        );
     }
 }
 
@@ -24,6 +30,7 @@ fn test_distribute_to_storage() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -31,6 +38,7 @@ fn test_distribute_to_storage() {
         .spawn()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id],
+            ..Default::default()
         })
         .id();
 
@@ -44,6 +52,7 @@ fn test_distribute_to_storage() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -53,6 +62,7 @@ fn test_distribute_to_storage() {
         .unwrap()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id, second_coke_storage_id],
+            ..Default::default()
         });
 
     stage.run(&mut world);
@@ -80,6 +90,7 @@ fn test_storage_deleted() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -87,6 +98,7 @@ fn test_storage_deleted() {
 
     world.spawn().insert(StorageConsolidator {
         connected_storage: vec![coke_storage_id],
+        ..Default::default()
     });
 
     // should not panic
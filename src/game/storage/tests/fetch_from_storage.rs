@@ -26,6 +26,7 @@ fn test_fetch_from_storage() {
             resource: COKE.to_owned(),
             amount: 3.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -33,6 +34,7 @@ fn test_fetch_from_storage() {
         .spawn()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id],
+            ..Default::default()
         })
         .id();
 
@@ -48,6 +50,7 @@ fn test_fetch_from_storage() {
             resource: COKE.to_owned(),
             amount: 10.0,
             capacity: 10.0,
+            ..Default::default()
         });
 
     stage.run(&mut world);
@@ -62,6 +65,7 @@ fn test_fetch_from_storage() {
             resource: COKE.to_owned(),
             amount: 3.0,
             capacity: 10.0,
+            ..Default::default()
         });
 
     let second_coke_storage_id = world
@@ -70,6 +74,7 @@ fn test_fetch_from_storage() {
             resource: COKE.to_owned(),
             amount: 8.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -78,6 +83,7 @@ fn test_fetch_from_storage() {
         .unwrap()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id, second_coke_storage_id],
+            ..Default::default()
         });
 
     stage.run(&mut world);
@@ -107,6 +113,7 @@ fn test_storage_deleted() {
             resource: COKE.to_owned(),
             amount: 10.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -114,6 +121,7 @@ fn test_storage_deleted() {
 
     world.spawn().insert(StorageConsolidator {
         connected_storage: vec![coke_storage_id],
+        ..Default::default()
     });
 
     stage.run(&mut world);
@@ -136,6 +144,7 @@ fn test_storage_deleted_with_others_active() {
             resource: COKE.to_owned(),
             amount: 10.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -147,11 +156,13 @@ fn test_storage_deleted_with_others_active() {
             resource: COKE.to_owned(),
             amount: 10.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
     world.spawn().insert(StorageConsolidator {
         connected_storage: vec![coke_storage_id, second_coke_storage_id],
+        ..Default::default()
     });
 
     stage.run(&mut world);
@@ -26,6 +26,7 @@ fn test_has_in_storage() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -33,6 +34,7 @@ fn test_has_in_storage() {
         .spawn()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id],
+            ..Default::default()
         })
         .id();
 
@@ -47,6 +49,7 @@ fn test_has_in_storage() {
             resource: COKE.to_owned(),
             amount: 1.0,
             capacity: 10.0,
+            ..Default::default()
         });
 
     stage.run(&mut world);
@@ -64,6 +67,7 @@ fn test_has_in_storage() {
             resource: COKE.to_owned(),
             amount: 1.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -72,6 +76,7 @@ fn test_has_in_storage() {
         .unwrap()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id, second_coke_storage_id],
+            ..Default::default()
         });
     stage.run(&mut world);
 
@@ -93,6 +98,7 @@ fn test_storage_deleted() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -100,6 +106,7 @@ fn test_storage_deleted() {
 
     world.spawn().insert(StorageConsolidator {
         connected_storage: vec![coke_storage_id],
+        ..Default::default()
     });
 
     stage.run(&mut world);
@@ -7,7 +7,13 @@ fn has_space_in_storage_test_system(
     params: Res<TestAmount>,
 ) {
     for consolidator in consolidator_query.iter() {
-        result.result = has_space_in_storage(consolidator, &mut storage_query, COKE, params.amount);
+        result.result = has_space_in_storage(
+            consolidator,
+            &mut storage_query,
+            COKE,
+            params.amount,
+            &ResourceSpecifications::new(),
+        );
     }
 }
 
@@ -26,6 +32,7 @@ fn test_has_space_in_storage() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -33,6 +40,7 @@ fn test_has_space_in_storage() {
         .spawn()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id],
+            ..Default::default()
         })
         .id();
 
@@ -47,6 +55,7 @@ fn test_has_space_in_storage() {
             resource: COKE.to_owned(),
             amount: 10.0,
             capacity: 10.0,
+            ..Default::default()
         });
 
     stage.run(&mut world);
@@ -59,6 +68,7 @@ fn test_has_space_in_storage() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -67,6 +77,7 @@ fn test_has_space_in_storage() {
         .unwrap()
         .insert(StorageConsolidator {
             connected_storage: vec![coke_storage_id, second_coke_storage_id],
+            ..Default::default()
         });
 
     stage.run(&mut world);
@@ -89,6 +100,7 @@ fn test_storage_deleted() {
             resource: COKE.to_owned(),
             amount: 0.0,
             capacity: 10.0,
+            ..Default::default()
         })
         .id();
 
@@ -96,6 +108,7 @@ fn test_storage_deleted() {
 
     world.spawn().insert(StorageConsolidator {
         connected_storage: vec![coke_storage_id],
+        ..Default::default()
     });
 
     stage.run(&mut world);
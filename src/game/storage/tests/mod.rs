@@ -2,6 +2,12 @@ mod distribute_to_storage;
 mod fetch_from_storage;
 mod has_in_storage;
 mod has_space_in_storage;
+mod ports;
+mod priority;
+mod salvage;
+mod tanks;
+mod tiers;
+mod warehouse;
 
 use super::*;
 
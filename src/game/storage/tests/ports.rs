@@ -0,0 +1,34 @@
+use super::*;
+
+#[test]
+fn side_of_neighbors() {
+    let origin = UVec2::new(2, 2);
+    let size = UVec2::new(2, 3);
+
+    assert_eq!(
+        neighbor_side(UVec2::new(1, 3), origin, size),
+        Some(Direction::West)
+    );
+    assert_eq!(
+        neighbor_side(UVec2::new(4, 2), origin, size),
+        Some(Direction::East)
+    );
+    assert_eq!(
+        neighbor_side(UVec2::new(3, 5), origin, size),
+        Some(Direction::North)
+    );
+    assert_eq!(
+        neighbor_side(UVec2::new(2, 1), origin, size),
+        Some(Direction::South)
+    );
+    assert_eq!(neighbor_side(UVec2::new(1, 1), origin, size), None);
+}
+
+#[test]
+fn rotated_ports() {
+    assert_eq!(Direction::North.rotated(0), Direction::North);
+    assert_eq!(Direction::North.rotated(1), Direction::East);
+    assert_eq!(Direction::West.rotated(2), Direction::East);
+    assert_eq!(Direction::South.rotated(3), Direction::East);
+    assert_eq!(Direction::East.rotated(4), Direction::East);
+}
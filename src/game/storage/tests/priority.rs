@@ -0,0 +1,107 @@
+use super::*;
+
+fn priority_test_system(
+    consolidator_query: Query<&StorageConsolidator>,
+    mut storage_query: Query<&mut Storage>,
+    mut result: ResMut<TestResult>,
+    params: Res<TestAmount>,
+) {
+    for consolidator in consolidator_query.iter() {
+        if params.amount > 0.0 {
+            distribute_to_storage(
+                consolidator,
+                &mut storage_query,
+                COKE,
+                params.amount,
+                &ResourceSpecifications::new(),
+            );
+        } else {
+            result.result = fetch_from_storage(consolidator, &mut storage_query, COKE, 4.0);
+        }
+    }
+}
+
+fn spawn_storage(world: &mut World, amount: f64, priority: i32) -> Entity {
+    world
+        .spawn()
+        .insert(Storage {
+            resource: COKE.to_owned(),
+            amount,
+            capacity: 10.0,
+            priority,
+            ..Default::default()
+        })
+        .id()
+}
+
+#[test]
+fn test_higher_priority_first() {
+    let mut world = World::default();
+    world.insert_resource(TestResult::default());
+    world.insert_resource(TestAmount { amount: 4.0 });
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(priority_test_system.system());
+
+    let buffer_id = spawn_storage(&mut world, 0.0, 0);
+    let output_id = spawn_storage(&mut world, 0.0, 1);
+
+    world.spawn().insert(StorageConsolidator {
+        connected_storage: vec![buffer_id, output_id],
+        ..Default::default()
+    });
+
+    stage.run(&mut world);
+
+    assert!((world.get::<Storage>(output_id).unwrap().amount - 4.0).abs() < f64::EPSILON);
+    assert!((world.get::<Storage>(buffer_id).unwrap().amount).abs() < f64::EPSILON);
+
+    world.get_mut::<Storage>(buffer_id).unwrap().amount = 4.0;
+    world.insert_resource(TestAmount { amount: 0.0 });
+
+    stage.run(&mut world);
+
+    assert!(world.get_resource::<TestResult>().unwrap().result);
+    assert!((world.get::<Storage>(output_id).unwrap().amount).abs() < f64::EPSILON);
+    assert!((world.get::<Storage>(buffer_id).unwrap().amount - 4.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_reservation_keeps_minimum() {
+    let mut world = World::default();
+    world.insert_resource(TestResult::default());
+    world.insert_resource(TestAmount { amount: 0.0 });
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(priority_test_system.system());
+
+    let storage_id = spawn_storage(&mut world, 6.0, 0);
+    world.get_mut::<Storage>(storage_id).unwrap().reservation = Some(StorageReservation {
+        building: UVec2::new(1, 1),
+        amount: 3.0,
+    });
+
+    let consolidator_id = world
+        .spawn()
+        .insert(StorageConsolidator {
+            connected_storage: vec![storage_id],
+            position: UVec2::new(2, 2),
+            ..Default::default()
+        })
+        .id();
+
+    stage.run(&mut world);
+
+    assert!(!world.get_resource::<TestResult>().unwrap().result);
+    assert!((world.get::<Storage>(storage_id).unwrap().amount - 6.0).abs() < f64::EPSILON);
+
+    world
+        .get_mut::<StorageConsolidator>(consolidator_id)
+        .unwrap()
+        .position = UVec2::new(1, 1);
+
+    stage.run(&mut world);
+
+    assert!(world.get_resource::<TestResult>().unwrap().result);
+    assert!((world.get::<Storage>(storage_id).unwrap().amount - 2.0).abs() < f64::EPSILON);
+}
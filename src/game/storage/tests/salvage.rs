@@ -0,0 +1,39 @@
+use super::*;
+
+const COAL: &str = "coal";
+
+#[test]
+fn test_absorb_fills_up_to_capacity() {
+    let mut storage = Storage {
+        resource: COKE.to_owned(),
+        amount: 8.0,
+        capacity: 10.0,
+        ..Default::default()
+    };
+
+    let resources = ResourceSpecifications::new();
+
+    assert!((storage.absorb(COKE, 5.0, &resources) - 3.0).abs() < f64::EPSILON);
+    assert!(storage.is_full());
+    assert!((storage.absorb(COAL, 5.0, &resources) - 5.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_clear_removes_goods() {
+    let mut warehouse = Storage {
+        capacity: 10.0,
+        ..Storage::new_warehouse()
+    };
+    warehouse.add(COKE, 4.0);
+    warehouse.add(COAL, 3.0);
+
+    let goods = warehouse.goods();
+    assert_eq!(goods.len(), 2);
+    for (resource, amount) in goods {
+        assert!((amount - warehouse.amount_of(&resource)).abs() < f64::EPSILON);
+    }
+
+    warehouse.clear();
+    assert!(warehouse.is_empty());
+    assert!(warehouse.goods().is_empty());
+}
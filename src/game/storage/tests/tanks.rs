@@ -0,0 +1,68 @@
+use super::*;
+use crate::game::assets::resource_specifications::ResourceSpecification;
+
+const MILK: &str = "milk";
+
+fn resources() -> ResourceSpecifications {
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(
+        MILK.to_owned(),
+        ResourceSpecification {
+            liquid: true,
+            ..Default::default()
+        },
+    );
+    resources.insert(COKE.to_owned(), ResourceSpecification::default());
+
+    resources
+}
+
+#[test]
+fn test_liquids_are_stored_in_tanks() {
+    let resources = resources();
+
+    assert!(Storage::for_resource(MILK, &resources).is_tank());
+    assert!(!Storage::for_resource(COKE, &resources).is_tank());
+}
+
+#[test]
+fn test_tanks_only_accept_liquids() {
+    let resources = resources();
+
+    let tank = Storage::new_tank(MILK);
+    assert!(tank.accepts(MILK, &resources));
+
+    let tank = Storage::new_tank(COKE);
+    assert!(!tank.accepts(COKE, &resources));
+    assert!(tank.space_for(COKE, &resources).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_single_storage_rejects_liquids() {
+    let resources = resources();
+
+    let storage = Storage {
+        resource: MILK.to_owned(),
+        ..Default::default()
+    };
+    assert!(!storage.accepts(MILK, &resources));
+}
+
+#[test]
+fn test_old_liquid_storage_becomes_tank() {
+    let resources = resources();
+
+    let mut storage = Storage {
+        resource: MILK.to_owned(),
+        ..Default::default()
+    };
+    storage.migrate_tank(&resources);
+    assert!(storage.accepts(MILK, &resources));
+
+    let mut storage = Storage {
+        resource: COKE.to_owned(),
+        ..Default::default()
+    };
+    storage.migrate_tank(&resources);
+    assert!(!storage.is_tank());
+}
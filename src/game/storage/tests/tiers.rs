@@ -0,0 +1,70 @@
+use super::*;
+use crate::game::constants::{SILO_SIZE, STORAGE_SIZE};
+
+#[test]
+fn test_tier_capacity() {
+    let storage = Storage {
+        resource: COKE.to_owned(),
+        ..Default::default()
+    };
+    let silo = Storage::new_silo(COKE);
+
+    assert!((storage.tier_capacity(0) - STORAGE_SIZE).abs() < f64::EPSILON);
+    assert!((storage.tier_capacity(2) - STORAGE_SIZE * 4.0).abs() < f64::EPSILON);
+    assert!((silo.tier_capacity(1) - SILO_SIZE * 2.0).abs() < f64::EPSILON);
+    assert!(silo.accepts(COKE, &ResourceSpecifications::new()));
+    assert_eq!(silo.footprint().unwrap().size, UVec2::new(2, 2));
+}
+
+#[test]
+fn test_apply_storage_tiers() {
+    let mut world = World::default();
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(apply_storage_tiers.system());
+
+    let upgraded_id = world
+        .spawn()
+        .insert(Storage {
+            resource: COKE.to_owned(),
+            ..Default::default()
+        })
+        .insert(UnderConstruction::upgrade(HashMap::new(), 100, 1))
+        .id();
+
+    let finished_id = world
+        .spawn()
+        .insert(Storage {
+            resource: COKE.to_owned(),
+            ..Default::default()
+        })
+        .insert(UnderConstruction::upgrade(HashMap::new(), 0, 2))
+        .id();
+
+    let car_storage_id = world
+        .spawn()
+        .insert(Storage {
+            resource: COKE.to_owned(),
+            capacity: 20.0,
+            ..Default::default()
+        })
+        .id();
+
+    stage.run(&mut world);
+
+    let upgraded = world.get::<Storage>(upgraded_id).unwrap();
+    assert_eq!(upgraded.tier, 0);
+    assert!((upgraded.capacity - STORAGE_SIZE).abs() < f64::EPSILON);
+
+    let finished = world.get::<Storage>(finished_id).unwrap();
+    assert_eq!(finished.tier, 2);
+    assert!((finished.capacity - STORAGE_SIZE * 4.0).abs() < f64::EPSILON);
+
+    assert!((world.get::<Storage>(car_storage_id).unwrap().capacity - 20.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_silo_tiles() {
+    assert_eq!(silo_tile(UVec2::new(0, 0)), MapTile::SiloOffset as u16 + 16);
+    assert_eq!(silo_tile(UVec2::new(1, 1)), MapTile::SiloOffset as u16 + 1);
+}
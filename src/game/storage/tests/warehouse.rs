@@ -0,0 +1,110 @@
+use super::*;
+use crate::game::assets::resource_specifications::ResourceSpecification;
+
+const COAL: &str = "coal";
+
+fn warehouse_test_system(
+    consolidator_query: Query<&StorageConsolidator>,
+    mut storage_query: Query<&mut Storage>,
+    mut result: ResMut<TestAmount>,
+    params: Res<TestResult>,
+) {
+    let resources = ResourceSpecifications::new();
+
+    for consolidator in consolidator_query.iter() {
+        if params.result {
+            distribute_to_storage(consolidator, &mut storage_query, COKE, 4.0, &resources);
+            distribute_to_storage(consolidator, &mut storage_query, COAL, 3.0, &resources);
+        }
+
+        result.amount = space_in_storage(consolidator, &mut storage_query, COAL, &resources);
+    }
+}
+
+#[test]
+fn test_warehouse_shares_capacity() {
+    let mut world = World::default();
+    world.insert_resource(TestResult { result: true });
+    world.insert_resource(TestAmount::default());
+
+    let mut stage = SystemStage::parallel();
+    stage.add_system(warehouse_test_system.system());
+
+    let warehouse_id = world
+        .spawn()
+        .insert(Storage {
+            capacity: 10.0,
+            ..Storage::new_warehouse()
+        })
+        .id();
+
+    world.spawn().insert(StorageConsolidator {
+        connected_storage: vec![warehouse_id],
+        ..Default::default()
+    });
+
+    stage.run(&mut world);
+
+    let warehouse = world.get::<Storage>(warehouse_id).unwrap();
+    assert!((warehouse.amount - 7.0).abs() < f64::EPSILON);
+    assert!((warehouse.amount_of(COKE) - 4.0).abs() < f64::EPSILON);
+    assert!((warehouse.amount_of(COAL) - 3.0).abs() < f64::EPSILON);
+    assert!((world.get_resource::<TestAmount>().unwrap().amount - 3.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_warehouse_fetch() {
+    let mut warehouse = Storage::new_warehouse();
+    warehouse.add(COKE, 5.0);
+    warehouse.add(COAL, 2.0);
+
+    warehouse.take(COAL, 2.0);
+
+    assert!(warehouse.accepts(COAL, &ResourceSpecifications::new()));
+    assert!((warehouse.amount_of(COAL)).abs() < f64::EPSILON);
+    assert!((warehouse.amount - 5.0).abs() < f64::EPSILON);
+    assert!(!warehouse.contents.contains_key(COAL));
+    assert!(!warehouse.can_retarget());
+}
+
+#[test]
+fn test_single_storage_ignores_other_resources() {
+    let storage = Storage {
+        resource: COKE.to_owned(),
+        amount: 0.0,
+        capacity: 10.0,
+        ..Default::default()
+    };
+
+    let resources = ResourceSpecifications::new();
+
+    assert!((storage.space_for(COAL, &resources)).abs() < f64::EPSILON);
+    assert!((storage.space_for(COKE, &resources) - 10.0).abs() < f64::EPSILON);
+    assert!(storage.can_retarget());
+}
+
+#[test]
+fn test_warehouse_rejects_liquid_and_virtual_resources() {
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(
+        "milk".to_owned(),
+        ResourceSpecification {
+            liquid: true,
+            ..Default::default()
+        },
+    );
+    resources.insert(
+        "electricity".to_owned(),
+        ResourceSpecification {
+            virtual_resource: true,
+            ..Default::default()
+        },
+    );
+    resources.insert(COAL.to_owned(), ResourceSpecification::default());
+
+    let warehouse = Storage::new_warehouse();
+    assert!(warehouse.accepts(COAL, &resources));
+    assert!(!warehouse.accepts("milk", &resources));
+    assert!(!warehouse.accepts("electricity", &resources));
+    assert!(warehouse.space_for("milk", &resources).abs() < f64::EPSILON);
+}
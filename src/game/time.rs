@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::constants::{DAY_LENGTH, PRODUCTION_TICK_SPEED};
+
+// in-game time, advancing with every production tick
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GameTime {
+    pub elapsed: f64,
+}
+
+impl GameTime {
+    pub fn day(&self) -> f64 {
+        self.elapsed / DAY_LENGTH
+    }
+}
+
+pub fn advance_time(mut time: ResMut<GameTime>) {
+    time.elapsed += PRODUCTION_TICK_SPEED;
+}
+
+pub fn reset_time(mut time: ResMut<GameTime>) {
+    *time = GameTime::default();
+}
@@ -0,0 +1,112 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    assets::Position,
+    car::{Destination, Waypoints},
+    construction::UnderConstruction,
+    rail::Track,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+};
+
+use super::Train;
+
+pub fn find_path<F>(
+    start: UVec2,
+    destination: UVec2,
+    size: UVec2,
+    is_track: F,
+) -> Option<Vec<UVec2>>
+where
+    F: Fn(UVec2) -> bool,
+{
+    if start == destination {
+        return Some(vec![]);
+    }
+
+    if !is_track(destination) {
+        return None;
+    }
+
+    let mut previous: HashMap<UVec2, UVec2> = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    previous.insert(start, start);
+
+    while let Some(current) = queue.pop_front() {
+        if current == destination {
+            let mut path = vec![current];
+            let mut step = current;
+            while let Some(&before) = previous.get(&step) {
+                if before == start {
+                    break;
+                }
+                path.push(before);
+                step = before;
+            }
+            path.reverse();
+
+            return Some(path);
+        }
+
+        let mut neighbors = vec![];
+        if current.y + 1 < size.y {
+            neighbors.push(UVec2::new(current.x, current.y + 1));
+        }
+        if current.y > 0 {
+            neighbors.push(UVec2::new(current.x, current.y - 1));
+        }
+        if current.x > 0 {
+            neighbors.push(UVec2::new(current.x - 1, current.y));
+        }
+        if current.x + 1 < size.x {
+            neighbors.push(UVec2::new(current.x + 1, current.y));
+        }
+
+        for neighbor in neighbors {
+            if !previous.contains_key(&neighbor) && is_track(neighbor) {
+                previous.insert(neighbor, current);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    None
+}
+
+pub fn calculate_destination(
+    mut commands: Commands,
+    mut train_query: Query<(Entity, &Destination, &Position, &mut Train)>,
+    track_query: Query<(), (With<Track>, Without<UnderConstruction>)>,
+    map_query: MapQuery,
+) {
+    if train_query.iter().next().is_none() {
+        return;
+    }
+
+    let (_entity, layer) = map_query.get_layer(MAP_ID, BUILDING_LAYER_ID).unwrap();
+    let size = layer.get_layer_size_in_tiles();
+
+    let is_track = |pos: UVec2| match map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
+        Ok(entity) => track_query.get(entity).is_ok(),
+        Err(_) => false,
+    };
+
+    for (entity, destination, position, mut train) in train_query.iter_mut() {
+        commands.entity(entity).remove::<Destination>();
+
+        match find_path(position.position, destination.destination, size, &is_track) {
+            Some(waypoints) if !waypoints.is_empty() => {
+                commands.entity(entity).insert(Waypoints::new(waypoints));
+            }
+            Some(_) => {}
+            None => {
+                // stop the train, otherwise its orders retry the same route every tick
+                log::error!("No rail path found for train!");
+                train.active = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    assets::{Direction, Position},
+    car::Waypoints,
+    construction::UnderConstruction,
+    rail::Track,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+};
+
+use super::Train;
+
+pub fn drive_to_destination(
+    mut commands: Commands,
+    mut train_query: Query<(Entity, &mut Train, &mut Position, &mut Waypoints)>,
+    track_query: Query<(), (With<Track>, Without<UnderConstruction>)>,
+    map_query: MapQuery,
+) {
+    let mut train_positions: HashSet<UVec2> = train_query
+        .iter_mut()
+        .map(|(_, _, position, _)| position.position)
+        .collect();
+
+    for (entity, mut train, mut position, mut waypoints) in train_query.iter_mut() {
+        let next = match waypoints.waypoints.first() {
+            Some(next) => *next,
+            None => {
+                commands.entity(entity).remove::<Waypoints>();
+                continue;
+            }
+        };
+
+        let on_track = match map_query.get_tile_entity(next, MAP_ID, BUILDING_LAYER_ID) {
+            Ok(tile_entity) => track_query.get(tile_entity).is_ok(),
+            Err(_) => false,
+        };
+
+        // track got removed, the next order will calculate a new route
+        if !on_track {
+            commands.entity(entity).remove::<Waypoints>();
+            continue;
+        }
+
+        if train_positions.contains(&next) {
+            waypoints.mark_blocked();
+            continue;
+        }
+        waypoints.mark_unblocked();
+
+        let current = position.position;
+        train.direction = if next.x < current.x {
+            Direction::West
+        } else if next.x > current.x {
+            Direction::East
+        } else if next.y < current.y {
+            Direction::South
+        } else {
+            Direction::North
+        };
+
+        train_positions.remove(&current);
+        train_positions.insert(next);
+        position.position = next;
+
+        waypoints.waypoints.remove(0);
+        if waypoints.waypoints.is_empty() {
+            commands.entity(entity).remove::<Waypoints>();
+        }
+    }
+}
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests;
+
+pub mod calculate_destination;
+pub mod drive_to_destination;
+pub mod orders;
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+use bevy_egui::egui::Ui;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    account::PurchaseCost,
+    assets::{resource_specifications::ResourceSpecifications, Direction, InfoUI, Position},
+    constants::{
+        MapTile, CAR_DRIVE_TICK_SPEED, TILE_MAP_HEIGHT, TILE_MAP_WIDTH, TILE_SIZE, UNIT,
+        WAGON_STORAGE_SIZE, Z_CAR,
+    },
+    storage::Storage,
+};
+
+pub use calculate_destination::calculate_destination;
+
+const LOCOMOTIVE_PRICE: i64 = 2500;
+const WAGON_PRICE: i64 = 400;
+pub const MAX_WAGONS: usize = 8;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum TrainOrder {
+    GoTo(UVec2),
+    Load(String),
+    Unload(String),
+}
+
+impl TrainOrder {
+    pub fn format(&self, resources: &ResourceSpecifications) -> String {
+        match self {
+            TrainOrder::GoTo(position) => format!("Go to {}", position),
+            TrainOrder::Load(resource) => {
+                format!("Load {:?}", resources.get(resource).unwrap().name)
+            }
+            TrainOrder::Unload(resource) => {
+                format!("Unload {:?}", resources.get(resource).unwrap().name)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Train {
+    pub direction: Direction,
+    pub wagons: Vec<Storage>,
+    pub orders: Vec<TrainOrder>,
+    pub current_order: usize,
+    pub active: bool,
+}
+
+impl Default for Train {
+    fn default() -> Self {
+        Self {
+            direction: Direction::North,
+            wagons: vec![],
+            orders: vec![],
+            current_order: 0,
+            active: false,
+        }
+    }
+}
+
+impl Train {
+    // liquids travel in tank wagons
+    pub fn new_wagon(resource: &str, resources: &ResourceSpecifications) -> Storage {
+        Storage {
+            capacity: WAGON_STORAGE_SIZE,
+            ..Storage::for_resource(resource, resources)
+        }
+    }
+
+    pub fn wagon_price(wagon: &Storage, resources: &ResourceSpecifications) -> i64 {
+        let resource = resources
+            .get(&wagon.resource)
+            .unwrap_or_else(|| panic!("expected to find resource {} in spec", wagon.resource));
+
+        ((resource.cost * wagon.capacity) / 100.0) as i64 + WAGON_PRICE
+    }
+
+    pub fn next_order(&mut self) {
+        if self.orders.is_empty() {
+            self.current_order = 0;
+        } else {
+            self.current_order = (self.current_order + 1) % self.orders.len();
+        }
+    }
+}
+
+impl PurchaseCost for Train {
+    fn price(&self, resources: &ResourceSpecifications) -> i64 {
+        self.wagons.iter().fold(LOCOMOTIVE_PRICE, |sum, wagon| {
+            sum + Train::wagon_price(wagon, resources)
+        })
+    }
+}
+
+impl InfoUI for Train {
+    fn ui(&self, ui: &mut Ui, resources: &ResourceSpecifications) {
+        ui.horizontal(|ui| {
+            ui.label(format!("Train ({} wagons)", self.wagons.len()));
+        });
+
+        for wagon in self.wagons.iter() {
+            let resource = resources.get(&wagon.resource).unwrap();
+
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Wagon: {} {:.2}{} / {:.2}{}",
+                    resource.name, wagon.amount, UNIT, wagon.capacity, UNIT,
+                ));
+            });
+        }
+    }
+}
+
+fn update_train_sprite(
+    sprite: &mut TextureAtlasSprite,
+    transform: &mut Transform,
+    train: &Train,
+    position: &Vec2,
+) {
+    let position = Vec2::new(position.x + 0.5, position.y + 0.5);
+    transform.translation = (position * TILE_SIZE).extend(Z_CAR);
+
+    sprite.index = if train.direction == Direction::North || train.direction == Direction::South {
+        MapTile::TrainVertical
+    } else {
+        MapTile::TrainHorizontal
+    } as u32;
+    sprite.flip_x = train.direction == Direction::West;
+    sprite.flip_y = train.direction == Direction::South;
+}
+
+pub fn spawn_train(
+    mut commands: Commands,
+    train_query: Query<(Entity, &Train, &Position), Without<TextureAtlasSprite>>,
+    assets: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    for (entity, train, position) in train_query.iter() {
+        let texture_handle = assets.load("oligarchy_tiles.png");
+        let texture_atlas = TextureAtlas::from_grid(
+            texture_handle,
+            Vec2::splat(TILE_SIZE),
+            TILE_MAP_WIDTH as usize,
+            TILE_MAP_HEIGHT as usize,
+        );
+        let texture_atlas_handle = texture_atlases.add(texture_atlas);
+
+        let mut sprite = TextureAtlasSprite::new(0);
+        let mut transform = Transform::default();
+        update_train_sprite(
+            &mut sprite,
+            &mut transform,
+            train,
+            &position.position.as_f32(),
+        );
+
+        commands.entity(entity).insert_bundle(SpriteSheetBundle {
+            texture_atlas: texture_atlas_handle,
+            transform,
+            sprite,
+            ..Default::default()
+        });
+    }
+}
+
+pub fn update_train(
+    time: Res<Time>,
+    mut train_query: Query<(&Train, &Position, &mut Transform, &mut TextureAtlasSprite)>,
+) {
+    for (train, position, mut transform, mut sprite) in train_query.iter_mut() {
+        let current = transform.translation.xy() / TILE_SIZE - Vec2::new(0.5, 0.5);
+        let diff = position.position.as_f32() - current;
+
+        let ref_position = if diff.length() < 0.05 || diff.length() > 1.5 {
+            position.position.as_f32()
+        } else {
+            current + diff.normalize() / CAR_DRIVE_TICK_SPEED as f32 * time.delta_seconds()
+        };
+
+        update_train_sprite(&mut sprite, &mut transform, train, &ref_position);
+    }
+}
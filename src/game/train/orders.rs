@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::game::{
+    assets::{resource_specifications::ResourceSpecifications, Position},
+    car::{Destination, Waypoints},
+    construction::UnderConstruction,
+    production::TrainStation,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    storage::{
+        amount_in_storage, distribute_to_storage, fetch_from_storage, space_in_storage, Storage,
+        StorageConsolidator,
+    },
+};
+
+use super::{Train, TrainOrder};
+
+const MAX_AMOUNT: f64 = 10.0;
+
+pub fn load_wagons(
+    train: &mut Train,
+    consolidator: &StorageConsolidator,
+    storage_query: &mut Query<&mut Storage>,
+    resource: &str,
+) -> bool {
+    let mut loaded = false;
+
+    for wagon in train.wagons.iter_mut() {
+        if wagon.resource != resource {
+            continue;
+        }
+
+        let amount = amount_in_storage(consolidator, storage_query, resource)
+            .min(wagon.capacity - wagon.amount)
+            .min(MAX_AMOUNT);
+
+        if amount > 0.0 && fetch_from_storage(consolidator, storage_query, resource, amount) {
+            wagon.amount += amount;
+            loaded = true;
+        }
+    }
+
+    loaded
+}
+
+pub fn unload_wagons(
+    train: &mut Train,
+    consolidator: &StorageConsolidator,
+    storage_query: &mut Query<&mut Storage>,
+    resource: &str,
+    resources: &ResourceSpecifications,
+) -> bool {
+    let mut unloaded = false;
+
+    for wagon in train.wagons.iter_mut() {
+        if wagon.resource != resource {
+            continue;
+        }
+
+        let amount = space_in_storage(consolidator, storage_query, resource, resources)
+            .min(wagon.amount)
+            .min(MAX_AMOUNT);
+
+        if amount > 0.0 {
+            distribute_to_storage(consolidator, storage_query, resource, amount, resources);
+            wagon.amount -= amount;
+            unloaded = true;
+        }
+    }
+
+    unloaded
+}
+
+pub fn train_orders(
+    mut commands: Commands,
+    mut train_query: Query<
+        (Entity, &mut Train, &Position),
+        (Without<Destination>, Without<Waypoints>),
+    >,
+    station_query: Query<&StorageConsolidator, (With<TrainStation>, Without<UnderConstruction>)>,
+    mut storage_query: Query<&mut Storage>,
+    map_query: MapQuery,
+    resources: Res<ResourceSpecifications>,
+) {
+    for (entity, mut train, position) in train_query.iter_mut() {
+        if !train.active || train.orders.is_empty() {
+            continue;
+        }
+
+        if train.current_order >= train.orders.len() {
+            train.current_order = 0;
+        }
+
+        let station = match map_query.get_tile_entity(position.position, MAP_ID, BUILDING_LAYER_ID)
+        {
+            Ok(tile_entity) => station_query.get(tile_entity).ok(),
+            Err(_) => None,
+        };
+
+        // keep loading / unloading until nothing moves anymore
+        let busy = match train.orders[train.current_order].clone() {
+            TrainOrder::GoTo(destination) => {
+                let arrived = position.position == destination;
+                if !arrived {
+                    commands.entity(entity).insert(Destination { destination });
+                }
+                !arrived
+            }
+            TrainOrder::Load(resource) => match station {
+                Some(consolidator) => {
+                    load_wagons(&mut train, consolidator, &mut storage_query, &resource)
+                }
+                None => false,
+            },
+            TrainOrder::Unload(resource) => match station {
+                Some(consolidator) => unload_wagons(
+                    &mut train,
+                    consolidator,
+                    &mut storage_query,
+                    &resource,
+                    &resources,
+                ),
+                None => false,
+            },
+        };
+
+        if !busy {
+            train.next_order();
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use super::{
+    calculate_destination::find_path,
+    orders::{load_wagons, unload_wagons},
+    Train,
+};
+use crate::game::{
+    assets::resource_specifications::{ResourceSpecification, ResourceSpecifications},
+    storage::{Storage, StorageConsolidator},
+};
+
+const COAL: &str = "coal";
+const MILK: &str = "milk";
+
+fn track(positions: &[(u32, u32)]) -> HashSet<UVec2> {
+    positions.iter().map(|(x, y)| UVec2::new(*x, *y)).collect()
+}
+
+#[test]
+fn path_follows_track() {
+    let track = track(&[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+
+    let path = find_path(
+        UVec2::new(0, 0),
+        UVec2::new(2, 2),
+        UVec2::new(10, 10),
+        |pos| track.contains(&pos),
+    );
+
+    assert_eq!(
+        path,
+        Some(vec![
+            UVec2::new(1, 0),
+            UVec2::new(2, 0),
+            UVec2::new(2, 1),
+            UVec2::new(2, 2)
+        ])
+    );
+}
+
+#[test]
+fn no_path_without_track() {
+    let track = track(&[(0, 0), (1, 0), (2, 2)]);
+
+    let path = find_path(
+        UVec2::new(0, 0),
+        UVec2::new(2, 2),
+        UVec2::new(10, 10),
+        |pos| track.contains(&pos),
+    );
+
+    assert_eq!(path, None);
+}
+
+#[test]
+fn empty_path_when_already_there() {
+    let path = find_path(
+        UVec2::new(3, 3),
+        UVec2::new(3, 3),
+        UVec2::new(10, 10),
+        |_| false,
+    );
+
+    assert_eq!(path, Some(vec![]));
+}
+
+struct TestSetup {
+    world: World,
+    storage_id: Entity,
+    train_id: Entity,
+}
+
+impl TestSetup {
+    fn new(storage_amount: f64, wagon_amount: f64) -> Self {
+        let mut world = World::default();
+
+        let storage_id = world
+            .spawn()
+            .insert(Storage {
+                resource: COAL.to_owned(),
+                amount: storage_amount,
+                capacity: 20.0,
+                ..Default::default()
+            })
+            .id();
+
+        world.spawn().insert(StorageConsolidator {
+            connected_storage: vec![storage_id],
+            ..Default::default()
+        });
+
+        let mut wagon = Train::new_wagon(COAL, &ResourceSpecifications::new());
+        wagon.amount = wagon_amount;
+
+        let train_id = world
+            .spawn()
+            .insert(Train {
+                wagons: vec![wagon],
+                ..Default::default()
+            })
+            .id();
+
+        Self {
+            world,
+            storage_id,
+            train_id,
+        }
+    }
+
+    fn run(&mut self, stage: &mut SystemStage) {
+        stage.run(&mut self.world);
+    }
+
+    fn assert_amounts(&self, storage: f64, wagon: f64) {
+        let storage_amount = self.world.get::<Storage>(self.storage_id).unwrap().amount;
+        let wagon_amount = self.world.get::<Train>(self.train_id).unwrap().wagons[0].amount;
+
+        assert!(
+            (storage_amount - storage).abs() < f64::EPSILON,
+            "expected storage to be {} but was {}",
+            storage,
+            storage_amount
+        );
+        assert!(
+            (wagon_amount - wagon).abs() < f64::EPSILON,
+            "expected wagon to be {} but was {}",
+            wagon,
+            wagon_amount
+        );
+    }
+}
+
+fn load_system(
+    mut train_query: Query<&mut Train>,
+    consolidator_query: Query<&StorageConsolidator>,
+    mut storage_query: Query<&mut Storage>,
+) {
+    for mut train in train_query.iter_mut() {
+        for consolidator in consolidator_query.iter() {
+            load_wagons(&mut train, consolidator, &mut storage_query, COAL);
+        }
+    }
+}
+
+fn unload_system(
+    mut train_query: Query<&mut Train>,
+    consolidator_query: Query<&StorageConsolidator>,
+    mut storage_query: Query<&mut Storage>,
+) {
+    for mut train in train_query.iter_mut() {
+        for consolidator in consolidator_query.iter() {
+            unload_wagons(
+                &mut train,
+                consolidator,
+                &mut storage_query,
+                COAL,
+                &ResourceSpecifications::new(),
+            );
+        }
+    }
+}
+
+#[test]
+fn loads_wagons_from_station() {
+    let mut setup = TestSetup::new(15.0, 0.0);
+    let mut stage = SystemStage::parallel();
+    stage.add_system(load_system.system());
+
+    setup.run(&mut stage);
+    setup.assert_amounts(5.0, 10.0);
+
+    setup.run(&mut stage);
+    setup.assert_amounts(0.0, 15.0);
+}
+
+#[test]
+fn unloads_wagons_into_station() {
+    let mut setup = TestSetup::new(15.0, 30.0);
+    let mut stage = SystemStage::parallel();
+    stage.add_system(unload_system.system());
+
+    setup.run(&mut stage);
+    setup.assert_amounts(20.0, 25.0);
+}
+
+#[test]
+fn liquids_get_tank_wagons() {
+    let mut resources = ResourceSpecifications::new();
+    resources.insert(
+        MILK.to_owned(),
+        ResourceSpecification {
+            liquid: true,
+            ..Default::default()
+        },
+    );
+
+    let wagon = Train::new_wagon(MILK, &resources);
+
+    assert!(wagon.is_tank());
+    assert!(wagon.accepts(MILK, &resources));
+    assert!(!Train::new_wagon(COAL, &resources).accepts(MILK, &resources));
+}
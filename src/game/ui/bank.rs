@@ -0,0 +1,99 @@
+use crate::game::{
+    account::{Account, AccountTransaction, TransactionCategory},
+    bank::{Bank, LOAN_OFFERS},
+    constants::{CURRENCY, DAY_LENGTH, PRODUCTION_TICK_SPEED},
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use num_format::{Locale, ToFormattedString};
+
+#[derive(Default)]
+pub struct BankWindow {
+    pub open: bool,
+}
+
+const TICKS_PER_DAY: f64 = DAY_LENGTH / PRODUCTION_TICK_SPEED;
+
+fn money(amount: i64) -> String {
+    format!("{} {}", amount.to_formatted_string(&Locale::en), CURRENCY)
+}
+
+pub fn bank_ui(
+    egui_context: ResMut<EguiContext>,
+    mut window: ResMut<BankWindow>,
+    mut bank: ResMut<Bank>,
+    account: Res<Account>,
+    mut events: EventWriter<AccountTransaction>,
+) {
+    let mut open = window.open;
+
+    egui::Window::new("Bank")
+        .open(&mut open)
+        .show(egui_context.ctx(), |ui| {
+            egui::Grid::new("bank_credit").show(ui, |ui| {
+                ui.label("Credit limit");
+                ui.label(money(bank.credit_limit));
+                ui.end_row();
+
+                ui.label("Available");
+                ui.label(money(bank.available(account.value)));
+                ui.end_row();
+
+                ui.label("Debt");
+                ui.label(money(bank.debt()));
+                ui.end_row();
+            });
+
+            ui.separator();
+            ui.heading("Loans");
+
+            let mut repay = None;
+            egui::Grid::new("bank_loans").show(ui, |ui| {
+                for (index, loan) in bank.loans.iter().enumerate() {
+                    ui.label(money(loan.amount));
+                    ui.label(format!(
+                        "{:.0} {} per day",
+                        loan.interest_per_tick() * TICKS_PER_DAY,
+                        CURRENCY
+                    ));
+                    if ui.button("Repay").clicked() {
+                        repay = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+
+            if let Some(amount) = repay.and_then(|index| bank.repay(index, account.value)) {
+                events.send(AccountTransaction {
+                    amount: -amount,
+                    category: TransactionCategory::Loan,
+                    source: None,
+                });
+            }
+
+            ui.separator();
+            ui.heading("Offers");
+
+            let mut take = None;
+            egui::Grid::new("bank_offers").show(ui, |ui| {
+                for (index, (amount, interest)) in LOAN_OFFERS.iter().enumerate() {
+                    ui.label(money(*amount));
+                    ui.label(format!("{:.1}% per day", interest * TICKS_PER_DAY * 100.0));
+                    if bank.can_borrow(*amount) && ui.button("Take").clicked() {
+                        take = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+
+            if let Some(amount) = take.and_then(|index| bank.take_loan(index)) {
+                events.send(AccountTransaction {
+                    amount,
+                    category: TransactionCategory::Loan,
+                    source: None,
+                });
+            }
+        });
+
+    window.open = open;
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use num_format::{Locale, ToFormattedString};
+
+use crate::game::{
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications,
+    },
+    blueprint::BlueprintManager,
+    constants::CURRENCY,
+    current_tool::{SelectedTool, Tool},
+};
+
+pub fn blueprint_ui(
+    egui_context: ResMut<EguiContext>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut manager: ResMut<BlueprintManager>,
+    mut name: Local<String>,
+    buildings: Res<BuildingSpecifications>,
+    resources: Res<ResourceSpecifications>,
+) {
+    if selected_tool.tool != Tool::BlueprintSelect && selected_tool.tool != Tool::BlueprintPaste {
+        return;
+    }
+
+    let mut save = false;
+    let mut selected = None;
+    let mut deleted = None;
+
+    egui::Window::new("Blueprints").show(egui_context.ctx(), |ui| {
+        if ui.small_button("Copy Area").clicked() {
+            selected_tool.tool = Tool::BlueprintSelect;
+        }
+
+        if selected_tool.tool == Tool::BlueprintSelect {
+            ui.label("Drag over an area to copy it.");
+        }
+
+        if let Some(blueprint) = &manager.current {
+            ui.separator();
+            ui.label(format!(
+                "{} buildings on {}x{} tiles",
+                blueprint.entities.len(),
+                blueprint.size.x,
+                blueprint.size.y
+            ));
+            ui.label(format!(
+                "Total Cost: {} {}",
+                blueprint
+                    .price(&buildings, &resources)
+                    .to_formatted_string(&Locale::en),
+                CURRENCY
+            ));
+
+            ui.horizontal(|ui| {
+                if ui.small_button("Paste").clicked() {
+                    selected_tool.tool = Tool::BlueprintPaste;
+                }
+                if ui.small_button("Rotate (R)").clicked() {
+                    selected_tool.rotation = (selected_tool.rotation + 1) % 4;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut *name);
+                if ui.small_button("Save").clicked() && !name.is_empty() {
+                    save = true;
+                }
+            });
+        }
+
+        ui.separator();
+        ui.heading("Saved");
+
+        if manager.blueprints.is_empty() {
+            ui.label("No blueprints saved yet.");
+        }
+
+        egui::containers::ScrollArea::from_max_height(200.0).show(ui, |ui| {
+            egui::Grid::new("blueprints").show(ui, |ui| {
+                for (index, blueprint) in manager.blueprints.iter().enumerate() {
+                    ui.label(&blueprint.name);
+                    if ui.small_button("Use").clicked() {
+                        selected = Some(index);
+                    }
+                    if ui.small_button("Delete").clicked() {
+                        deleted = Some(blueprint.name.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    });
+
+    if save {
+        if let Some(mut blueprint) = manager.current.clone() {
+            blueprint.name = name.clone();
+            manager.save_blueprint(blueprint);
+            name.clear();
+        }
+    }
+
+    if let Some(index) = selected {
+        manager.current = manager.blueprints.get(index).cloned();
+        selected_tool.tool = Tool::BlueprintPaste;
+        selected_tool.rotation = 0;
+    }
+
+    if let Some(name) = deleted {
+        manager.delete_blueprint(&name);
+    }
+}
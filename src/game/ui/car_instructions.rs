@@ -6,6 +6,7 @@ use crate::game::{
     car::{Car, CarController, CarInstructions, Destination, UserController, Waypoints},
     current_selection::CurrentlySelected,
     highlight_tiles::HighlightTilesUpdateEvent,
+    history::{EditCommand, EditHistory},
     storage::Storage,
 };
 
@@ -33,6 +34,7 @@ pub fn program_ui(
     clicked_tile: Res<ClickedTile>,
     resources: Res<ResourceSpecifications>,
     mut highlight: EventWriter<HighlightTilesUpdateEvent>,
+    mut history: ResMut<EditHistory>,
 ) {
     let mut open = false;
 
@@ -81,6 +83,11 @@ pub fn program_ui(
                         }
 
                         if let Ok((mut car, _storage, _position)) = car_query.get_mut(entity) {
+                            history.push(EditCommand::CarProgram {
+                                car: entity,
+                                before: car.controller.clone(),
+                                after: controller.clone(),
+                            });
                             car.controller = controller;
                         }
                     }
@@ -103,6 +110,15 @@ pub fn program_ui(
         if let Some(selected_index) = edit_instruction.index {
             let instruction = car_controller.instructions[selected_index].clone();
 
+            // loading is only offered for resources the car can carry
+            let loadable = {
+                let (_car, storage, _position) = car_query.get_mut(entity).unwrap();
+                edit_instruction
+                    .resource
+                    .as_ref()
+                    .map_or(false, |resource| storage.accepts(resource, &resources))
+            };
+
             if let Some(pos) = clicked_tile.pos {
                 if edit_instruction.select_mode {
                     car_controller.instructions[selected_index] = CarInstructions::GoTo(pos);
@@ -145,7 +161,7 @@ pub fn program_ui(
                                 }
                             }
 
-                            if ui.button("Load").clicked() {
+                            if ui.add(egui::Button::new("Load").enabled(loadable)).clicked() {
                                 if let Some(resource) = &edit_instruction.resource {
                                     car_controller.instructions[selected_index] =
                                         CarInstructions::Load(resource.clone());
@@ -155,7 +171,8 @@ pub fn program_ui(
                                 }
                             }
 
-                            if ui.button("Wait for Load").clicked() {
+                            let wait_for_load = egui::Button::new("Wait for Load").enabled(loadable);
+                            if ui.add(wait_for_load).clicked() {
                                 if let Some(resource) = &edit_instruction.resource {
                                     car_controller.instructions[selected_index] =
                                         CarInstructions::WaitForLoad(resource.clone());
@@ -280,8 +297,14 @@ pub fn program_ui(
 
         if car_controller_modified {
             let (mut car, _storage, _position) = car_query.get_mut(entity).unwrap();
+            let controller = CarController::UserControlled(car_controller);
 
-            car.controller = CarController::UserControlled(car_controller);
+            history.push(EditCommand::CarProgram {
+                car: entity,
+                before: car.controller.clone(),
+                after: controller.clone(),
+            });
+            car.controller = controller;
         }
     }
 
@@ -11,15 +11,28 @@ use crate::game::{
     assets::{
         building_specifications::{BuildingSpecification, BuildingSpecifications},
         resource_specifications::{ResourceSpecification, ResourceSpecifications},
+        Direction,
     },
     car::Car,
     constants::CURRENCY,
-    current_tool::{SelectedTool, Tool},
+    construction::{ConstructionSettings, MARKET_MARKUP},
+    conveyor::Conveyor,
+    current_tool::{
+        bulldoze::{BulldozePreview, SALVAGE_DISCOUNT},
+        SelectedTool, Tool,
+    },
+    history::{EditHistory, HistoryEvent},
+    market::Market,
+    pipeline::Pipeline,
     production::{
         DeliveryStation, Depot, ImportExportDirection, ImportExportStation, StorageManagement,
+        TownShop, TrainStation,
     },
+    rail::Rail,
+    scenario::ScenarioManager,
     storage::Storage,
     street::{Street, StreetType},
+    train::Train,
 };
 
 fn button(
@@ -47,6 +60,22 @@ fn button(
     button.on_hover_text(hover_text)
 }
 
+fn storage_name(resource: &ResourceSpecification) -> String {
+    if resource.liquid {
+        format!("{} Tank", resource.name)
+    } else {
+        format!("{} Storage", resource.name)
+    }
+}
+
+fn vehicle_name(resource: &ResourceSpecification) -> String {
+    if resource.liquid {
+        format!("{} Tanker", resource.name)
+    } else {
+        format!("{} Truck", resource.name)
+    }
+}
+
 pub struct Filter(String);
 
 impl Filter {
@@ -69,6 +98,12 @@ pub fn construction_ui(
     account: Res<Account>,
     windows: Res<Windows>,
     mut filter: Local<Filter>,
+    market: Res<Market>,
+    scenarios: Res<ScenarioManager>,
+    mut settings: ResMut<ConstructionSettings>,
+    history: Res<EditHistory>,
+    mut history_events: EventWriter<HistoryEvent>,
+    bulldoze_preview: Res<BulldozePreview>,
 ) {
     let win = windows.get_primary().expect("no primary window");
     let max_height = win.height() * 0.75;
@@ -96,8 +131,75 @@ pub fn construction_ui(
                 if ui.small_button("Bulldoze").clicked() {
                     selected_tool.tool = Tool::Bulldoze;
                 }
+                if ui.small_button("Blueprints").clicked() {
+                    selected_tool.tool = Tool::BlueprintSelect;
+                }
+                let undo = egui::Button::new("Undo")
+                    .small()
+                    .enabled(history.can_undo());
+                if ui.add(undo).on_hover_text("Ctrl+Z").clicked() {
+                    history_events.send(HistoryEvent::Undo);
+                }
+                let redo = egui::Button::new("Redo")
+                    .small()
+                    .enabled(history.can_redo());
+                if ui.add(redo).on_hover_text("Ctrl+Y").clicked() {
+                    history_events.send(HistoryEvent::Redo);
+                }
             });
 
+            if selected_tool.tool == Tool::Bulldoze {
+                ui.checkbox(&mut settings.salvage, "Salvage goods to adjacent storage")
+                    .on_hover_text(format!(
+                        "Stored goods which are not salvaged are sold at {}% of the market price",
+                        SALVAGE_DISCOUNT * 100.0
+                    ));
+                if selected_tool.drag.is_some() {
+                    ui.label(format!(
+                        "Removes {} buildings, refund {} {}",
+                        bulldoze_preview.buildings,
+                        bulldoze_preview.refund.to_formatted_string(&Locale::en),
+                        CURRENCY
+                    ));
+                } else {
+                    ui.label("Drag to bulldoze an area");
+                }
+            }
+
+            ui.separator();
+            ui.checkbox(&mut settings.deliveries, "Build from adjacent storage")
+                .on_hover_text("Construction sites use materials delivered to their storage");
+            if settings.deliveries {
+                ui.checkbox(
+                    &mut settings.market_fallback,
+                    format!("Buy missing materials ({}x price)", MARKET_MARKUP),
+                );
+            }
+
+            let ports = match &selected_tool.tool {
+                Tool::Building(id) => buildings
+                    .get(id)
+                    .and_then(|building| building.ports.clone()),
+                _ => None,
+            };
+
+            if let Some(ports) = ports {
+                let sides = |directions: &Vec<Direction>| -> String {
+                    directions
+                        .iter()
+                        .map(|direction| format!("{:?}", direction.rotated(selected_tool.rotation)))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                };
+
+                ui.separator();
+                ui.label(format!("Inputs: {}", sides(&ports.inputs)));
+                ui.label(format!("Outputs: {}", sides(&ports.outputs)));
+                if ui.small_button("Rotate (R)").clicked() {
+                    selected_tool.rotation = (selected_tool.rotation + 1) % 4;
+                }
+            }
+
             ui.separator();
 
             egui::containers::ScrollArea::from_max_height(max_height).show(ui, |ui| {
@@ -107,8 +209,15 @@ pub fn construction_ui(
                     "Export Station",
                     "Import Station",
                     "Delivery Station",
+                    "Town Shop",
                     "Depot",
                     "Storage Management",
+                    "Warehouse",
+                    "Rail",
+                    "Train Station",
+                    "Locomotive",
+                    "Conveyor",
+                    "Pipeline",
                 ]
                 .into_iter()
                 .any(|item| filter.match_name(item))
@@ -194,11 +303,24 @@ pub fn construction_ui(
                                 selected_tool.tool = Tool::DeliveryStation;
                             }
 
+                            if filter.match_name("Town Shop")
+                                && button(
+                                    ui,
+                                    "Town Shop",
+                                    &TownShop::default(),
+                                    &resources,
+                                    &account,
+                                )
+                                .clicked()
+                            {
+                                selected_tool.tool = Tool::TownShop;
+                            }
+
                             if filter.match_name("Storage Management")
                                 && button(
                                     ui,
                                     "Storage Management",
-                                    &StorageManagement,
+                                    &StorageManagement::default(),
                                     &resources,
                                     &account,
                                 )
@@ -206,6 +328,58 @@ pub fn construction_ui(
                             {
                                 selected_tool.tool = Tool::StorageManagement;
                             }
+
+                            if filter.match_name("Warehouse")
+                                && button(
+                                    ui,
+                                    "Warehouse",
+                                    &Storage::new_warehouse(),
+                                    &resources,
+                                    &account,
+                                )
+                                .clicked()
+                            {
+                                selected_tool.tool = Tool::Warehouse;
+                            }
+
+                            if filter.match_name("Rail")
+                                && button(ui, "Rail", &Rail, &resources, &account).clicked()
+                            {
+                                selected_tool.tool = Tool::Rail;
+                            }
+
+                            if filter.match_name("Train Station")
+                                && button(ui, "Train Station", &TrainStation, &resources, &account)
+                                    .clicked()
+                            {
+                                selected_tool.tool = Tool::TrainStation;
+                            }
+
+                            if filter.match_name("Locomotive")
+                                && button(ui, "Locomotive", &Train::default(), &resources, &account)
+                                    .clicked()
+                            {
+                                selected_tool.tool = Tool::Train;
+                            }
+
+                            if filter.match_name("Conveyor")
+                                && button(
+                                    ui,
+                                    "Conveyor",
+                                    &Conveyor::default(),
+                                    &resources,
+                                    &account,
+                                )
+                                .clicked()
+                            {
+                                selected_tool.tool = Tool::Conveyor;
+                            }
+
+                            if filter.match_name("Pipeline")
+                                && button(ui, "Pipeline", &Pipeline, &resources, &account).clicked()
+                            {
+                                selected_tool.tool = Tool::Pipeline;
+                            }
                         });
                     });
 
@@ -221,6 +395,10 @@ pub fn construction_ui(
 
                 let mut groups = CollectingHashMap::new();
                 for (id, building) in buildings.iter() {
+                    if !scenarios.allows_building(id) {
+                        continue;
+                    }
+
                     groups.insert(building.group.clone(), (id, building));
                 }
 
@@ -288,7 +466,7 @@ pub fn construction_ui(
                         .unwrap()
                         .iter()
                         .filter(|(_id, resource)| {
-                            let name = format!("{} Storage", resource.name);
+                            let name = storage_name(resource);
                             filter.match_name(&name)
                         })
                         .collect();
@@ -299,13 +477,48 @@ pub fn construction_ui(
                         let items: Box<dyn FnOnce(&mut Ui)> = Box::new(|ui| {
                             ui.vertical_centered_justified(|ui| {
                                 for (id, resource) in resource_list.iter() {
-                                    let name = format!("{} Storage", resource.name);
+                                    let name = storage_name(resource);
 
+                                    if button(
+                                        ui,
+                                        &name,
+                                        &Storage::for_resource(id, &resources),
+                                        &resources,
+                                        &account,
+                                    )
+                                    .clicked()
+                                    {
+                                        selected_tool.tool = Tool::Storage(id.to_string());
+                                    }
+
+                                    if resource.liquid {
+                                        continue;
+                                    }
+
+                                    let silo_name = format!("{} Silo", resource.name);
+                                    if button(
+                                        ui,
+                                        &silo_name,
+                                        &Storage::new_silo(id),
+                                        &resources,
+                                        &account,
+                                    )
+                                    .clicked()
+                                    {
+                                        selected_tool.tool = Tool::Silo(id.to_string());
+                                    }
+
+                                    if resource.perishable.is_none() {
+                                        continue;
+                                    }
+
+                                    let name = format!("Refrigerated {}", name);
                                     if button(
                                         ui,
                                         &name,
                                         &Storage {
                                             resource: id.to_string(),
+                                            refrigerated: true,
                                             ..Default::default()
                                         },
                                         &resources,
@@ -313,7 +526,8 @@ pub fn construction_ui(
                                     )
                                     .clicked()
                                     {
-                                        selected_tool.tool = Tool::Storage(id.to_string());
+                                        selected_tool.tool =
+                                            Tool::RefrigeratedStorage(id.to_string());
                                     }
                                 }
                             });
@@ -336,7 +550,7 @@ pub fn construction_ui(
                         .unwrap()
                         .iter()
                         .filter(|(_id, resource)| {
-                            let name = format!("{} Truck", resource.name);
+                            let name = vehicle_name(resource);
                             filter.match_name(&name)
                         })
                         .collect();
@@ -347,7 +561,7 @@ pub fn construction_ui(
                         let items: Box<dyn FnOnce(&mut Ui)> = Box::new(|ui| {
                             ui.vertical_centered_justified(|ui| {
                                 for (id, resource) in resource_list.iter() {
-                                    let name = format!("{} Truck", resource.name);
+                                    let name = vehicle_name(resource);
 
                                     if button(
                                         ui,
@@ -380,6 +594,45 @@ pub fn construction_ui(
                         }
                     }
                 }
+
+                let mut prices: Vec<(&String, &String)> = resources
+                    .iter()
+                    .filter(|(_id, resource)| {
+                        resource.cost > f64::EPSILON
+                            && !resource.virtual_resource
+                            && filter.match_name(&resource.name)
+                    })
+                    .map(|(id, resource)| (id, &resource.name))
+                    .collect();
+                prices.sort_by_key(|(_id, name)| name.to_lowercase());
+
+                if !prices.is_empty() {
+                    egui::CollapsingHeader::new("Market Prices")
+                        .default_open(open_groups)
+                        .show(ui, |ui| {
+                            egui::Grid::new("market_prices").show(ui, |ui| {
+                                ui.label("");
+                                ui.label("Export");
+                                ui.label("Import");
+                                ui.end_row();
+
+                                for (id, name) in prices {
+                                    ui.label(name);
+                                    ui.label(format!(
+                                        "{:.0} {}",
+                                        market.export_price(id, &resources),
+                                        CURRENCY
+                                    ));
+                                    ui.label(format!(
+                                        "{:.0} {}",
+                                        market.import_price(id, &resources),
+                                        CURRENCY
+                                    ));
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                }
             });
         });
 }
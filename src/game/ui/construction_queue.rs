@@ -0,0 +1,193 @@
+use crate::game::{
+    account::{AccountTransaction, MaintenanceCost, PurchaseCost, TransactionCategory},
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications, Building, Name, Position, RequiresUpdate,
+    },
+    constants::{CURRENCY, UNIT},
+    construction::{ConstructionSettings, UnderConstruction},
+    current_tool::bulldoze::DemolishEvent,
+    storage::Storage,
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use num_format::{Locale, ToFormattedString};
+
+#[derive(Default)]
+pub struct ConstructionQueueWindow {
+    pub open: bool,
+}
+
+enum QueueAction {
+    Swap(Entity, Entity),
+    TogglePause(Entity),
+    Cancel(Entity),
+}
+
+pub fn construction_queue_ui(
+    mut commands: Commands,
+    egui_context: ResMut<EguiContext>,
+    mut window: ResMut<ConstructionQueueWindow>,
+    mut settings: ResMut<ConstructionSettings>,
+    mut site_query: Query<(Entity, &mut UnderConstruction, Option<&Position>)>,
+    storage_query: Query<&Storage>,
+    name_queries: (Query<&Name>, Query<&Building>),
+    buildings: Res<BuildingSpecifications>,
+    resources: Res<ResourceSpecifications>,
+    mut events: EventWriter<AccountTransaction>,
+    mut demolish_events: EventWriter<DemolishEvent>,
+) {
+    let (name_query, building_query) = name_queries;
+    let mut open = window.open;
+    let mut action = None;
+
+    let site_name = |entity: Entity| {
+        if let Ok(name) = name_query.get(entity) {
+            return name.name.clone();
+        }
+
+        if let Ok(building) = building_query.get(entity) {
+            if let Some(building) = buildings.get(&building.id) {
+                return building.name.clone();
+            }
+        }
+
+        if storage_query.get(entity).is_ok() {
+            return "Storage".to_owned();
+        }
+
+        "Construction".to_owned()
+    };
+
+    let mut sites: Vec<(Entity, &UnderConstruction, Option<&Position>)> =
+        site_query.iter().collect();
+    sites.sort_by_key(|(_entity, construction, _position)| construction.order);
+
+    egui::Window::new("Construction Queue")
+        .open(&mut open)
+        .show(egui_context.ctx(), |ui| {
+            let mut limited = settings.budget.is_some();
+            ui.checkbox(&mut limited, "Limit spending per tick");
+            if let Some(budget) = &mut settings.budget {
+                ui.add(egui::Slider::new(budget, 0..=50_000).text(CURRENCY));
+            }
+            if limited != settings.budget.is_some() {
+                settings.budget = if limited { Some(5000) } else { None };
+            }
+
+            ui.separator();
+
+            if sites.is_empty() {
+                ui.label("Nothing is under construction.");
+            }
+
+            egui::containers::ScrollArea::from_max_height(300.0).show(ui, |ui| {
+                egui::Grid::new("construction_queue").show(ui, |ui| {
+                    for (index, (entity, construction, position)) in sites.iter().enumerate() {
+                        let name = match position {
+                            Some(position) => format!(
+                                "{} ({}, {})",
+                                site_name(*entity),
+                                position.position.x,
+                                position.position.y
+                            ),
+                            None => site_name(*entity),
+                        };
+                        ui.label(name);
+
+                        let mut remaining = vec![];
+                        if construction.labor() > f64::EPSILON {
+                            remaining.push(format!(
+                                "Labor {} {}",
+                                (construction.labor() as i64).to_formatted_string(&Locale::en),
+                                CURRENCY
+                            ));
+                        }
+                        for (resource, amount) in construction.resources_needed() {
+                            let name = resources
+                                .get(resource)
+                                .map(|resource| resource.name.as_str())
+                                .unwrap_or(resource);
+                            remaining.push(format!("{:.1}{} {}", amount, UNIT, name));
+                        }
+                        ui.label(remaining.join(", "));
+
+                        if index > 0 && ui.small_button("Up").clicked() {
+                            action = Some(QueueAction::Swap(*entity, sites[index - 1].0));
+                        }
+                        if index + 1 < sites.len() && ui.small_button("Down").clicked() {
+                            action = Some(QueueAction::Swap(*entity, sites[index + 1].0));
+                        }
+
+                        let pause = if construction.paused {
+                            "Resume"
+                        } else {
+                            "Pause"
+                        };
+                        if ui.small_button(pause).clicked() {
+                            action = Some(QueueAction::TogglePause(*entity));
+                        }
+
+                        if ui
+                            .small_button("Cancel")
+                            .on_hover_text(format!(
+                                "Refunds {} {}",
+                                construction.refund().to_formatted_string(&Locale::en),
+                                CURRENCY
+                            ))
+                            .clicked()
+                        {
+                            action = Some(QueueAction::Cancel(*entity));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+
+    window.open = open;
+
+    match action {
+        Some(QueueAction::Swap(entity, other)) => {
+            let order = site_query.get_mut(entity).unwrap().1.order;
+            let mut other = site_query.get_mut(other).unwrap().1;
+            let other_order = other.order;
+            other.order = order;
+            site_query.get_mut(entity).unwrap().1.order = other_order;
+        }
+        Some(QueueAction::TogglePause(entity)) => {
+            let mut construction = site_query.get_mut(entity).unwrap().1;
+            construction.paused = !construction.paused;
+        }
+        Some(QueueAction::Cancel(entity)) => {
+            let (_entity, construction, position) = site_query.get_mut(entity).unwrap();
+
+            let refund = construction.refund();
+            if refund > 0 {
+                events.send(AccountTransaction {
+                    amount: refund,
+                    category: TransactionCategory::Construction,
+                    source: Some(entity),
+                });
+            }
+
+            if construction.upgrade {
+                commands
+                    .entity(entity)
+                    .remove::<UnderConstruction>()
+                    .insert(RequiresUpdate);
+
+                if let Ok(storage) = storage_query.get(entity) {
+                    commands
+                        .entity(entity)
+                        .insert(MaintenanceCost::new_from_cost(storage.price(&resources)));
+                }
+            } else if let Some(position) = position {
+                demolish_events.send(DemolishEvent {
+                    position: position.position,
+                });
+            }
+        }
+        None => {}
+    }
+}
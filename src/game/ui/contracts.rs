@@ -0,0 +1,108 @@
+use crate::game::{
+    assets::resource_specifications::ResourceSpecifications,
+    constants::{CURRENCY, UNIT},
+    contracts::{exported_total, Contract, ContractManager, ContractState},
+    statistics::{StatisticTracker, Statistics},
+    time::GameTime,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContext,
+};
+use num_format::{Locale, ToFormattedString};
+
+fn contract_label(contract: &Contract, resources: &ResourceSpecifications) -> String {
+    let name = resources
+        .get(&contract.resource)
+        .map(|resource| resource.name.as_str())
+        .unwrap_or(&contract.resource);
+
+    format!(
+        "{}{} {}",
+        (contract.amount as i64).to_formatted_string(&Locale::en),
+        UNIT,
+        name,
+    )
+}
+
+pub fn contracts_ui(
+    egui_context: ResMut<EguiContext>,
+    query: Query<&Statistics>,
+    deleted_export_statistics: Res<StatisticTracker>,
+    mut manager: ResMut<ContractManager>,
+    time: Res<GameTime>,
+    resources: Res<ResourceSpecifications>,
+) {
+    let day = time.day();
+    let mut accepted = None;
+
+    egui::Window::new("Contracts")
+        .anchor(Align2::LEFT_BOTTOM, [10.0, -10.0])
+        .show(egui_context.ctx(), |ui| {
+            ui.heading("Open");
+            egui::Grid::new("contracts_open").show(ui, |ui| {
+                for (index, contract) in manager.with_state(ContractState::Offered) {
+                    ui.label(contract_label(contract, &resources));
+                    ui.label(format!("{:.0} days", contract.days));
+                    ui.label(format!(
+                        "+{} / -{} {}",
+                        contract.bonus.to_formatted_string(&Locale::en),
+                        contract.penalty.to_formatted_string(&Locale::en),
+                        CURRENCY,
+                    ));
+                    if ui.button("Accept").clicked() {
+                        accepted = Some((index, contract.resource.clone()));
+                    }
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            ui.heading("Active");
+            egui::Grid::new("contracts_active").show(ui, |ui| {
+                for (_index, contract) in manager.with_state(ContractState::Active) {
+                    ui.label(contract_label(contract, &resources));
+                    ui.label(format!(
+                        "{}{} delivered",
+                        (contract.delivered.max(0.0) as i64).to_formatted_string(&Locale::en),
+                        UNIT,
+                    ));
+                    ui.label(format!(
+                        "{:.0} days left",
+                        (contract.deadline - day).max(0.0).ceil()
+                    ));
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            ui.heading("Completed");
+            egui::Grid::new("contracts_completed").show(ui, |ui| {
+                for contract in manager.contracts.iter().rev() {
+                    let result = match contract.state {
+                        ContractState::Fulfilled => format!(
+                            "+{} {}",
+                            contract.bonus.to_formatted_string(&Locale::en),
+                            CURRENCY
+                        ),
+                        ContractState::Failed => format!(
+                            "-{} {}",
+                            contract.penalty.to_formatted_string(&Locale::en),
+                            CURRENCY
+                        ),
+                        _ => continue,
+                    };
+
+                    ui.label(contract_label(contract, &resources));
+                    ui.label(result);
+                    ui.end_row();
+                }
+            });
+        });
+
+    if let Some((index, resource)) = accepted {
+        let exported = exported_total(&resource, &query, &deleted_export_statistics);
+        manager.accept(index, exported, day);
+    }
+}
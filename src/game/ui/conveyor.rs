@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::game::{
+    assets::{
+        resource_specifications::ResourceSpecifications, Direction, Position, RequiresUpdate,
+    },
+    conveyor::{trace_line, Conveyor},
+    current_selection::CurrentlySelected,
+    highlight_tiles::HighlightTilesUpdateEvent,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+};
+
+pub fn edit_ui(
+    mut commands: Commands,
+    egui_context: ResMut<EguiContext>,
+    mut conveyor_query: Query<(&mut Conveyor, &Position)>,
+    currently_selected: Res<CurrentlySelected>,
+    map_query: MapQuery,
+    resources: Res<ResourceSpecifications>,
+    mut highlight: EventWriter<HighlightTilesUpdateEvent>,
+) {
+    if !currently_selected.editing {
+        return;
+    }
+
+    let entity = match currently_selected.entity {
+        Some(entity) => entity,
+        None => return,
+    };
+
+    let (mut conveyor, position) = match conveyor_query.get_mut(entity) {
+        Ok((conveyor, position)) => (conveyor.clone(), position.position),
+        Err(_) => return,
+    };
+
+    let (_entity, layer) = map_query.get_layer(MAP_ID, BUILDING_LAYER_ID).unwrap();
+    let size = layer.get_layer_size_in_tiles();
+
+    let line: Vec<Entity> = {
+        let direction_at = |pos: UVec2| -> Option<Direction> {
+            let entity = map_query
+                .get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID)
+                .ok()?;
+
+            conveyor_query
+                .get_component::<Conveyor>(entity)
+                .ok()
+                .map(|conveyor| conveyor.direction)
+        };
+
+        trace_line(position, size, direction_at)
+            .into_iter()
+            .filter_map(|pos| {
+                map_query
+                    .get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID)
+                    .ok()
+            })
+            .collect()
+    };
+
+    let mut direction_changed = false;
+    let mut resource_changed = false;
+
+    egui::Window::new("Conveyor").show(egui_context.ctx(), |ui| {
+        ui.label("Direction");
+        ui.horizontal(|ui| {
+            for (direction, label) in [
+                (Direction::North, "North"),
+                (Direction::East, "East"),
+                (Direction::South, "South"),
+                (Direction::West, "West"),
+            ]
+            .iter()
+            {
+                if ui
+                    .radio_value(&mut conveyor.direction, *direction, *label)
+                    .clicked()
+                {
+                    direction_changed = true;
+                }
+            }
+        });
+
+        ui.separator();
+
+        let label = ui.label("Resource (whole belt)");
+        if label.hovered() {
+            let positions = line
+                .iter()
+                .filter_map(|entity| {
+                    conveyor_query
+                        .get_component::<Position>(*entity)
+                        .ok()
+                        .map(|position| position.position)
+                })
+                .collect();
+            highlight.send(HighlightTilesUpdateEvent::from_positions(positions));
+        }
+
+        egui::containers::ScrollArea::from_max_height(200.0).show(ui, |ui| {
+            let mut resource_list: Vec<(&String, &String)> = resources
+                .iter()
+                .filter(|(_id, resource)| !resource.virtual_resource)
+                .map(|(id, resource)| (id, &resource.name))
+                .collect();
+            resource_list.sort_by_key(|(_id, name)| name.to_lowercase());
+
+            if ui
+                .radio_value(&mut conveyor.resource, None, "Nothing")
+                .clicked()
+            {
+                resource_changed = true;
+            }
+
+            for (id, name) in resource_list {
+                if ui
+                    .radio_value(&mut conveyor.resource, Some(id.clone()), name.clone())
+                    .clicked()
+                {
+                    resource_changed = true;
+                }
+            }
+        });
+    });
+
+    if direction_changed {
+        if let Ok((mut selected, _position)) = conveyor_query.get_mut(entity) {
+            selected.direction = conveyor.direction;
+        }
+        commands.entity(entity).insert(RequiresUpdate);
+    }
+
+    if resource_changed {
+        for line_entity in line {
+            if let Ok((mut other, _position)) = conveyor_query.get_mut(line_entity) {
+                other.resource = conveyor.resource.clone();
+            }
+        }
+    }
+}
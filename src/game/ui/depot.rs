@@ -7,6 +7,7 @@ use crate::game::{
     car::{Car, CarController, DepotController},
     current_selection::CurrentlySelected,
     highlight_tiles::HighlightTilesUpdateEvent,
+    history::{EditCommand, EditHistory},
     production::{DeliveryStation, Depot},
     setup::{BUILDING_LAYER_ID, MAP_ID},
 };
@@ -29,12 +30,13 @@ pub fn edit_ui(
     egui_context: ResMut<EguiContext>,
     mut depot_query: Query<&mut Depot>,
     delivery_query: Query<(), With<DeliveryStation>>,
-    mut car_query: Query<(&mut Car, &Position)>,
+    mut car_query: Query<(Entity, &mut Car, &Position)>,
     mut currently_selected: ResMut<CurrentlySelected>,
     clicked_tile: Res<ClickedTile>,
     mut edit_mode: Local<EditMode>,
     map_query: MapQuery,
     mut highlight: EventWriter<HighlightTilesUpdateEvent>,
+    mut history: ResMut<EditHistory>,
 ) {
     if !currently_selected.editing {
         return;
@@ -46,6 +48,8 @@ pub fn edit_ui(
 
     if let Some(entity) = currently_selected.entity {
         if let Ok(mut depot) = depot_query.get_mut(entity) {
+            let before = depot.clone();
+
             if clicked_tile.occupied_building {
                 if let Some(pos) = clicked_tile.pos {
                     if let Ok(entity) = map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID) {
@@ -72,13 +76,20 @@ pub fn edit_ui(
                         currently_selected.locked = false;
                         *edit_mode = EditMode::None;
 
-                        for (mut car, position) in car_query.iter_mut() {
+                        for (car_entity, mut car, position) in car_query.iter_mut() {
                             if position.position != pos {
                                 continue;
                             }
 
-                            car.controller =
+                            let controller =
                                 CarController::DepotControlled(DepotController { depot: entity });
+
+                            history.push(EditCommand::CarProgram {
+                                car: car_entity,
+                                before: car.controller.clone(),
+                                after: controller.clone(),
+                            });
+                            car.controller = controller;
                         }
                     }
                 }
@@ -159,6 +170,14 @@ pub fn edit_ui(
                     }
                 });
             });
+
+            if *depot != before {
+                history.push(EditCommand::DepotEdit {
+                    depot: entity,
+                    before,
+                    after: depot.clone(),
+                });
+            }
         }
     }
 }
@@ -0,0 +1,140 @@
+use crate::game::{
+    account::{Ledger, TransactionCategory},
+    assets::{building_specifications::BuildingSpecifications, Building, Name},
+    constants::CURRENCY,
+    production::{ImportExportDirection, ImportExportStation, TownShop},
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use num_format::{Locale, ToFormattedString};
+
+const CATEGORIES: [TransactionCategory; 10] = [
+    TransactionCategory::Export,
+    TransactionCategory::Sales,
+    TransactionCategory::Contract,
+    TransactionCategory::Loan,
+    TransactionCategory::Import,
+    TransactionCategory::ProductionCost,
+    TransactionCategory::Maintenance,
+    TransactionCategory::Construction,
+    TransactionCategory::Vehicle,
+    TransactionCategory::Interest,
+];
+
+pub struct FinanceWindow {
+    pub open: bool,
+    pub days: usize,
+}
+
+impl Default for FinanceWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            days: 1,
+        }
+    }
+}
+
+fn money(amount: f64) -> String {
+    format!(
+        "{} {}",
+        (amount as i64).to_formatted_string(&Locale::en),
+        CURRENCY
+    )
+}
+
+pub fn finance_ui(
+    egui_context: ResMut<EguiContext>,
+    mut window: ResMut<FinanceWindow>,
+    ledger: Res<Ledger>,
+    buildings: Res<BuildingSpecifications>,
+    queries: (
+        Query<&Name>,
+        Query<&Building>,
+        Query<&ImportExportStation>,
+        Query<&TownShop>,
+    ),
+) {
+    let (name_query, building_query, station_query, shop_query) = queries;
+    let mut open = window.open;
+    let summary = ledger.summary(window.days);
+
+    let source_name = |entity: Entity| {
+        if let Ok(name) = name_query.get(entity) {
+            return name.name.clone();
+        }
+
+        if let Ok(building) = building_query.get(entity) {
+            if let Some(building) = buildings.get(&building.id) {
+                return building.name.clone();
+            }
+        }
+
+        if let Ok(station) = station_query.get(entity) {
+            return match station.direction {
+                ImportExportDirection::Export => "Export Station".to_owned(),
+                ImportExportDirection::Import => "Import Station".to_owned(),
+            };
+        }
+
+        if shop_query.get(entity).is_ok() {
+            return "Town Shop".to_owned();
+        }
+
+        "Other".to_owned()
+    };
+
+    egui::Window::new("Finances")
+        .open(&mut open)
+        .show(egui_context.ctx(), |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut window.days, 1, "Today");
+                ui.selectable_value(&mut window.days, 7, "Last 7 days");
+            });
+
+            ui.separator();
+            egui::Grid::new("finance_categories").show(ui, |ui| {
+                for category in CATEGORIES.iter() {
+                    if let Some(amount) = summary.categories.get(category) {
+                        ui.label(category.name());
+                        ui.label(money(*amount));
+                        ui.end_row();
+                    }
+                }
+
+                ui.label("Income");
+                ui.label(money(summary.income()));
+                ui.end_row();
+
+                ui.label("Expenses");
+                ui.label(money(summary.expenses()));
+                ui.end_row();
+
+                ui.label("Profit");
+                ui.label(money(summary.income() + summary.expenses()));
+                ui.end_row();
+            });
+
+            ui.separator();
+            ui.heading("Buildings");
+
+            let mut sources: Vec<(String, f64)> = summary
+                .sources
+                .iter()
+                .map(|(entity, amount)| (source_name(*entity), *amount))
+                .collect();
+            sources.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+            egui::containers::ScrollArea::from_max_height(200.0).show(ui, |ui| {
+                egui::Grid::new("finance_buildings").show(ui, |ui| {
+                    for (name, amount) in sources.iter() {
+                        ui.label(name);
+                        ui.label(money(*amount));
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+
+    window.open = open;
+}
@@ -3,10 +3,15 @@ use crate::game::{
     assets::resource_specifications::ResourceSpecifications,
     constants::{CURRENCY, UNIT},
     goals::GoalManager,
+    outcome::GameOutcome,
+    production::{Town, Workforce},
+    scenario::ScenarioManager,
+    time::GameTime,
+    ui::{bank::BankWindow, construction_queue::ConstructionQueueWindow, finance::FinanceWindow},
 };
 use bevy::prelude::*;
 use bevy_egui::{
-    egui::{self, Align2},
+    egui::{self, Align2, Color32},
     EguiContext,
 };
 use num_format::{Locale, ToFormattedString};
@@ -16,6 +21,14 @@ pub fn goals_ui(
     goals: Res<GoalManager>,
     resources: Res<ResourceSpecifications>,
     account: Res<Account>,
+    workforce: Res<Workforce>,
+    town: Res<Town>,
+    time: Res<GameTime>,
+    scenarios: Res<ScenarioManager>,
+    outcome: Res<GameOutcome>,
+    mut bank_window: ResMut<BankWindow>,
+    mut finance_window: ResMut<FinanceWindow>,
+    mut construction_window: ResMut<ConstructionQueueWindow>,
 ) {
     egui::Window::new(format!(
         "{} {}",
@@ -25,6 +38,48 @@ pub fn goals_ui(
     .anchor(Align2::RIGHT_BOTTOM, [-10.0, -10.0])
     .id(egui::Id::new("account_goals"))
     .show(egui_context.ctx(), |ui| {
+        ui.label(format!("Day {}", time.day() as u64 + 1));
+        // saves from before housing need no workers
+        if workforce.base.is_some() {
+            ui.label(format!(
+                "Workers: {} / {}",
+                workforce.available, workforce.required
+            ));
+        }
+        ui.label(format!(
+            "Town: {:.0} residents, {:.0}% happy",
+            town.population,
+            town.happiness * 100.0
+        ));
+
+        ui.horizontal(|ui| {
+            if ui.small_button("Bank").clicked() {
+                bank_window.open = !bank_window.open;
+            }
+
+            if ui.small_button("Finances").clicked() {
+                finance_window.open = !finance_window.open;
+            }
+
+            if ui.small_button("Construction").clicked() {
+                construction_window.open = !construction_window.open;
+            }
+        });
+
+        if let Some(days) = outcome.days_until_bankruptcy(time.day()) {
+            ui.colored_label(
+                Color32::RED,
+                format!("Bankrupt in {:.0} days!", days.ceil()),
+            );
+        }
+
+        // scenarios list their objectives in their own window
+        if scenarios.active.is_some() {
+            return;
+        }
+
+        ui.separator();
+
         egui::Grid::new("goals").show(ui, |ui| {
             if goals.goals.is_empty() {
                 ui.label("You have reached all goals.");
@@ -4,8 +4,10 @@ use collecting_hashmap::CollectingHashMap;
 
 use crate::game::{
     assets::resource_specifications::ResourceSpecifications,
+    constants::CURRENCY,
     current_selection::CurrentlySelected,
     goals::GoalManager,
+    market::Market,
     production::{ImportExportDirection, ImportExportStation},
 };
 
@@ -15,6 +17,7 @@ pub fn edit_ui(
     currently_selected: Res<CurrentlySelected>,
     resources: Res<ResourceSpecifications>,
     goals: Res<GoalManager>,
+    market: Res<Market>,
 ) {
     if !currently_selected.editing {
         return;
@@ -29,6 +32,7 @@ pub fn edit_ui(
                 ImportExportDirection::Import => "Import Station",
             })
             .show(egui_context.ctx(), |ui| {
+                let all_resources = &*resources;
                 let mut groups = CollectingHashMap::new();
                 for (id, resource) in resources.iter() {
                     if station.direction == ImportExportDirection::Import
@@ -58,15 +62,26 @@ pub fn edit_ui(
                         .id_source(group)
                         .show(ui, |ui| {
                             for (id, resource) in resources.iter() {
+                                let price = match station.direction {
+                                    ImportExportDirection::Export => {
+                                        market.export_price(id, all_resources)
+                                    }
+                                    ImportExportDirection::Import => {
+                                        market.import_price(id, all_resources)
+                                    }
+                                };
+
                                 if ui
                                     .button(format!(
-                                        "{}: {}",
+                                        "{}: {} ({:.0} {})",
                                         resource.name,
                                         if station.goods.contains(id) {
                                             "Yes"
                                         } else {
                                             "No"
-                                        }
+                                        },
+                                        price,
+                                        CURRENCY
                                     ))
                                     .clicked()
                                 {
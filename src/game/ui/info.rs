@@ -5,13 +5,16 @@ use crate::game::{
     assets::{
         building_specifications::BuildingSpecifications,
         resource_specifications::ResourceSpecifications, Building, Editable, InfoUI, Name,
+        Position,
     },
     car::Car,
     construction::UnderConstruction,
+    conveyor::Conveyor,
     current_selection::CurrentlySelected,
-    production::{Depot, ImportExportStation},
+    production::{Depot, Housing, ImportExportStation, TownShop},
     statistics::Statistics,
-    storage::Storage,
+    storage::{Storage, StorageConsolidator, StorageReservation},
+    train::Train,
 };
 
 fn query_resolve<'a, T>(items: &mut Vec<&'a dyn InfoUI>, item: Result<&'a T, QueryEntityError>)
@@ -26,19 +29,24 @@ where
 pub fn info_ui(
     buildings: Res<BuildingSpecifications>,
     egui_context: ResMut<EguiContext>,
-    queries: (
+    mut queries: (
         Query<&Building>,
         Query<&Editable>,
         Query<&Statistics>,
         Query<&Name>,
         Query<&Car>,
-        Query<&Storage>,
+        Query<&mut Storage>,
         Query<&ImportExportStation>,
         Query<&Depot>,
         Query<&UnderConstruction>,
+        Query<&Train>,
+        Query<&Conveyor>,
+        Query<&Housing>,
+        Query<&TownShop>,
     ),
     mut currently_selected: ResMut<CurrentlySelected>,
     resources: Res<ResourceSpecifications>,
+    consolidator_query: Query<(Entity, &StorageConsolidator, &Position)>,
 ) {
     if let Some(entity) = currently_selected.entity {
         let mut items: Vec<&dyn InfoUI> = vec![];
@@ -51,10 +59,50 @@ pub fn info_ui(
 
         query_resolve(&mut items, queries.3.get(entity));
         query_resolve(&mut items, queries.4.get(entity));
-        query_resolve(&mut items, queries.5.get(entity));
+        if let Ok(storage) = queries.5.get_component::<Storage>(entity) {
+            items.push(storage);
+        }
         query_resolve(&mut items, queries.6.get(entity));
         query_resolve(&mut items, queries.7.get(entity));
         query_resolve(&mut items, queries.8.get(entity));
+        query_resolve(&mut items, queries.9.get(entity));
+        query_resolve(&mut items, queries.10.get(entity));
+        query_resolve(&mut items, queries.11.get(entity));
+        query_resolve(&mut items, queries.12.get(entity));
+
+        // buildings a storage tile can be reserved for
+        let storage_settings = match queries.5.get_component::<Storage>(entity) {
+            Ok(storage) if queries.4.get(entity).is_err() => {
+                let mut connected_buildings: Vec<(UVec2, String)> = consolidator_query
+                    .iter()
+                    .filter(|(_entity, consolidator, _position)| {
+                        consolidator.connected_storage.contains(&entity)
+                    })
+                    .map(|(building, _consolidator, position)| {
+                        let name = if let Ok(name) = queries.3.get(building) {
+                            name.name.clone()
+                        } else if let Ok(building) = queries.0.get(building) {
+                            buildings.get(&building.id).unwrap().name.clone()
+                        } else {
+                            "Building".to_owned()
+                        };
+
+                        (
+                            position.position,
+                            format!(
+                                "{} ({}, {})",
+                                name, position.position.x, position.position.y
+                            ),
+                        )
+                    })
+                    .collect();
+                connected_buildings.sort_by(|a, b| a.1.cmp(&b.1));
+
+                Some((storage.clone(), connected_buildings))
+            }
+            _ => None,
+        };
+        let mut changed_settings = None;
 
         if !items.is_empty() {
             egui::SidePanel::left("side_panel")
@@ -66,6 +114,55 @@ pub fn info_ui(
                         item.ui(ui, &resources);
                     }
 
+                    if let Some((storage, connected_buildings)) = &storage_settings {
+                        let mut priority = storage.priority;
+                        let mut reservation = storage.reservation.clone();
+
+                        ui.horizontal(|ui| {
+                            ui.label(format!("Priority: {}", priority));
+                            if ui.small_button("-").clicked() {
+                                priority -= 1;
+                            }
+                            if ui.small_button("+").clicked() {
+                                priority += 1;
+                            }
+                        });
+
+                        egui::CollapsingHeader::new("Reserve").show(ui, |ui| {
+                            if ui.radio(reservation.is_none(), "Nobody").clicked() {
+                                reservation = None;
+                            }
+
+                            for (position, name) in connected_buildings.iter() {
+                                let selected = matches!(
+                                    &reservation,
+                                    Some(reservation) if reservation.building == *position
+                                );
+
+                                if ui.radio(selected, name.clone()).clicked() && !selected {
+                                    reservation = Some(StorageReservation {
+                                        building: *position,
+                                        amount: storage.capacity / 2.0,
+                                    });
+                                }
+                            }
+
+                            if let Some(reservation) = &mut reservation {
+                                ui.add(
+                                    egui::Slider::new(
+                                        &mut reservation.amount,
+                                        0.0..=storage.capacity,
+                                    )
+                                    .text("Amount"),
+                                );
+                            }
+                        });
+
+                        if priority != storage.priority || reservation != storage.reservation {
+                            changed_settings = Some((priority, reservation));
+                        }
+                    }
+
                     if queries.1.get(entity).is_ok() {
                         let label = if currently_selected.editing {
                             "Edit (Close)"
@@ -101,5 +198,12 @@ pub fn info_ui(
                     }
                 });
         }
+
+        if let Some((priority, reservation)) = changed_settings {
+            if let Ok(mut storage) = queries.5.get_mut(entity) {
+                storage.priority = priority;
+                storage.reservation = reservation;
+            }
+        }
     }
 }
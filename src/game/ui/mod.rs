@@ -1,12 +1,23 @@
+pub mod bank;
+pub mod blueprint;
 pub mod car_instructions;
 pub mod construction;
+pub mod construction_queue;
+pub mod contracts;
+pub mod conveyor;
 pub mod depot;
+pub mod finance;
 pub mod goals;
 pub mod import_export_station;
 pub mod info;
 pub mod mouse_pos_to_tile;
 pub mod name;
+pub mod outcome;
 pub mod pause;
 pub mod production_building;
+pub mod scenario;
 pub mod state;
 pub mod statistics;
+pub mod storage;
+pub mod storage_management;
+pub mod train_orders;
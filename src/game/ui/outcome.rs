@@ -0,0 +1,116 @@
+use crate::game::{
+    constants::{CURRENCY, UNIT},
+    outcome::{GameSummary, Outcome},
+    scenario::ScenarioManager,
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Ui},
+    EguiContext,
+};
+use num_format::{Locale, ToFormattedString};
+
+fn summary_grid(ui: &mut Ui, summary: &GameSummary) {
+    egui::Grid::new("game_summary").show(ui, |ui| {
+        ui.label("Days played");
+        ui.label(format!("{:.0}", summary.days.ceil()));
+        ui.end_row();
+
+        ui.label("Balance");
+        ui.label(format!(
+            "{} {}",
+            summary.balance.to_formatted_string(&Locale::en),
+            CURRENCY
+        ));
+        ui.end_row();
+
+        ui.label("Exported");
+        ui.label(format!(
+            "{}{}",
+            (summary.exported as i64).to_formatted_string(&Locale::en),
+            UNIT
+        ));
+        ui.end_row();
+
+        ui.label("Produced");
+        ui.label(format!(
+            "{}{}",
+            (summary.produced as i64).to_formatted_string(&Locale::en),
+            UNIT
+        ));
+        ui.end_row();
+
+        ui.label("Buildings");
+        ui.label(summary.buildings.to_string());
+        ui.end_row();
+    });
+}
+
+pub fn victory_ui(
+    egui_context: ResMut<EguiContext>,
+    mut app_state: ResMut<State<AppState>>,
+    mut scenarios: ResMut<ScenarioManager>,
+    summary: Res<GameSummary>,
+) {
+    let has_next = scenarios.next().is_some();
+
+    egui::Window::new("Victory")
+        .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_width(200.0)
+        .resizable(false)
+        .collapsible(false)
+        .show(egui_context.ctx(), |ui| {
+            ui.heading(match scenarios.current() {
+                Some(scenario) => format!("You have completed {}!", scenario.name),
+                None => "You have reached all goals!".to_owned(),
+            });
+            ui.separator();
+
+            summary_grid(ui, &summary);
+
+            ui.separator();
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Continue Playing").clicked() {
+                    let _ = app_state.pop();
+                }
+
+                if has_next && ui.button("Continue Campaign").clicked() {
+                    scenarios.show_campaign = true;
+                    let _ = app_state.overwrite_replace(AppState::MainMenu);
+                }
+
+                if ui.button("Back to Menu").clicked() {
+                    let _ = app_state.overwrite_replace(AppState::MainMenu);
+                }
+            });
+        });
+}
+
+pub fn game_over_ui(
+    egui_context: ResMut<EguiContext>,
+    mut app_state: ResMut<State<AppState>>,
+    summary: Res<GameSummary>,
+) {
+    egui::Window::new("Game Over")
+        .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_width(200.0)
+        .resizable(false)
+        .collapsible(false)
+        .show(egui_context.ctx(), |ui| {
+            ui.heading(match summary.outcome {
+                Some(Outcome::ScenarioFailed) => "You have failed the scenario.",
+                _ => "You went bankrupt.",
+            });
+            ui.separator();
+
+            summary_grid(ui, &summary);
+
+            ui.separator();
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Back to Menu").clicked() {
+                    let _ = app_state.overwrite_replace(AppState::MainMenu);
+                }
+            });
+        });
+}
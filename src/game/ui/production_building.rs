@@ -47,6 +47,15 @@ pub fn edit_ui(
             let building = buildings.get(&building.id).unwrap();
 
             egui::Window::new(&building.name).show(egui_context.ctx(), |ui| {
+                ui.checkbox(&mut production.mothballed, "Mothball");
+                if let Some(maintenance) = &building.maintenance {
+                    ui.label(format!(
+                        "Maintenance: {} {} active, {} {} idle",
+                        maintenance.active, CURRENCY, maintenance.idle, CURRENCY
+                    ));
+                }
+                ui.separator();
+
                 for (product, active) in &mut production.products {
                     let resource = resources.get(&product.resource).unwrap();
 
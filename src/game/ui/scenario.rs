@@ -0,0 +1,56 @@
+use crate::game::{
+    assets::{
+        building_specifications::BuildingSpecifications,
+        resource_specifications::ResourceSpecifications,
+    },
+    scenario::ScenarioManager,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContext,
+};
+use num_format::{Locale, ToFormattedString};
+
+pub fn scenario_ui(
+    egui_context: ResMut<EguiContext>,
+    scenarios: Res<ScenarioManager>,
+    resources: Res<ResourceSpecifications>,
+    buildings: Res<BuildingSpecifications>,
+) {
+    let (scenario, active) = match (scenarios.current(), &scenarios.active) {
+        (Some(scenario), Some(active)) => (scenario, active),
+        _ => return,
+    };
+
+    egui::Window::new(scenario.name.as_str())
+        .anchor(Align2::CENTER_TOP, [0.0, 10.0])
+        .id(egui::Id::new("scenario"))
+        .show(egui_context.ctx(), |ui| {
+            egui::Grid::new("scenario_objectives").show(ui, |ui| {
+                for (index, objective) in scenario.objectives.iter().enumerate() {
+                    ui.label(objective.description(&resources, &buildings));
+                    if index < active.objective {
+                        ui.label("Done");
+                    } else if index == active.objective {
+                        ui.label(format!(
+                            "{} / {}",
+                            (active.current as i64).to_formatted_string(&Locale::en),
+                            (objective.target() as i64).to_formatted_string(&Locale::en),
+                        ));
+                    } else {
+                        ui.label("-");
+                    }
+                    ui.end_row();
+                }
+            });
+
+            if !scenario.lose.is_empty() {
+                ui.separator();
+                ui.label("You lose with:");
+                for condition in scenario.lose.iter() {
+                    ui.label(condition.description());
+                }
+            }
+        });
+}
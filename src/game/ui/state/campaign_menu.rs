@@ -0,0 +1,80 @@
+use crate::game::{
+    scenario::{Scenario, ScenarioManager},
+    state_manager::NewGameEvent,
+    ui::state::MainMenuState,
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Ui},
+    EguiContext,
+};
+
+fn scenario_button(ui: &mut Ui, scenario: &Scenario, unlocked: bool) -> bool {
+    if !unlocked {
+        ui.label(format!("{} (locked)", scenario.name));
+        return false;
+    }
+
+    ui.button(scenario.name.as_str())
+        .on_hover_text(scenario.description.as_str())
+        .clicked()
+}
+
+pub fn campaign_menu(
+    mut commands: Commands,
+    mut new_game: EventWriter<NewGameEvent>,
+    mut app_state: ResMut<State<AppState>>,
+    mut menu_state: ResMut<State<MainMenuState>>,
+    egui_context: ResMut<EguiContext>,
+    mut scenarios: ResMut<ScenarioManager>,
+) {
+    let mut selected = None;
+
+    egui::Window::new("Campaign")
+        .default_width(200.0)
+        .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(egui_context.ctx(), |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for campaign in scenarios.campaigns.iter() {
+                    ui.heading(campaign.name.as_str());
+
+                    for (index, id) in campaign.scenarios.iter().enumerate() {
+                        if let Some(scenario) = scenarios.get(id) {
+                            let unlocked = scenarios.is_unlocked(campaign, index);
+                            if scenario_button(ui, scenario, unlocked) {
+                                selected = Some((id.clone(), Some(campaign.id.clone())));
+                            }
+                        }
+                    }
+
+                    ui.separator();
+                }
+
+                ui.heading("Scenarios");
+                for scenario in scenarios.scenarios.iter() {
+                    if scenario_button(ui, scenario, true) {
+                        selected = Some((scenario.id.clone(), None));
+                    }
+                }
+
+                ui.separator();
+
+                if ui.button("Abort").clicked() {
+                    menu_state.pop().unwrap();
+                }
+            });
+        });
+
+    if let Some((id, campaign)) = selected {
+        if let Some(settings) = scenarios.start(&id, campaign.as_deref()) {
+            commands.insert_resource(settings);
+
+            new_game.send(NewGameEvent);
+            app_state.push(AppState::InGame).unwrap();
+            menu_state.pop().unwrap();
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::game::{
+    scenario::ScenarioManager,
     ui::state::{ConfirmDialogState, MainMenuState},
     AppState,
 };
@@ -13,7 +14,14 @@ pub fn main_menu(
     mut menu_state: ResMut<State<MainMenuState>>,
     egui_context: ResMut<EguiContext>,
     mut confirm_dialog: ResMut<ConfirmDialogState>,
+    mut scenarios: ResMut<ScenarioManager>,
 ) {
+    if scenarios.show_campaign {
+        scenarios.show_campaign = false;
+        let _ = menu_state.push(MainMenuState::Campaign);
+        return;
+    }
+
     if let AppState::MainMenu = app_state.current() {
         egui::Window::new("Game")
             .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
@@ -27,6 +35,10 @@ pub fn main_menu(
                             menu_state.push(MainMenuState::New).unwrap();
                         }
 
+                        if ui.button("Campaign").clicked() {
+                            menu_state.push(MainMenuState::Campaign).unwrap();
+                        }
+
                         if ui.button("Load Game").clicked() {
                             menu_state.push(MainMenuState::Load).unwrap();
                         }
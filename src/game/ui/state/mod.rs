@@ -2,11 +2,13 @@ mod emit_load_game;
 mod get_state_name;
 mod save_game_list;
 
+mod campaign_menu;
 mod confirm_dialog;
 mod load_save_game_menu;
 mod main_menu;
 mod new_game_menu;
 
+pub use campaign_menu::campaign_menu;
 pub use confirm_dialog::confirm_dialog;
 pub use load_save_game_menu::load_save_game_menu;
 pub use main_menu::main_menu;
@@ -32,6 +34,7 @@ pub enum MainMenuState {
     None,
     Main,
     New,
+    Campaign,
     Load,
     Save,
     ConfirmDialog,
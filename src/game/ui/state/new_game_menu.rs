@@ -1,5 +1,6 @@
 use crate::game::{
     assets::{MapSettings, MapSize},
+    scenario::ScenarioManager,
     state_manager::NewGameEvent,
     ui::state::MainMenuState,
     AppState,
@@ -16,6 +17,7 @@ pub fn new_game_menu(
     mut app_state: ResMut<State<AppState>>,
     mut menu_state: ResMut<State<MainMenuState>>,
     egui_context: ResMut<EguiContext>,
+    mut scenarios: ResMut<ScenarioManager>,
 ) {
     egui::Window::new("New Game")
         .default_width(100.0)
@@ -25,11 +27,8 @@ pub fn new_game_menu(
         .show(egui_context.ctx(), |ui| {
             ui.vertical_centered_justified(|ui| {
                 if ui.button("Size: Small").clicked() {
-                    commands.insert_resource(MapSettings {
-                        width: 3,
-                        height: 3,
-                        size: MapSize::Small,
-                    });
+                    commands.insert_resource(MapSettings::from_size(MapSize::Small));
+                    scenarios.active = None;
 
                     new_game.send(NewGameEvent);
                     app_state.push(AppState::InGame).unwrap();
@@ -37,11 +36,8 @@ pub fn new_game_menu(
                 }
 
                 if ui.button("Size: Medium").clicked() {
-                    commands.insert_resource(MapSettings {
-                        width: 5,
-                        height: 5,
-                        size: MapSize::Medium,
-                    });
+                    commands.insert_resource(MapSettings::from_size(MapSize::Medium));
+                    scenarios.active = None;
 
                     new_game.send(NewGameEvent);
                     app_state.push(AppState::InGame).unwrap();
@@ -49,11 +45,8 @@ pub fn new_game_menu(
                 }
 
                 if ui.button("Size Large").clicked() {
-                    commands.insert_resource(MapSettings {
-                        width: 8,
-                        height: 8,
-                        size: MapSize::Large,
-                    });
+                    commands.insert_resource(MapSettings::from_size(MapSize::Large));
+                    scenarios.active = None;
 
                     new_game.send(NewGameEvent);
                     app_state.push(AppState::InGame).unwrap();
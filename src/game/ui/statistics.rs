@@ -58,6 +58,8 @@ pub fn statistics_ui(
                     && !group("Imported", &statistics.import, &resources, ui)
                     && !group("Production", &statistics.production, &resources, ui)
                     && !group("Consumption", &statistics.consumption, &resources, ui)
+                    && !group("Spoiled", &statistics.spoiled, &resources, ui)
+                    && !group("Sold", &statistics.sold, &resources, ui)
                 {
                     ui.label("No statistics");
                 }
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::game::{
+    account::{Account, AccountTransaction, MaintenanceCost, PurchaseCost, TransactionCategory},
+    assets::{resource_specifications::ResourceSpecifications, RequiresUpdate},
+    car::Car,
+    constants::{CURRENCY, UNIT},
+    construction::UnderConstruction,
+    current_selection::CurrentlySelected,
+    storage::Storage,
+};
+
+const UPGRADE_RESOURCE: &str = "steel.slabs";
+
+pub fn edit_ui(
+    mut commands: Commands,
+    egui_context: ResMut<EguiContext>,
+    mut storage_query: Query<(&mut Storage, Option<&UnderConstruction>), Without<Car>>,
+    currently_selected: Res<CurrentlySelected>,
+    resources: Res<ResourceSpecifications>,
+    account: Res<Account>,
+    mut events: EventWriter<AccountTransaction>,
+) {
+    if !currently_selected.editing {
+        return;
+    }
+
+    let entity = match currently_selected.entity {
+        Some(entity) => entity,
+        None => return,
+    };
+
+    let (mut storage, under_construction) = match storage_query.get_mut(entity) {
+        Ok(result) => result,
+        Err(_) => return,
+    };
+
+    let current = resources.get(&storage.resource);
+
+    let mut resource_list: Vec<(&String, &String)> = match current {
+        Some(current) if !storage.is_warehouse() => resources
+            .iter()
+            .filter(|(id, resource)| {
+                **id != storage.resource
+                    && resource.group == current.group
+                    && resource.liquid == current.liquid
+                    && !resource.virtual_resource
+            })
+            .map(|(id, resource)| (id, &resource.name))
+            .collect(),
+        _ => vec![],
+    };
+    resource_list.sort_by_key(|(_id, name)| name.to_lowercase());
+
+    let mut retarget = None;
+    let mut upgrade = false;
+
+    egui::Window::new("Storage").show(egui_context.ctx(), |ui| {
+        ui.label(format!("Tier {}", storage.tier + 1));
+
+        if under_construction.is_some() {
+            ui.label("Upgrade under construction");
+        } else if let Some(tier) = storage.next_tier() {
+            let capacity = storage.base_capacity() * tier.capacity;
+
+            if ui
+                .button(format!("Upgrade to {:.0}{}", capacity, UNIT))
+                .on_hover_text(format!(
+                    "Labor worth {} {} and {} {}",
+                    tier.labor, CURRENCY, tier.steel_slabs, UNIT
+                ))
+                .clicked()
+            {
+                upgrade = true;
+            }
+        }
+
+        let current = match current {
+            Some(current) if !storage.is_warehouse() => current,
+            _ => return,
+        };
+
+        ui.separator();
+
+        if !storage.can_retarget() {
+            ui.label("Only empty storage can be switched to another resource");
+            return;
+        }
+
+        ui.label(format!("Switch {} storage to", current.name));
+
+        egui::containers::ScrollArea::from_max_height(200.0).show(ui, |ui| {
+            for (id, name) in resource_list {
+                let mut target = storage.clone();
+                target.resource = id.clone();
+                let difference = target.price(&resources) - storage.price(&resources);
+
+                if ui
+                    .small_button(name.clone())
+                    .on_hover_text(format!("Price: {}", difference.max(0)))
+                    .clicked()
+                    && account.value >= difference
+                {
+                    retarget = Some((id.clone(), difference));
+                }
+            }
+        });
+    });
+
+    if upgrade {
+        if let Some(tier) = storage.next_tier() {
+            let mut resources_needed = HashMap::new();
+            resources_needed.insert(UPGRADE_RESOURCE.to_owned(), tier.steel_slabs);
+
+            // the new tier only applies once the upgrade is finished
+            let next = storage.tier + 1;
+
+            let mut upgraded = storage.clone();
+            upgraded.capacity = upgraded.tier_capacity(next);
+
+            commands
+                .entity(entity)
+                .insert(UnderConstruction::upgrade(
+                    resources_needed,
+                    tier.labor,
+                    next,
+                ))
+                .insert(MaintenanceCost::new_from_cost(upgraded.price(&resources)))
+                .insert(RequiresUpdate);
+        }
+    }
+
+    if let Some((resource, difference)) = retarget {
+        if difference > 0 {
+            events.send(AccountTransaction {
+                amount: -difference,
+                category: TransactionCategory::Construction,
+                source: Some(entity),
+            });
+        }
+
+        storage.resource = resource;
+
+        let price = storage.price(&resources);
+        commands
+            .entity(entity)
+            .insert(RequiresUpdate)
+            .insert(MaintenanceCost::new_from_cost(price));
+    }
+}
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::game::{
+    assets::{resource_specifications::ResourceSpecifications, Position},
+    current_selection::CurrentlySelected,
+    highlight_tiles::HighlightTilesUpdateEvent,
+    production::{StorageManagement, StorageManagementMode},
+    storage::{Storage, StorageConsolidator},
+};
+
+pub fn edit_ui(
+    egui_context: ResMut<EguiContext>,
+    mut management_query: Query<(&mut StorageManagement, &StorageConsolidator)>,
+    storage_query: Query<(&Storage, &Position)>,
+    currently_selected: Res<CurrentlySelected>,
+    resources: Res<ResourceSpecifications>,
+    mut highlight: EventWriter<HighlightTilesUpdateEvent>,
+) {
+    if !currently_selected.editing {
+        return;
+    }
+
+    let entity = match currently_selected.entity {
+        Some(entity) => entity,
+        None => return,
+    };
+
+    let (mut management, consolidator) = match management_query.get_mut(entity) {
+        Ok(result) => result,
+        Err(_) => return,
+    };
+
+    let mut connected: Vec<(String, UVec2)> = consolidator
+        .connected_storage
+        .iter()
+        .filter_map(|entity| storage_query.get(*entity).ok())
+        .filter(|(storage, _position)| !storage.is_warehouse())
+        .map(|(storage, position)| {
+            let name = resources
+                .get(&storage.resource)
+                .map(|resource| resource.name.clone())
+                .unwrap_or_default();
+
+            (name, position.position)
+        })
+        .collect();
+    connected.sort_by(|a, b| a.0.cmp(&b.0));
+
+    egui::Window::new("Storage Management").show(egui_context.ctx(), |ui| {
+        ui.label("Mode");
+        for mode in [
+            StorageManagementMode::Equalize,
+            StorageManagementMode::FillTargetFirst,
+            StorageManagementMode::KeepPercentage,
+            StorageManagementMode::InputToOutput,
+        ]
+        .iter()
+        {
+            ui.radio_value(&mut management.mode, *mode, mode.name());
+        }
+
+        ui.separator();
+
+        if management.mode == StorageManagementMode::KeepPercentage {
+            ui.add(egui::Slider::new(&mut management.percentage, 0.0..=1.0).text("Percentage"));
+        }
+
+        ui.add(egui::Slider::new(&mut management.transfers_per_tick, 1..=10).text("Transfers"));
+
+        if management.mode == StorageManagementMode::Equalize {
+            return;
+        }
+
+        ui.separator();
+
+        egui::Grid::new("storage_management_roles").show(ui, |ui| {
+            for (name, position) in connected.iter() {
+                let label = ui.label(format!("{} ({}, {})", name, position.x, position.y));
+                if label.hovered() {
+                    highlight.send(HighlightTilesUpdateEvent::from_position(*position));
+                }
+
+                let is_target = management.targets.contains(position);
+                let is_input = management.inputs.contains(position);
+
+                if ui.radio(is_target, "Target").clicked() {
+                    management.inputs.remove(position);
+                    management.targets.insert(*position);
+                }
+
+                if management.mode == StorageManagementMode::InputToOutput
+                    && ui.radio(is_input, "Input").clicked()
+                {
+                    management.targets.remove(position);
+                    management.inputs.insert(*position);
+                }
+
+                if ui.radio(!is_target && !is_input, "None").clicked() {
+                    management.targets.remove(position);
+                    management.inputs.remove(position);
+                }
+
+                ui.end_row();
+            }
+        });
+    });
+}
@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::MapQuery;
+use bevy_egui::{egui, EguiContext};
+
+use crate::game::{
+    account::{Account, AccountTransaction, MaintenanceCost, PurchaseCost, TransactionCategory},
+    assets::{
+        resource_specifications::{ResourceSpecification, ResourceSpecifications},
+        ClickedTile,
+    },
+    car::{Destination, Waypoints},
+    current_selection::CurrentlySelected,
+    highlight_tiles::HighlightTilesUpdateEvent,
+    production::TrainStation,
+    setup::{BUILDING_LAYER_ID, MAP_ID},
+    storage::Storage,
+    train::{Train, TrainOrder, MAX_WAGONS},
+};
+
+fn wagon_name(resource: &ResourceSpecification) -> String {
+    if resource.liquid {
+        format!("{} Tank Wagon", resource.name)
+    } else {
+        resource.name.clone()
+    }
+}
+
+pub fn orders_ui(
+    mut commands: Commands,
+    egui_context: ResMut<EguiContext>,
+    mut train_query: Query<&mut Train>,
+    station_query: Query<(), With<TrainStation>>,
+    mut currently_selected: ResMut<CurrentlySelected>,
+    clicked_tile: Res<ClickedTile>,
+    mut select_station: Local<bool>,
+    map_query: MapQuery,
+    resources: Res<ResourceSpecifications>,
+    account: Res<Account>,
+    mut events: EventWriter<AccountTransaction>,
+    mut highlight: EventWriter<HighlightTilesUpdateEvent>,
+) {
+    if !currently_selected.editing {
+        return;
+    }
+
+    if !*select_station && currently_selected.locked {
+        return;
+    }
+
+    if let Some(entity) = currently_selected.entity {
+        if let Ok(mut train) = train_query.get_mut(entity) {
+            if *select_station && clicked_tile.occupied_building {
+                if let Some(pos) = clicked_tile.pos {
+                    if let Ok(tile_entity) =
+                        map_query.get_tile_entity(pos, MAP_ID, BUILDING_LAYER_ID)
+                    {
+                        if station_query.get(tile_entity).is_ok() {
+                            currently_selected.locked = false;
+                            *select_station = false;
+                            train.orders.push(TrainOrder::GoTo(pos));
+                        }
+                    }
+                }
+            }
+
+            let mut resources_in_wagons: Vec<String> = train
+                .wagons
+                .iter()
+                .map(|wagon| wagon.resource.clone())
+                .collect();
+            resources_in_wagons.sort();
+            resources_in_wagons.dedup();
+
+            egui::Window::new("Train").show(egui_context.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    if train.active {
+                        if ui.button("Deactivate").clicked() {
+                            train.active = false;
+                        }
+                    } else if ui.button("Activate").clicked() {
+                        commands
+                            .entity(entity)
+                            .remove::<Waypoints>()
+                            .remove::<Destination>();
+                        train.active = true;
+                    }
+                });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new("Orders")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let orders = train.orders.clone();
+                        egui::Grid::new("train_orders").show(ui, |ui| {
+                            for (index, order) in orders.iter().enumerate() {
+                                let label = if index == train.current_order {
+                                    format!("> {}", order.format(&resources))
+                                } else {
+                                    order.format(&resources)
+                                };
+
+                                if ui.label(label).hovered() {
+                                    if let TrainOrder::GoTo(position) = order {
+                                        highlight.send(HighlightTilesUpdateEvent::from_position(
+                                            *position,
+                                        ));
+                                    }
+                                }
+
+                                if ui.small_button("Delete").clicked() {
+                                    train.orders.remove(index);
+                                    if train.current_order >= train.orders.len() {
+                                        train.current_order = 0;
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                        ui.separator();
+
+                        if *select_station {
+                            if ui.button("Abort selection").clicked() {
+                                *select_station = false;
+                                currently_selected.locked = false;
+                            }
+                        } else if ui.button("Go to station").clicked() {
+                            *select_station = true;
+                            currently_selected.locked = true;
+                        }
+
+                        for resource in resources_in_wagons.iter() {
+                            let name = &resources.get(resource).unwrap().name;
+
+                            ui.horizontal(|ui| {
+                                if ui.small_button(format!("Load {}", name)).clicked() {
+                                    train.orders.push(TrainOrder::Load(resource.clone()));
+                                }
+                                if ui.small_button(format!("Unload {}", name)).clicked() {
+                                    train.orders.push(TrainOrder::Unload(resource.clone()));
+                                }
+                            });
+                        }
+                    });
+
+                egui::CollapsingHeader::new("Wagons").show(ui, |ui| {
+                    let mut removed_wagon = None;
+                    for (index, wagon) in train.wagons.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(resources.get(&wagon.resource).unwrap().name.clone());
+
+                            if wagon.is_empty() && ui.small_button("Remove").clicked() {
+                                removed_wagon = Some(index);
+                            }
+                        });
+                    }
+
+                    if let Some(index) = removed_wagon {
+                        train.wagons.remove(index);
+                        let price = train.price(&resources);
+                        commands
+                            .entity(entity)
+                            .insert(MaintenanceCost::new_from_cost(price));
+                    }
+
+                    if train.wagons.len() >= MAX_WAGONS {
+                        return;
+                    }
+
+                    egui::CollapsingHeader::new("Add wagon").show(ui, |ui| {
+                        egui::containers::ScrollArea::from_max_height(200.0).show(ui, |ui| {
+                            let mut resource_list: Vec<(&String, String, Storage)> = resources
+                                .iter()
+                                .filter(|(_id, resource)| !resource.virtual_resource)
+                                .map(|(id, resource)| {
+                                    (id, wagon_name(resource), Train::new_wagon(id, &resources))
+                                })
+                                .filter(|(id, _name, wagon)| wagon.accepts(id, &resources))
+                                .collect();
+                            resource_list.sort_by_key(|(_id, name, _wagon)| name.to_lowercase());
+
+                            for (_id, name, wagon) in resource_list {
+                                let price = Train::wagon_price(&wagon, &resources);
+
+                                if ui
+                                    .small_button(name)
+                                    .on_hover_text(format!("Price: {}", price))
+                                    .clicked()
+                                    && account.value >= price
+                                {
+                                    events.send(AccountTransaction {
+                                        amount: -price,
+                                        category: TransactionCategory::Vehicle,
+                                        source: Some(entity),
+                                    });
+                                    train.wagons.push(wagon);
+                                    let price = train.price(&resources);
+                                    commands
+                                        .entity(entity)
+                                        .insert(MaintenanceCost::new_from_cost(price));
+                                }
+                            }
+                        });
+                    });
+                });
+            });
+        } else if *select_station {
+            *select_station = false;
+            currently_selected.locked = false;
+        }
+    }
+}